@@ -0,0 +1,82 @@
+//! Decoding and paging helpers for clients and indexers reading program accounts in bulk.
+//!
+//! ```
+//! use blocks_contracts::client::decode_profiles;
+//! use solana_program::pubkey::Pubkey;
+//!
+//! // e.g. the keys and data returned by `getMultipleAccounts`
+//! let fetched = vec![(Pubkey::new_unique(), vec![0; 16])];
+//! for (address, profile) in decode_profiles(&fetched) {
+//!     match profile {
+//!         Ok(profile) => println!("{address}: @{}", profile.username),
+//!         Err(error) => println!("{address}: undecodable ({error})"),
+//!     }
+//! }
+//! ```
+
+use crate::state::{unpack_profile_from_slice, AccountKind, Post, Profile};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use std::cmp::Reverse;
+
+/// Decode a batch of fetched profile accounts, pairing each result with its address.
+///
+/// Uninitialized entries (empty or zeroed data, as left by a missing or closed account) are
+/// skipped; anything else that isn't a valid profile is returned as an error.
+pub fn decode_profiles(accounts: &[(Pubkey, Vec<u8>)]) -> Vec<(Pubkey, Result<Profile, ProgramError>)> {
+    accounts
+        .iter()
+        .filter(|(_, data)| !is_uninitialized(data))
+        .map(|(address, data)| (*address, unpack_profile_from_slice(data)))
+        .collect()
+}
+
+fn is_uninitialized(data: &[u8]) -> bool {
+    data.first().is_none_or(|&kind| kind == AccountKind::Uninitialized as u8)
+}
+
+/// Position reached while paging a feed newest first: the last post a page returned.
+///
+/// Posts are ordered by `(timestamp, id)`, so posts published after a cursor was taken sort
+/// above it and never shift later pages; each page resumes strictly below the cursor.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeedCursor {
+    pub last_post_id: u64,
+    pub last_timestamp: u64,
+}
+
+impl FeedCursor {
+    /// Cursor positioned at `post`
+    pub fn at(post: &Post) -> Self {
+        FeedCursor { last_post_id: post.id, last_timestamp: post.timestamp }
+    }
+
+    /// Whether `post` sorts below the cursor, i.e. belongs to a later page
+    pub fn admits(&self, post: &Post) -> bool {
+        (post.timestamp, post.id) < (self.last_timestamp, self.last_post_id)
+    }
+}
+
+/// One page of a feed and the cursor to request the next one with.
+pub struct FeedPage<'a> {
+    pub posts: Vec<&'a (Pubkey, Post)>,
+    /// `None` once the feed is exhausted
+    pub next: Option<FeedCursor>,
+}
+
+/// Take the next `limit` (at least one) posts below `cursor`, or from the top when it is `None`.
+///
+/// `posts` are the decoded accounts of a `PostIndex` or `CommunityFeed`, in any order.
+/// Soft-deleted posts are skipped.
+pub fn feed_page(posts: &[(Pubkey, Post)], cursor: Option<FeedCursor>, limit: usize) -> FeedPage<'_> {
+    let mut page: Vec<&(Pubkey, Post)> = posts
+        .iter()
+        .filter(|(_, post)| !post.is_deleted && cursor.is_none_or(|cursor| cursor.admits(post)))
+        .collect();
+    page.sort_by_key(|(_, post)| Reverse((post.timestamp, post.id)));
+
+    let limit = limit.max(1);
+    let next = if page.len() > limit { Some(FeedCursor::at(&page[limit - 1].1)) } else { None };
+    page.truncate(limit);
+    FeedPage { posts: page, next }
+}
@@ -2,64 +2,170 @@
 use solana_program::program_error::ProgramError;
 use thiserror::Error;
 
+// Each variant's discriminant is the code clients see as `ProgramError::Custom(code)`.
+// Codes are part of the program's interface: never renumber or reuse one, and give new
+// variants the next unused number.
 #[derive(Error, Debug, Copy, Clone)]
+#[repr(u32)]
 pub enum BlocksError {
     #[error("Invalid Instruction")]
-    InvalidInstruction,
+    InvalidInstruction = 0,
 
     #[error("Not Rent Exempt")]
-    NotRentExempt,
+    NotRentExempt = 1,
 
     #[error("Profile Already Exists")]
-    ProfileAlreadyExists,
+    ProfileAlreadyExists = 2,
 
     #[error("Profile Not Found")]
-    ProfileNotFound,
+    ProfileNotFound = 3,
 
     #[error("Post Not Found")]
-    PostNotFound,
+    PostNotFound = 4,
 
     #[error("Community Not Found")]
-    CommunityNotFound,
+    CommunityNotFound = 5,
 
     #[error("Not Profile Owner")]
-    NotProfileOwner,
+    NotProfileOwner = 6,
 
     #[error("Not Post Owner")]
-    NotPostOwner,
+    NotPostOwner = 7,
 
     #[error("Not Community Owner")]
-    NotCommunityOwner,
+    NotCommunityOwner = 8,
     
     #[error("Invalid Community Name")]
-    InvalidCommunityName,
+    InvalidCommunityName = 9,
     
     #[error("Already Member")]
-    AlreadyMember,
+    AlreadyMember = 10,
     
     #[error("Community Limit Exceeded")]
-    CommunityLimitExceeded,
+    CommunityLimitExceeded = 11,
     
     #[error("Daily Post Limit Reached")]
-    DailyPostLimitReached,
+    DailyPostLimitReached = 12,
     
     #[error("Post Time Limit")]
-    PostTimeLimit,
+    PostTimeLimit = 13,
     
     #[error("Spam User")]
-    SpamUser,
+    SpamUser = 14,
     
     #[error("Already Liked")]
-    AlreadyLiked,
+    AlreadyLiked = 15,
     
     #[error("Already Disliked")]
-    AlreadyDisliked,
+    AlreadyDisliked = 16,
     
     #[error("Post In Kill Zone")]
-    PostInKillZone,
+    PostInKillZone = 17,
     
     #[error("Insufficient Funds")]
-    InsufficientFunds,
+    InsufficientFunds = 18,
+    
+    #[error("Verification Threshold Not Met")]
+    VerificationThresholdNotMet = 19,
+    
+    #[error("Profile Not Empty")]
+    ProfileNotEmpty = 20,
+    
+    #[error("Content Too Long")]
+    ContentTooLong = 21,
+    
+    #[error("User Blocked")]
+    UserBlocked = 22,
+    
+    #[error("Already Following")]
+    AlreadyFollowing = 23,
+    
+    #[error("Not Following")]
+    NotFollowing = 24,
+    
+    #[error("Arithmetic Overflow")]
+    ArithmeticOverflow = 25,
+    
+    #[error("Already Reported")]
+    AlreadyReported = 26,
+    
+    #[error("Field Too Long")]
+    FieldTooLong = 27,
+    
+    #[error("Comment Limit Reached")]
+    CommentLimitReached = 28,
+    
+    #[error("Batch Too Large")]
+    BatchTooLarge = 29,
+    
+    #[error("Unauthorized")]
+    Unauthorized = 30,
+    
+    #[error("Invalid Image Uri")]
+    InvalidImageUri = 31,
+    
+    #[error("Not A Member")]
+    NotAMember = 32,
+    
+    #[error("Already Bookmarked")]
+    AlreadyBookmarked = 33,
+    
+    #[error("Bookmark Not Found")]
+    BookmarkNotFound = 34,
+    
+    #[error("Member Index Page Full")]
+    MemberIndexPageFull = 35,
+    
+    #[error("Follow Index Page Full")]
+    FollowIndexPageFull = 36,
+    
+    #[error("Edit Window Expired")]
+    EditWindowExpired = 37,
+    
+    #[error("Reply Depth Exceeded")]
+    ReplyDepthExceeded = 38,
+    
+    #[error("Account Not Writable")]
+    AccountNotWritable = 39,
+    
+    #[error("Muted Words Full")]
+    MutedWordsFull = 40,
+    
+    #[error("Already Muted")]
+    AlreadyMuted = 41,
+    
+    #[error("Muted Word Not Found")]
+    MutedWordNotFound = 42,
+    
+    #[error("Account Still Referenced")]
+    AccountStillReferenced = 43,
+
+    #[error("Already Deleted")]
+    AlreadyDeleted = 44,
+
+    #[error("Comments Disabled")]
+    CommentsDisabled = 45,
+
+    #[error("Ucr Below Community Minimum")]
+    UcrBelowCommunityMinimum = 46,
+
+    #[error("Verification Required")]
+    VerificationRequired = 47,
+
+    #[error("Follow Request Pending")]
+    FollowRequestPending = 48,
+
+    #[error("Follow Request Not Found")]
+    FollowRequestNotFound = 49,
+
+    #[error("Community Already Exists")]
+    CommunityAlreadyExists = 50,
+
+    #[error("Duplicate Content")]
+    DuplicateContent = 51,
+
+    #[error("Nested Quote")]
+    NestedQuote = 52,
 }
 
 impl From<BlocksError> for ProgramError {
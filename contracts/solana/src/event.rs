@@ -0,0 +1,220 @@
+//! Structured events logged through `sol_log_data` for indexers.
+//!
+//! Each event is a single borsh-serialized `Event` in the transaction's
+//! `Program data:` log lines; decode it with `Event::try_from_slice`.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use crate::state::{CommunityPostRules, PostRating, RatingConfig, VerificationLevel};
+use solana_program::{log::sol_log_data, program_error::ProgramError, pubkey::Pubkey};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum Event {
+    ProfileCreated {
+        profile: Pubkey,
+        owner: Pubkey,
+    },
+    ProfileUpdated {
+        profile: Pubkey,
+    },
+    PostCreated {
+        post: Pubkey,
+        author: Pubkey,
+        id: u64,
+    },
+    PostLiked {
+        post: Pubkey,
+        liker: Pubkey,
+        likes: u64,
+    },
+    CommentCreated {
+        comment: Pubkey,
+        parent: Pubkey,
+        author: Pubkey,
+    },
+    ProfileFollowed {
+        follower: Pubkey,
+        followed: Pubkey,
+    },
+    ProfileUnfollowed {
+        follower: Pubkey,
+        followed: Pubkey,
+    },
+    CommunityCreated {
+        community: Pubkey,
+        owner: Pubkey,
+    },
+    CommunityJoined {
+        community: Pubkey,
+        user: Pubkey,
+    },
+    CommunityLeft {
+        community: Pubkey,
+        user: Pubkey,
+    },
+    ProfileVerified {
+        profile: Pubkey,
+    },
+    PostDeleted {
+        post: Pubkey,
+        author: Pubkey,
+    },
+    ProfileDeleted {
+        profile: Pubkey,
+        owner: Pubkey,
+    },
+    PostEdited {
+        post: Pubkey,
+    },
+    UserBlocked {
+        blocker: Pubkey,
+        blocked: Pubkey,
+    },
+    UserUnblocked {
+        blocker: Pubkey,
+        blocked: Pubkey,
+    },
+    ModeratorAdded {
+        community: Pubkey,
+        moderator: Pubkey,
+    },
+    PostModerated {
+        post: Pubkey,
+        moderator: Pubkey,
+    },
+    CommunityOwnershipTransferred {
+        community: Pubkey,
+        new_owner: Pubkey,
+    },
+    PostTipped {
+        post: Pubkey,
+        tipper: Pubkey,
+        amount: u64,
+    },
+    UcrDecayed {
+        profile: Pubkey,
+        user_credit_rating: i64,
+    },
+    PostQuoted {
+        post: Pubkey,
+        quoted_post: Pubkey,
+        author: Pubkey,
+    },
+    PostReported {
+        post: Pubkey,
+        reporter: Pubkey,
+        report_count: u64,
+    },
+    UsernameChanged {
+        profile: Pubkey,
+        username: String,
+    },
+    RatingConfigUpdated {
+        config: RatingConfig,
+    },
+    ProgramInitialized {
+        authority: Pubkey,
+    },
+    PinnedPostChanged {
+        account: Pubkey,
+        pinned_post: Option<Pubkey>,
+    },
+    PostBookmarked {
+        user: Pubkey,
+        post: Pubkey,
+    },
+    BookmarkRemoved {
+        user: Pubkey,
+        post: Pubkey,
+    },
+    AccountMigrated {
+        account: Pubkey,
+        from_version: u8,
+        to_version: u8,
+    },
+    VerificationChanged {
+        profile: Pubkey,
+        verified: bool,
+    },
+    StateReconciled {
+        profiles: u64,
+        posts: u64,
+        communities: u64,
+    },
+    CommentDeleted {
+        comment: Pubkey,
+        parent: Pubkey,
+        author: Pubkey,
+    },
+    MutedWordAdded {
+        user: Pubkey,
+        word: String,
+    },
+    MutedWordRemoved {
+        user: Pubkey,
+        word: String,
+    },
+    AccountSoftDeleted {
+        account: Pubkey,
+        owner: Pubkey,
+    },
+    ConquerorMilestone {
+        post: Pubkey,
+        author: Pubkey,
+        bonus: i64,
+    },
+    CommunityPostRulesUpdated {
+        community: Pubkey,
+        rules: CommunityPostRules,
+    },
+    ProfilePrivacyUpdated {
+        profile: Pubkey,
+        is_private: bool,
+    },
+    FollowRequested {
+        follower: Pubkey,
+        followed: Pubkey,
+    },
+    FollowRequestApproved {
+        follower: Pubkey,
+        followed: Pubkey,
+    },
+    FollowRequestRejected {
+        follower: Pubkey,
+        followed: Pubkey,
+    },
+    CommunityMaxMembersUpdated {
+        community: Pubkey,
+        max_members: u64,
+    },
+    CommunityUpdated {
+        community: Pubkey,
+    },
+    PostRatingChanged {
+        post: Pubkey,
+        author: Pubkey,
+        previous: PostRating,
+        rating: PostRating,
+    },
+    VerificationLevelChanged {
+        profile: Pubkey,
+        level: VerificationLevel,
+    },
+    LeaderboardUpdated {
+        profile: Pubkey,
+        ucr: i64,
+        rank: Option<u8>, // 0 is the top; None when the profile isn't on the board
+    },
+    ConquerorMilestoneRevoked {
+        post: Pubkey,
+        author: Pubkey,
+        bonus: i64,
+    },
+}
+
+impl Event {
+    // Log the event as a single `Program data:` entry
+    pub fn emit(&self) -> Result<(), ProgramError> {
+        sol_log_data(&[&self.try_to_vec()?]);
+        Ok(())
+    }
+}
@@ -1,23 +1,37 @@
 
+use crate::state::{CommunityPostRules, RatingConfig, VerificationLevel};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::pubkey::Pubkey;
 
+// Adding an instruction:
+// 1. Append the variant at the end of this enum; its position is the Borsh tag clients send,
+//    so existing variants must never move. Document the accounts it expects.
+// 2. Add its handler to `Processor` and an arm to the `process` match.
+// 3. Add a builder to `instruction_builders` listing the accounts in the documented order.
+// 4. Add a sample to `dispatch_cases!` in tests/dispatcher.rs under the next tag, which fails to
+//    compile until you do. The tags listed there pin the wire format; never renumber them.
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub enum ContractInstruction {
     /// Create a new user profile
     /// Accounts expected:
-    /// 0. `[signer]` The user's wallet account
-    /// 1. `[]` The profile account (PDA)
+    /// 0. `[signer, writable]` The user's wallet account
+    /// 1. `[writable]` The profile account (PDA)
     /// 2. `[]` The system program
+    /// 3. `[writable]` The program state account (PDA)
+    /// 4. `[writable]` The username registry account (PDA)
     CreateProfile {
         username: String,
         bio: String,
         profile_image: String,
         cover_image: String,
+        profile_image_hash: Option<[u8; 32]>,
+        cover_image_hash: Option<[u8; 32]>,
     },
     /// Update an existing user profile
     /// Accounts expected:
-    /// 0. `[signer, writable]` The user's wallet account
+    /// 0. `[signer, writable]` The user's wallet account, pays for any growth
+    /// 1. `[writable]` The profile account
+    /// 2. `[]` The system program
     UpdateProfile {
         bio: String,
         profile_image: String,
@@ -25,28 +39,62 @@ pub enum ContractInstruction {
     },
     /// Create a new post
     /// Accounts expected:
-    /// 0. `[signer]` The post's author wallet account
+    /// 0. `[signer, writable]` The post's author wallet account
+    /// 1. `[writable]` The post account (PDA for the author's next post id)
+    /// 2. `[writable]` The author's profile account
+    /// 3. `[]` The system program
+    /// 4. `[writable]` The program state account (PDA)
+    /// 5. `[writable]` The user's post index account (PDA)
+    /// 6. `[]` The username registry account for the author's username (PDA)
     CreatePost {
         content: String,
         images: Vec<String>,
+        content_hash: Option<[u8; 32]>,
+        allow_comments: bool,
     },
-    /// Like a post
+    /// Like a post; each user can like a post once
     /// Accounts expected:
-    /// 0. `[signer]` The user's wallet account
+    /// 0. `[signer, writable]` The user's wallet account, which pays for the like record
+    /// 1. `[writable]` The post account
+    /// 2. `[writable]` The post author's profile account
+    /// 3. `[]` The liker's profile account
+    /// 4. `[]` The program state account (PDA)
+    /// 5. `[]` The username registry account for the post author's username (PDA)
+    /// 6. `[writable]` The like record of the user liking the post (PDA)
+    /// 7. `[]` The system program
     LikePost {
         post_id: u64,
     },
     /// Comment on a post
     /// Accounts expected:
-    /// 0. `[signer]` The user's wallet account
+    /// 0. `[signer, writable]` The user's wallet account
+    /// 1. `[writable]` The comment account (PDA for the commenter's next post id)
+    /// 2. `[writable]` The parent post account
+    /// 3. `[writable]` The commenter's profile account
+    /// 4. `[]` The system program
+    /// 5. `[writable]` The program state account (PDA)
+    /// 6. `[]` The block record of the parent author blocking the commenter (PDA)
+    /// 7. `[writable]` The commenter's comment receipt for the parent post (PDA)
+    /// 8. `[writable]` The parent author's profile account
+    /// 9. `[]` The username registry account for the parent author's username (PDA)
+    /// 10. `[]` The commenter's membership account (PDA), only when the parent post is in a community
     CommentOnPost {
         content: String,
         parent_id: u64,
+        images: Vec<String>,
     },
     /// Follow another profile
     /// Accounts expected:
     /// 0. `[signer, writable]` The follower's wallet account
     /// 1. `[writable]` The profile to follow
+    /// 2. `[writable]` The follower's profile account
+    /// 3. `[]` The block record of the followed user blocking the follower (PDA)
+    /// 4. `[writable]` The follow record account (PDA)
+    /// 5. `[]` The system program
+    /// 6. `[writable]` The follower's last following index page (PDA), or the next page once it is full
+    /// 7. `[writable]` The followed user's last follower index page (PDA), or the next page once it is full
+    /// 8. `[writable]` The follow request account (PDA); only read when the followed profile is private,
+    ///    in which case a pending request is opened instead of following
     FollowProfile {
         profile_id: Pubkey,
     },
@@ -54,12 +102,21 @@ pub enum ContractInstruction {
     /// Accounts expected:
     /// 0. `[signer, writable]` The follower's wallet account
     /// 1. `[writable]` The profile to unfollow
+    /// 2. `[writable]` The follower's profile account
+    /// 3. `[writable]` The follow record account (PDA)
+    /// 4. `[writable]` The following index page recorded on the follow record (PDA)
+    /// 5. `[writable]` The follower index page recorded on the follow record (PDA)
     UnfollowProfile {
         profile_id: Pubkey,
     },
     /// Create a new community
     /// Accounts expected:
-    /// 0. `[signer]` The community creator's wallet account
+    /// 0. `[signer, writable]` The community creator's wallet account
+    /// 1. `[writable]` The community account (PDA seeded on the normalized name)
+    /// 2. `[]` The system program
+    /// 3. `[writable]` The program state account (PDA)
+    /// 4. `[writable]` The creator's membership account (PDA)
+    /// 5. `[writable]` The first member index page (PDA, page 0)
     CreateCommunity {
         name: String,
         description: String,
@@ -68,8 +125,382 @@ pub enum ContractInstruction {
     },
     /// Join a community
     /// Accounts expected:
-    /// 0. `[signer]` The user's wallet account
+    /// 0. `[signer, writable]` The user's wallet account
+    /// 1. `[writable]` The community account
+    /// 2. `[writable]` The membership account (PDA)
+    /// 3. `[]` The system program
+    /// 4. `[writable]` The last member index page (PDA), or the next page once it is full
     JoinCommunity {
         community_id: u64,
     },
+    /// Leave a community
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The user's wallet account
+    /// 1. `[writable]` The community account
+    /// 2. `[writable]` The membership account (PDA)
+    /// 3. `[writable]` The member index page listing the user (PDA)
+    LeaveCommunity {
+        community_id: u64,
+    },
+    /// Verify a profile once its like rate reaches the verification threshold
+    /// Accounts expected:
+    /// 0. `[signer]` The user's wallet account
+    /// 1. `[writable]` The user's profile account
+    VerifyProfile,
+    /// Delete a post and reclaim its rent; comments are deleted with `DeleteComment` instead
+    /// Posts with comments are refused unless `force` is set, which leaves the comments
+    /// for their authors to delete
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The post's author wallet account
+    /// 1. `[writable]` The post account
+    /// 2. `[writable]` The author's profile account
+    /// 3. `[writable]` The program state account (PDA)
+    DeletePost {
+        post_id: u64,
+        force: bool,
+    },
+    /// Delete a profile and reclaim its rent, releasing its username
+    /// The profile must have no posts, follow no one and have no followers
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The user's wallet account
+    /// 1. `[writable]` The profile account
+    /// 2. `[writable]` The username registry account for the profile's username (PDA)
+    /// 3. `[writable]` The program state account (PDA)
+    DeleteProfile,
+    /// Edit the content of an existing post
+    /// Accounts expected:
+    /// 0. `[signer]` The post's author wallet account
+    /// 1. `[writable]` The post account
+    /// 2. `[]` The author's profile account (optional; grants the tier's longer edit window)
+    /// 3. `[]` The username registry account for the profile's username (PDA), required with the profile
+    EditPost {
+        post_id: u64,
+        content: String,
+        images: Vec<String>,
+    },
+    /// Block another user from following or commenting
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The blocker's wallet account
+    /// 1. `[writable]` The block record account (PDA)
+    /// 2. `[]` The system program
+    BlockUser {
+        target: Pubkey,
+    },
+    /// Remove a block on another user
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The blocker's wallet account
+    /// 1. `[writable]` The block record account (PDA)
+    UnblockUser {
+        target: Pubkey,
+    },
+    /// Promote a community member to moderator
+    /// Accounts expected:
+    /// 0. `[signer]` The community owner's wallet account
+    /// 1. `[]` The community account
+    /// 2. `[writable]` The target's membership account (PDA)
+    AddModerator {
+        target: Pubkey,
+    },
+    /// Hide a post within a community
+    /// Accounts expected:
+    /// 0. `[signer]` The moderator's wallet account
+    /// 1. `[]` The community account
+    /// 2. `[]` The moderator's membership account (PDA)
+    /// 3. `[writable]` The post account
+    ModeratePost {
+        post_id: u64,
+    },
+    /// Hand a community off to another member
+    /// Accounts expected:
+    /// 0. `[signer]` The current owner's wallet account
+    /// 1. `[writable]` The community account
+    /// 2. `[writable]` The current owner's membership account (PDA)
+    /// 3. `[writable]` The new owner's membership account (PDA)
+    TransferCommunityOwnership {
+        new_owner: Pubkey,
+    },
+    /// Tip a post's author in lamports
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The tipper's wallet account
+    /// 1. `[writable]` The post account
+    /// 2. `[writable]` The author's wallet account
+    /// 3. `[writable]` The author's profile account
+    /// 4. `[]` The system program
+    /// 5. `[]` The username registry account for the author's username (PDA)
+    TipPost {
+        post_id: u64,
+        amount: u64,
+    },
+    /// Apply inactivity decay to a profile's UCR (callable by anyone)
+    /// Accounts expected:
+    /// 0. `[writable]` The profile account
+    DecayUcr,
+    /// Reshare a post with added commentary; the quoted post can't itself be a quote
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The user account
+    /// 1. `[writable]` The new post account (PDA for the user's next post id)
+    /// 2. `[writable]` The user's profile account
+    /// 3. `[]` The system program
+    /// 4. `[writable]` The program state account (PDA)
+    /// 5. `[writable]` The user's post index account (PDA)
+    /// 6. `[]` The username registry account for the user's username (PDA)
+    /// 7. `[writable]` The quoted post account
+    /// 8. `[writable]` The quoted post author's profile account
+    /// 9. `[]` The username registry account for the quoted post author's username (PDA)
+    QuotePost {
+        post_id: u64,
+        content: String,
+    },
+    /// Report a post to the moderators
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The reporter's account
+    /// 1. `[writable]` The post account
+    /// 2. `[writable]` The report account (PDA)
+    /// 3. `[]` The system program
+    ReportPost {
+        post_id: u64,
+        reason: u8,
+    },
+    /// Change a profile's username, moving its registry claim.
+    /// The profile account keeps the address derived from the original username;
+    /// only the stored `username` field changes.
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The user's wallet account
+    /// 1. `[writable]` The profile account
+    /// 2. `[writable]` The current username registry account (PDA)
+    /// 3. `[writable]` The new username registry account (PDA)
+    /// 4. `[]` The system program
+    ChangeUsername {
+        new_username: String,
+    },
+    /// Like several posts in one instruction; a batch can't name the same post twice
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The user's wallet account, which pays for the like records
+    /// 1. `[]` The liker's profile account
+    /// 2. `[]` The program state account (PDA)
+    /// 3. `[]` The system program
+    /// 4. `[writable]` The first post account
+    /// 5. `[writable]` The first post author's profile account
+    /// 6. `[]` The username registry account for the first post author's username (PDA)
+    /// 7. `[writable]` The user's like record for the first post (PDA)
+    /// 8. ... followed by one post, author profile, author username registry and like record group per remaining post ID
+    LikePosts {
+        post_ids: Vec<u64>,
+    },
+    /// Update the like thresholds used for post ratings (program authority only)
+    /// Accounts expected:
+    /// 0. `[signer]` The program authority
+    /// 1. `[writable]` The program state account (PDA)
+    SetRatingConfig {
+        config: RatingConfig,
+    },
+    /// Create the program state and record the signer as the program authority
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The authority's wallet account
+    /// 1. `[writable]` The program state account (PDA)
+    /// 2. `[]` The system program
+    InitializeProgram,
+    /// Create a new post in a community the author belongs to
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The post's author wallet account
+    /// 1. `[writable]` The post account (PDA for the author's next post id)
+    /// 2. `[writable]` The author's profile account
+    /// 3. `[]` The system program
+    /// 4. `[writable]` The program state account (PDA)
+    /// 5. `[writable]` The user's post index account (PDA)
+    /// 6. `[]` The community account
+    /// 7. `[]` The author's membership account (PDA)
+    /// 8. `[writable]` The community feed account (PDA)
+    /// 9. `[]` The username registry account for the author's username (PDA)
+    CreatePostInCommunity {
+        content: String,
+        images: Vec<String>,
+        community_id: u64,
+        content_hash: Option<[u8; 32]>,
+    },
+    /// Pin one of the user's posts to their profile
+    /// Accounts expected:
+    /// 0. `[signer]` The user's wallet account
+    /// 1. `[writable]` The user's profile account
+    /// 2. `[]` The post account
+    PinPost {
+        post_id: u64,
+    },
+    /// Clear the pinned post on the user's profile
+    /// Accounts expected:
+    /// 0. `[signer]` The user's wallet account
+    /// 1. `[writable]` The user's profile account
+    UnpinPost,
+    /// Pin a community post (community owner only)
+    /// Accounts expected:
+    /// 0. `[signer]` The community owner's wallet account
+    /// 1. `[writable]` The community account
+    /// 2. `[]` The post account
+    PinCommunityPost {
+        post_id: u64,
+    },
+    /// Clear the community's pinned post (community owner only)
+    /// Accounts expected:
+    /// 0. `[signer]` The community owner's wallet account
+    /// 1. `[writable]` The community account
+    UnpinCommunityPost,
+    /// Save a post to the user's bookmarks
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The user's wallet account
+    /// 1. `[]` The post account
+    /// 2. `[writable]` The bookmark account (PDA)
+    /// 3. `[]` The system program
+    BookmarkPost {
+        post_id: u64,
+    },
+    /// Remove a saved post and reclaim the bookmark's rent
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The user's wallet account
+    /// 1. `[]` The bookmarked post's address
+    /// 2. `[writable]` The bookmark account (PDA)
+    RemoveBookmark,
+    /// Rewrite an account written under an older schema version, growing it if needed
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The wallet that owns the account
+    /// 1. `[writable]` The account to migrate
+    /// 2. `[]` The system program
+    MigrateAccount,
+    /// Grant or revoke a profile's verification badge (program authority only)
+    /// Accounts expected:
+    /// 0. `[signer]` The program authority
+    /// 1. `[]` The program state account (PDA)
+    /// 2. `[writable]` The target profile account
+    SetVerification {
+        target: Pubkey,
+        verified: bool,
+    },
+    /// Overwrite the program-wide counters after they drift (program authority only)
+    /// Accounts expected:
+    /// 0. `[signer]` The program authority
+    /// 1. `[writable]` The program state account (PDA)
+    ReconcileState {
+        profiles: u64,
+        posts: u64,
+        communities: u64,
+    },
+    /// Delete a comment, decrement its parent's comment count and reclaim its rent
+    /// Comments with replies are refused unless `force` is set
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The comment's author wallet account
+    /// 1. `[writable]` The comment account
+    /// 2. `[writable]` The parent post account
+    /// 3. `[writable]` The comment author's profile account
+    /// 4. `[writable]` The program state account (PDA)
+    DeleteComment {
+        comment_id: u64,
+        parent_id: u64,
+        force: bool,
+    },
+    /// Add a keyword to the user's muted words, creating the list on first use
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The user's wallet account
+    /// 1. `[writable]` The user's mute preferences account (PDA)
+    /// 2. `[]` The system program
+    AddMutedWord {
+        word: String,
+    },
+    /// Remove a keyword from the user's muted words, closing the list once it is empty
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The user's wallet account
+    /// 1. `[writable]` The user's mute preferences account (PDA)
+    RemoveMutedWord {
+        word: String,
+    },
+    /// Soft-delete a profile, post or community: the record stays on chain but is treated as
+    /// gone by every other instruction until a hard delete closes it
+    /// Accounts expected:
+    /// 0. `[signer]` The owner of the record
+    /// 1. `[writable]` The profile, post or community account
+    SoftDeleteAccount,
+    /// Replace the requirements members must meet to post in a community (owner only)
+    /// Accounts expected:
+    /// 0. `[signer]` The community owner's wallet account
+    /// 1. `[writable]` The community account
+    SetCommunityPostRules {
+        rules: CommunityPostRules,
+    },
+    /// Make a profile private, so follows need approval, or public again
+    /// Accounts expected:
+    /// 0. `[signer]` The profile owner's wallet account
+    /// 1. `[writable]` The profile account
+    SetProfilePrivacy {
+        is_private: bool,
+    },
+    /// Accept a pending follow request on the signer's private profile
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The followed user's wallet account, which pays for the follow record
+    /// 1. `[writable]` The followed user's profile account
+    /// 2. `[writable]` The follower's profile account
+    /// 3. `[writable]` The follow request account (PDA)
+    /// 4. `[writable]` The follower's wallet account, refunded the request's rent
+    /// 5. `[writable]` The follow record account (PDA)
+    /// 6. `[]` The system program
+    /// 7. `[writable]` The follower's last following index page (PDA), or the next page once it is full
+    /// 8. `[writable]` The followed user's last follower index page (PDA), or the next page once it is full
+    /// 9. `[]` The block record of the followed user blocking the follower (PDA)
+    /// 10. `[]` The block record of the follower blocking the followed user (PDA)
+    ApproveFollow {
+        follower: Pubkey,
+    },
+    /// Decline a pending follow request on the signer's profile
+    /// Accounts expected:
+    /// 0. `[signer]` The followed user's wallet account
+    /// 1. `[]` The followed user's profile account
+    /// 2. `[writable]` The follow request account (PDA)
+    /// 3. `[writable]` The follower's wallet account, refunded the request's rent
+    RejectFollow {
+        follower: Pubkey,
+    },
+    /// Cap a community's membership (owner only); at most MAX_COMMUNITY_MEMBERS
+    /// Accounts expected:
+    /// 0. `[signer]` The community owner's wallet account
+    /// 1. `[writable]` The community account
+    SetCommunityMaxMembers {
+        max_members: u64,
+    },
+    /// Update a community's metadata (owner only); fields left as `None` are unchanged
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The community owner's wallet account, which pays for any growth
+    /// 1. `[writable]` The community account
+    /// 2. `[]` The system program
+    UpdateCommunity {
+        description: Option<String>,
+        avatar: Option<String>,
+        rules: Option<Vec<String>>,
+    },
+    /// Set a profile's verification badge tier (program authority only)
+    /// Accounts expected:
+    /// 0. `[signer]` The program authority
+    /// 1. `[]` The program state account (PDA)
+    /// 2. `[writable]` The target profile account
+    SetVerificationLevel {
+        target: Pubkey,
+        level: VerificationLevel,
+    },
+    /// Create a new community and register up to MAX_INITIAL_MEMBERS members, paid for by the creator
+    /// Accounts expected:
+    /// 0-5. The `CreateCommunity` accounts
+    /// 6. `[writable]` The first member's membership account (PDA)
+    /// 7. ... followed by one membership account per remaining member
+    CreateCommunityWithMembers {
+        name: String,
+        description: String,
+        avatar: String,
+        rules: Vec<String>,
+        members: Vec<Pubkey>,
+    },
+    /// Re-rank profiles on the UCR leaderboard by their current UCR (callable by anyone, typically
+    /// after a profile's UCR changes); deleted profiles are removed
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The payer's wallet account, which funds the leaderboard on first use
+    /// 1. `[writable]` The leaderboard account (PDA)
+    /// 2. `[]` The system program
+    /// 3. `[]` The first profile account
+    /// 4. ... followed by up to LEADERBOARD_LEN - 1 more profile accounts
+    UpdateLeaderboard,
 }
@@ -0,0 +1,1006 @@
+//! Typed helpers for building `ContractInstruction`s on the client side.
+//!
+//! Each builder derives the PDAs the program expects and lists the account
+//! metas in the order documented on `ContractInstruction`.
+//!
+//! ```
+//! use blocks_contracts::instruction_builders::create_profile_ix;
+//! use solana_program::pubkey::Pubkey;
+//!
+//! let program_id = Pubkey::new_unique();
+//! let user = Pubkey::new_unique();
+//! let ix = create_profile_ix(
+//!     &program_id,
+//!     &user,
+//!     "alice".to_string(),
+//!     "gm".to_string(),
+//!     String::new(),
+//!     String::new(),
+//!     None,
+//!     None,
+//! );
+//! assert_eq!(ix.accounts.len(), 5);
+//! ```
+
+use crate::{
+    instruction::ContractInstruction,
+    pda::{
+        block_record_pda, bookmark_pda, follow_request_pda, comment_receipt_pda, community_feed_pda, community_pda, leaderboard_pda, like_record_pda, follow_record_pda, follower_index_pda, following_index_pda, member_index_pda, membership_pda, mute_preferences_pda, post_index_pda, post_pda, profile_pda,
+        program_state_pda, report_pda, username_registry_pda,
+    },
+    state::{CommunityPostRules, RatingConfig, VerificationLevel},
+};
+use borsh::BorshSerialize;
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+
+fn build(program_id: &Pubkey, data: ContractInstruction, accounts: Vec<AccountMeta>) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: data.try_to_vec().expect("serializing into a Vec cannot fail"),
+    }
+}
+
+/// Build a `CreateProfile` instruction for `user`
+#[allow(clippy::too_many_arguments)]
+pub fn create_profile_ix(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    username: String,
+    bio: String,
+    profile_image: String,
+    cover_image: String,
+    profile_image_hash: Option<[u8; 32]>,
+    cover_image_hash: Option<[u8; 32]>,
+) -> Instruction {
+    let (profile, _) = profile_pda(program_id, user, &username);
+    let (registry, _) = username_registry_pda(program_id, &username);
+    build(
+        program_id,
+        ContractInstruction::CreateProfile {
+            username,
+            bio,
+            profile_image,
+            cover_image,
+            profile_image_hash,
+            cover_image_hash,
+        },
+        vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(profile, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(program_state_pda(program_id).0, false),
+            AccountMeta::new(registry, false),
+        ],
+    )
+}
+
+/// Build an `UpdateProfile` instruction
+pub fn update_profile_ix(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    profile: &Pubkey,
+    bio: String,
+    profile_image: String,
+    cover_image: String,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::UpdateProfile { bio, profile_image, cover_image },
+        vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(*profile, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Build a `CreatePost` instruction; `post_id` is the author's next post id
+/// (`Profile::last_post_id + 1`) and `username` is the author's current username
+#[allow(clippy::too_many_arguments)]
+pub fn create_post_ix(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    post_id: u64,
+    profile: &Pubkey,
+    username: &str,
+    content: String,
+    images: Vec<String>,
+    content_hash: Option<[u8; 32]>,
+    allow_comments: bool,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::CreatePost { content, images, content_hash, allow_comments },
+        vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(post_pda(program_id, user, post_id).0, false),
+            AccountMeta::new(*profile, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(program_state_pda(program_id).0, false),
+            AccountMeta::new(post_index_pda(program_id, user).0, false),
+            AccountMeta::new_readonly(username_registry_pda(program_id, username).0, false),
+        ],
+    )
+}
+
+/// Build a `LikePost` instruction; `author_username` is the post author's current username
+pub fn like_post_ix(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    post: &Pubkey,
+    author_profile: &Pubkey,
+    author_username: &str,
+    liker_profile: &Pubkey,
+    post_id: u64,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::LikePost { post_id },
+        vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(*post, false),
+            AccountMeta::new(*author_profile, false),
+            AccountMeta::new_readonly(*liker_profile, false),
+            AccountMeta::new_readonly(program_state_pda(program_id).0, false),
+            AccountMeta::new_readonly(username_registry_pda(program_id, author_username).0, false),
+            AccountMeta::new(like_record_pda(program_id, post, user).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Build a `CommentOnPost` instruction; `comment_id` is the commenter's next post id
+/// (`Profile::last_post_id + 1`).
+/// Pass the parent post's community, if any, so the commenter's membership is included.
+#[allow(clippy::too_many_arguments)]
+pub fn comment_on_post_ix(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    comment_id: u64,
+    parent_post: &Pubkey,
+    parent_author: &Pubkey,
+    parent_author_profile: &Pubkey,
+    parent_author_username: &str,
+    parent_community: Option<&Pubkey>,
+    profile: &Pubkey,
+    content: String,
+    parent_id: u64,
+    images: Vec<String>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*user, true),
+        AccountMeta::new(post_pda(program_id, user, comment_id).0, false),
+        AccountMeta::new(*parent_post, false),
+        AccountMeta::new(*profile, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new(program_state_pda(program_id).0, false),
+        AccountMeta::new_readonly(block_record_pda(program_id, parent_author, user).0, false),
+        AccountMeta::new(comment_receipt_pda(program_id, parent_post, user).0, false),
+        AccountMeta::new(*parent_author_profile, false),
+        AccountMeta::new_readonly(username_registry_pda(program_id, parent_author_username).0, false),
+    ];
+    if let Some(community) = parent_community {
+        accounts.push(AccountMeta::new_readonly(membership_pda(program_id, community, user).0, false));
+    }
+    build(program_id, ContractInstruction::CommentOnPost { content, parent_id, images }, accounts)
+}
+
+/// Build a `FollowProfile` instruction; `followed_owner` is the followed profile's wallet.
+/// `following_page` and `follower_page` are the last pages of the follower's following index and
+/// the followed user's follower index, or the next page once full
+#[allow(clippy::too_many_arguments)]
+pub fn follow_profile_ix(
+    program_id: &Pubkey,
+    follower: &Pubkey,
+    follower_profile: &Pubkey,
+    followed_profile: &Pubkey,
+    followed_owner: &Pubkey,
+    following_page: u32,
+    follower_page: u32,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::FollowProfile { profile_id: *followed_profile },
+        vec![
+            AccountMeta::new(*follower, true),
+            AccountMeta::new(*followed_profile, false),
+            AccountMeta::new(*follower_profile, false),
+            AccountMeta::new_readonly(block_record_pda(program_id, followed_owner, follower).0, false),
+            AccountMeta::new(follow_record_pda(program_id, follower, followed_owner).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(following_index_pda(program_id, follower, following_page).0, false),
+            AccountMeta::new(follower_index_pda(program_id, followed_owner, follower_page).0, false),
+            AccountMeta::new(follow_request_pda(program_id, follower, followed_owner).0, false),
+        ],
+    )
+}
+
+/// Build an `UnfollowProfile` instruction; `followed_owner` is the followed profile's wallet and
+/// the pages are the ones recorded on the follow record
+#[allow(clippy::too_many_arguments)]
+pub fn unfollow_profile_ix(
+    program_id: &Pubkey,
+    follower: &Pubkey,
+    follower_profile: &Pubkey,
+    followed_profile: &Pubkey,
+    followed_owner: &Pubkey,
+    following_page: u32,
+    follower_page: u32,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::UnfollowProfile { profile_id: *followed_profile },
+        vec![
+            AccountMeta::new(*follower, true),
+            AccountMeta::new(*followed_profile, false),
+            AccountMeta::new(*follower_profile, false),
+            AccountMeta::new(follow_record_pda(program_id, follower, followed_owner).0, false),
+            AccountMeta::new(following_index_pda(program_id, follower, following_page).0, false),
+            AccountMeta::new(follower_index_pda(program_id, followed_owner, follower_page).0, false),
+        ],
+    )
+}
+
+/// Build a `CreateCommunity` instruction; `community` is a fresh keypair that must also sign
+pub fn create_community_ix(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    name: String,
+    description: String,
+    avatar: String,
+    rules: Vec<String>,
+) -> Instruction {
+    let community = &community_pda(program_id, &name).0;
+    build(
+        program_id,
+        ContractInstruction::CreateCommunity { name, description, avatar, rules },
+        vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(*community, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(program_state_pda(program_id).0, false),
+            AccountMeta::new(membership_pda(program_id, community, owner).0, false),
+            AccountMeta::new(member_index_pda(program_id, community, 0).0, false),
+        ],
+    )
+}
+
+/// Build a `CreateCommunityWithMembers` instruction
+pub fn create_community_with_members_ix(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    name: String,
+    description: String,
+    avatar: String,
+    rules: Vec<String>,
+    members: Vec<Pubkey>,
+) -> Instruction {
+    let community = &community_pda(program_id, &name).0;
+    let mut accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(*community, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new(program_state_pda(program_id).0, false),
+        AccountMeta::new(membership_pda(program_id, community, owner).0, false),
+        AccountMeta::new(member_index_pda(program_id, community, 0).0, false),
+    ];
+    accounts.extend(members.iter().map(|member| AccountMeta::new(membership_pda(program_id, community, member).0, false)));
+    build(
+        program_id,
+        ContractInstruction::CreateCommunityWithMembers { name, description, avatar, rules, members },
+        accounts,
+    )
+}
+
+/// Build a `JoinCommunity` instruction; `member_index_page` is the community's last
+/// page, or `member_index_pages` when that page is full
+pub fn join_community_ix(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    community: &Pubkey,
+    community_id: u64,
+    member_index_page: u32,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::JoinCommunity { community_id },
+        vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(*community, false),
+            AccountMeta::new(membership_pda(program_id, community, user).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(member_index_pda(program_id, community, member_index_page).0, false),
+        ],
+    )
+}
+
+/// Build a `LeaveCommunity` instruction; `member_index_page` is the membership's `index_page`
+pub fn leave_community_ix(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    community: &Pubkey,
+    community_id: u64,
+    member_index_page: u32,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::LeaveCommunity { community_id },
+        vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(*community, false),
+            AccountMeta::new(membership_pda(program_id, community, user).0, false),
+            AccountMeta::new(member_index_pda(program_id, community, member_index_page).0, false),
+        ],
+    )
+}
+
+/// Build a `VerifyProfile` instruction
+pub fn verify_profile_ix(program_id: &Pubkey, user: &Pubkey, profile: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::VerifyProfile,
+        vec![
+            AccountMeta::new_readonly(*user, true),
+            AccountMeta::new(*profile, false),
+        ],
+    )
+}
+
+/// Build a `DeletePost` instruction
+pub fn delete_post_ix(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    post: &Pubkey,
+    profile: &Pubkey,
+    post_id: u64,
+    force: bool,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::DeletePost { post_id, force },
+        vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(*post, false),
+            AccountMeta::new(*profile, false),
+            AccountMeta::new(program_state_pda(program_id).0, false),
+        ],
+    )
+}
+
+/// Build a `DeleteComment` instruction; `parent` is the post the comment replies to
+#[allow(clippy::too_many_arguments)]
+pub fn delete_comment_ix(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    comment: &Pubkey,
+    parent: &Pubkey,
+    profile: &Pubkey,
+    comment_id: u64,
+    parent_id: u64,
+    force: bool,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::DeleteComment { comment_id, parent_id, force },
+        vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(*comment, false),
+            AccountMeta::new(*parent, false),
+            AccountMeta::new(*profile, false),
+            AccountMeta::new(program_state_pda(program_id).0, false),
+        ],
+    )
+}
+
+/// Build a `DeleteProfile` instruction; `username` is the profile's current username
+pub fn delete_profile_ix(program_id: &Pubkey, user: &Pubkey, profile: &Pubkey, username: &str) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::DeleteProfile,
+        vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(*profile, false),
+            AccountMeta::new(username_registry_pda(program_id, username).0, false),
+            AccountMeta::new(program_state_pda(program_id).0, false),
+        ],
+    )
+}
+
+/// Build an `EditPost` instruction; `author_profile` pairs the profile with its current username
+pub fn edit_post_ix(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    post: &Pubkey,
+    author_profile: Option<(&Pubkey, &str)>,
+    post_id: u64,
+    content: String,
+    images: Vec<String>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*user, true),
+        AccountMeta::new(*post, false),
+    ];
+    if let Some((profile, username)) = author_profile {
+        accounts.push(AccountMeta::new_readonly(*profile, false));
+        accounts.push(AccountMeta::new_readonly(username_registry_pda(program_id, username).0, false));
+    }
+    build(program_id, ContractInstruction::EditPost { post_id, content, images }, accounts)
+}
+
+/// Build a `BlockUser` instruction
+pub fn block_user_ix(program_id: &Pubkey, user: &Pubkey, target: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::BlockUser { target: *target },
+        vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(block_record_pda(program_id, user, target).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Build an `UnblockUser` instruction
+pub fn unblock_user_ix(program_id: &Pubkey, user: &Pubkey, target: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::UnblockUser { target: *target },
+        vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(block_record_pda(program_id, user, target).0, false),
+        ],
+    )
+}
+
+/// Build an `AddModerator` instruction
+pub fn add_moderator_ix(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    community: &Pubkey,
+    target: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::AddModerator { target: *target },
+        vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new_readonly(*community, false),
+            AccountMeta::new(membership_pda(program_id, community, target).0, false),
+        ],
+    )
+}
+
+/// Build a `ModeratePost` instruction
+pub fn moderate_post_ix(
+    program_id: &Pubkey,
+    moderator: &Pubkey,
+    community: &Pubkey,
+    post: &Pubkey,
+    post_id: u64,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::ModeratePost { post_id },
+        vec![
+            AccountMeta::new_readonly(*moderator, true),
+            AccountMeta::new_readonly(*community, false),
+            AccountMeta::new_readonly(membership_pda(program_id, community, moderator).0, false),
+            AccountMeta::new(*post, false),
+        ],
+    )
+}
+
+/// Build a `TransferCommunityOwnership` instruction
+pub fn transfer_community_ownership_ix(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    community: &Pubkey,
+    new_owner: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::TransferCommunityOwnership { new_owner: *new_owner },
+        vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(*community, false),
+            AccountMeta::new(membership_pda(program_id, community, owner).0, false),
+            AccountMeta::new(membership_pda(program_id, community, new_owner).0, false),
+        ],
+    )
+}
+
+/// Build a `TipPost` instruction; `author_username` is the post author's current username
+#[allow(clippy::too_many_arguments)]
+pub fn tip_post_ix(
+    program_id: &Pubkey,
+    tipper: &Pubkey,
+    post: &Pubkey,
+    author: &Pubkey,
+    author_profile: &Pubkey,
+    author_username: &str,
+    post_id: u64,
+    amount: u64,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::TipPost { post_id, amount },
+        vec![
+            AccountMeta::new(*tipper, true),
+            AccountMeta::new(*post, false),
+            AccountMeta::new(*author, false),
+            AccountMeta::new(*author_profile, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(username_registry_pda(program_id, author_username).0, false),
+        ],
+    )
+}
+
+/// Build a `DecayUcr` instruction
+pub fn decay_ucr_ix(program_id: &Pubkey, profile: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::DecayUcr,
+        vec![AccountMeta::new(*profile, false)],
+    )
+}
+
+/// Build an `UpdateLeaderboard` instruction submitting `profiles` for re-ranking
+pub fn update_leaderboard_ix(program_id: &Pubkey, payer: &Pubkey, profiles: &[Pubkey]) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(leaderboard_pda(program_id).0, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    accounts.extend(profiles.iter().map(|profile| AccountMeta::new_readonly(*profile, false)));
+    build(program_id, ContractInstruction::UpdateLeaderboard, accounts)
+}
+
+/// Build a `QuotePost` instruction; `new_post_id` is the quoting user's next post id
+/// (`Profile::last_post_id + 1`) and `username` is the quoting user's current username
+#[allow(clippy::too_many_arguments)]
+pub fn quote_post_ix(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    new_post_id: u64,
+    profile: &Pubkey,
+    username: &str,
+    quoted_post: &Pubkey,
+    quoted_author_profile: &Pubkey,
+    quoted_author_username: &str,
+    post_id: u64,
+    content: String,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::QuotePost { post_id, content },
+        vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(post_pda(program_id, user, new_post_id).0, false),
+            AccountMeta::new(*profile, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(program_state_pda(program_id).0, false),
+            AccountMeta::new(post_index_pda(program_id, user).0, false),
+            AccountMeta::new_readonly(username_registry_pda(program_id, username).0, false),
+            AccountMeta::new(*quoted_post, false),
+            AccountMeta::new(*quoted_author_profile, false),
+            AccountMeta::new_readonly(username_registry_pda(program_id, quoted_author_username).0, false),
+        ],
+    )
+}
+
+/// Build a `ReportPost` instruction
+pub fn report_post_ix(
+    program_id: &Pubkey,
+    reporter: &Pubkey,
+    post: &Pubkey,
+    post_id: u64,
+    reason: u8,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::ReportPost { post_id, reason },
+        vec![
+            AccountMeta::new(*reporter, true),
+            AccountMeta::new(*post, false),
+            AccountMeta::new(report_pda(program_id, post, reporter).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Build a `ChangeUsername` instruction
+pub fn change_username_ix(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    profile: &Pubkey,
+    current_username: &str,
+    new_username: String,
+) -> Instruction {
+    let (old_registry, _) = username_registry_pda(program_id, current_username);
+    let (new_registry, _) = username_registry_pda(program_id, &new_username);
+    build(
+        program_id,
+        ContractInstruction::ChangeUsername { new_username },
+        vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(*profile, false),
+            AccountMeta::new(old_registry, false),
+            AccountMeta::new(new_registry, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Build a `LikePosts` instruction from `(post, author_profile, author_username, post_id)` entries
+pub fn like_posts_ix(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    liker_profile: &Pubkey,
+    likes: &[(Pubkey, Pubkey, &str, u64)],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*user, true),
+        AccountMeta::new_readonly(*liker_profile, false),
+        AccountMeta::new_readonly(program_state_pda(program_id).0, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    for (post, author_profile, author_username, _) in likes {
+        accounts.push(AccountMeta::new(*post, false));
+        accounts.push(AccountMeta::new(*author_profile, false));
+        accounts.push(AccountMeta::new_readonly(username_registry_pda(program_id, author_username).0, false));
+        accounts.push(AccountMeta::new(like_record_pda(program_id, post, user).0, false));
+    }
+    let post_ids = likes.iter().map(|(_, _, _, post_id)| *post_id).collect();
+    build(program_id, ContractInstruction::LikePosts { post_ids }, accounts)
+}
+
+/// Build a `SetRatingConfig` instruction
+pub fn set_rating_config_ix(program_id: &Pubkey, authority: &Pubkey, config: RatingConfig) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::SetRatingConfig { config },
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(program_state_pda(program_id).0, false),
+        ],
+    )
+}
+
+/// Build an `InitializeProgram` instruction
+pub fn initialize_program_ix(program_id: &Pubkey, authority: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::InitializeProgram,
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(program_state_pda(program_id).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Build a `CreatePostInCommunity` instruction; `post_id` is the author's next post id
+/// (`Profile::last_post_id + 1`) and `username` is the author's current username
+#[allow(clippy::too_many_arguments)]
+pub fn create_post_in_community_ix(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    post_id: u64,
+    profile: &Pubkey,
+    username: &str,
+    community: &Pubkey,
+    community_id: u64,
+    content: String,
+    images: Vec<String>,
+    content_hash: Option<[u8; 32]>,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::CreatePostInCommunity { content, images, community_id, content_hash },
+        vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(post_pda(program_id, user, post_id).0, false),
+            AccountMeta::new(*profile, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(program_state_pda(program_id).0, false),
+            AccountMeta::new(post_index_pda(program_id, user).0, false),
+            AccountMeta::new_readonly(*community, false),
+            AccountMeta::new_readonly(membership_pda(program_id, community, user).0, false),
+            AccountMeta::new(community_feed_pda(program_id, community).0, false),
+            AccountMeta::new_readonly(username_registry_pda(program_id, username).0, false),
+        ],
+    )
+}
+
+/// Build a `PinPost` instruction
+pub fn pin_post_ix(program_id: &Pubkey, user: &Pubkey, profile: &Pubkey, post: &Pubkey, post_id: u64) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::PinPost { post_id },
+        vec![
+            AccountMeta::new_readonly(*user, true),
+            AccountMeta::new(*profile, false),
+            AccountMeta::new_readonly(*post, false),
+        ],
+    )
+}
+
+/// Build an `UnpinPost` instruction
+pub fn unpin_post_ix(program_id: &Pubkey, user: &Pubkey, profile: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::UnpinPost,
+        vec![
+            AccountMeta::new_readonly(*user, true),
+            AccountMeta::new(*profile, false),
+        ],
+    )
+}
+
+/// Build a `PinCommunityPost` instruction
+pub fn pin_community_post_ix(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    community: &Pubkey,
+    post: &Pubkey,
+    post_id: u64,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::PinCommunityPost { post_id },
+        vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(*community, false),
+            AccountMeta::new_readonly(*post, false),
+        ],
+    )
+}
+
+/// Build an `UnpinCommunityPost` instruction
+pub fn unpin_community_post_ix(program_id: &Pubkey, owner: &Pubkey, community: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::UnpinCommunityPost,
+        vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(*community, false),
+        ],
+    )
+}
+
+/// Build a `BookmarkPost` instruction
+pub fn bookmark_post_ix(program_id: &Pubkey, user: &Pubkey, post: &Pubkey, post_id: u64) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::BookmarkPost { post_id },
+        vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(*post, false),
+            AccountMeta::new(bookmark_pda(program_id, user, post).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Build a `RemoveBookmark` instruction
+pub fn remove_bookmark_ix(program_id: &Pubkey, user: &Pubkey, post: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::RemoveBookmark,
+        vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(*post, false),
+            AccountMeta::new(bookmark_pda(program_id, user, post).0, false),
+        ],
+    )
+}
+
+/// Build a `MigrateAccount` instruction
+pub fn migrate_account_ix(program_id: &Pubkey, owner: &Pubkey, account: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::MigrateAccount,
+        vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(*account, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Build a `SetVerification` instruction; `profile` is the target profile account
+pub fn set_verification_ix(program_id: &Pubkey, authority: &Pubkey, profile: &Pubkey, verified: bool) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::SetVerification { target: *profile, verified },
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(program_state_pda(program_id).0, false),
+            AccountMeta::new(*profile, false),
+        ],
+    )
+}
+
+/// Build a `SetVerificationLevel` instruction
+pub fn set_verification_level_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    profile: &Pubkey,
+    level: VerificationLevel,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::SetVerificationLevel { target: *profile, level },
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(program_state_pda(program_id).0, false),
+            AccountMeta::new(*profile, false),
+        ],
+    )
+}
+
+/// Build a `ReconcileState` instruction that overwrites the program-wide counters
+pub fn reconcile_state_ix(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    profiles: u64,
+    posts: u64,
+    communities: u64,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::ReconcileState { profiles, posts, communities },
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(program_state_pda(program_id).0, false),
+        ],
+    )
+}
+
+/// Build an `AddMutedWord` instruction
+pub fn add_muted_word_ix(program_id: &Pubkey, user: &Pubkey, word: String) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::AddMutedWord { word },
+        vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(mute_preferences_pda(program_id, user).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Build a `RemoveMutedWord` instruction
+pub fn remove_muted_word_ix(program_id: &Pubkey, user: &Pubkey, word: String) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::RemoveMutedWord { word },
+        vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(mute_preferences_pda(program_id, user).0, false),
+        ],
+    )
+}
+
+/// Build a `SetCommunityPostRules` instruction
+pub fn set_community_post_rules_ix(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    community: &Pubkey,
+    rules: CommunityPostRules,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::SetCommunityPostRules { rules },
+        vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(*community, false),
+        ],
+    )
+}
+
+/// Build a `SetCommunityMaxMembers` instruction
+pub fn set_community_max_members_ix(program_id: &Pubkey, owner: &Pubkey, community: &Pubkey, max_members: u64) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::SetCommunityMaxMembers { max_members },
+        vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(*community, false),
+        ],
+    )
+}
+
+/// Build an `UpdateCommunity` instruction
+pub fn update_community_ix(
+    program_id: &Pubkey,
+    owner: &Pubkey,
+    community: &Pubkey,
+    description: Option<String>,
+    avatar: Option<String>,
+    rules: Option<Vec<String>>,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::UpdateCommunity { description, avatar, rules },
+        vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(*community, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Build a `SetProfilePrivacy` instruction
+pub fn set_profile_privacy_ix(program_id: &Pubkey, user: &Pubkey, profile: &Pubkey, is_private: bool) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::SetProfilePrivacy { is_private },
+        vec![
+            AccountMeta::new_readonly(*user, true),
+            AccountMeta::new(*profile, false),
+        ],
+    )
+}
+
+/// Build an `ApproveFollow` instruction; the pages are the follower's last following index page
+/// and the followed user's last follower index page, as for `follow_profile_ix`
+#[allow(clippy::too_many_arguments)]
+pub fn approve_follow_ix(
+    program_id: &Pubkey,
+    followed: &Pubkey,
+    followed_profile: &Pubkey,
+    follower: &Pubkey,
+    follower_profile: &Pubkey,
+    following_page: u32,
+    follower_page: u32,
+) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::ApproveFollow { follower: *follower },
+        vec![
+            AccountMeta::new(*followed, true),
+            AccountMeta::new(*followed_profile, false),
+            AccountMeta::new(*follower_profile, false),
+            AccountMeta::new(follow_request_pda(program_id, follower, followed).0, false),
+            AccountMeta::new(*follower, false),
+            AccountMeta::new(follow_record_pda(program_id, follower, followed).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(following_index_pda(program_id, follower, following_page).0, false),
+            AccountMeta::new(follower_index_pda(program_id, followed, follower_page).0, false),
+            AccountMeta::new_readonly(block_record_pda(program_id, followed, follower).0, false),
+            AccountMeta::new_readonly(block_record_pda(program_id, follower, followed).0, false),
+        ],
+    )
+}
+
+/// Build a `RejectFollow` instruction
+pub fn reject_follow_ix(program_id: &Pubkey, followed: &Pubkey, followed_profile: &Pubkey, follower: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::RejectFollow { follower: *follower },
+        vec![
+            AccountMeta::new_readonly(*followed, true),
+            AccountMeta::new_readonly(*followed_profile, false),
+            AccountMeta::new(follow_request_pda(program_id, follower, followed).0, false),
+            AccountMeta::new(*follower, false),
+        ],
+    )
+}
+
+/// Build a `SoftDeleteAccount` instruction; `account` is the owner's profile, post or community
+pub fn soft_delete_account_ix(program_id: &Pubkey, owner: &Pubkey, account: &Pubkey) -> Instruction {
+    build(
+        program_id,
+        ContractInstruction::SoftDeleteAccount,
+        vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(*account, false),
+        ],
+    )
+}
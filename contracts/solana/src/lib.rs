@@ -1,6 +1,12 @@
 
+#[cfg(feature = "client")]
+pub mod client;
 pub mod error;
+pub mod event;
 pub mod instruction;
+#[cfg(feature = "client")]
+pub mod instruction_builders;
+pub mod pda;
 pub mod processor;
 pub mod state;
 
@@ -0,0 +1,185 @@
+//! PDA seed scheme shared by the processor and clients.
+
+use solana_program::{hash::hash, pubkey::Pubkey};
+
+pub const PROFILE_SEED: &[u8] = b"profile";
+pub const PROGRAM_STATE_SEED: &[u8] = b"state";
+pub const MEMBERSHIP_SEED: &[u8] = b"member";
+pub const BLOCK_RECORD_SEED: &[u8] = b"block";
+pub const FOLLOW_RECORD_SEED: &[u8] = b"follow";
+pub const POST_INDEX_SEED: &[u8] = b"posts";
+pub const REPORT_SEED: &[u8] = b"report";
+pub const USERNAME_SEED: &[u8] = b"username";
+pub const COMMENT_RECEIPT_SEED: &[u8] = b"comment";
+pub const BOOKMARK_SEED: &[u8] = b"bookmark";
+pub const MEMBER_INDEX_SEED: &[u8] = b"members";
+pub const FOLLOWER_INDEX_SEED: &[u8] = b"followers";
+pub const FOLLOWING_INDEX_SEED: &[u8] = b"following";
+pub const COMMUNITY_FEED_SEED: &[u8] = b"feed";
+pub const MUTE_PREFERENCES_SEED: &[u8] = b"mutes";
+pub const POST_SEED: &[u8] = b"post";
+pub const FOLLOW_REQUEST_SEED: &[u8] = b"follow_request";
+pub const COMMUNITY_SEED: &[u8] = b"community";
+pub const LEADERBOARD_SEED: &[u8] = b"leaderboard";
+pub const LIKE_RECORD_SEED: &[u8] = b"like";
+
+/// Profile PDA: `[user, "profile", username]`
+pub fn profile_pda(program_id: &Pubkey, user: &Pubkey, username: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[user.as_ref(), PROFILE_SEED, username.as_bytes()], program_id)
+}
+
+/// Program state PDA: `["state"]`
+pub fn program_state_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRAM_STATE_SEED], program_id)
+}
+
+/// Community membership PDA: `[community, user, "member"]`
+pub fn membership_pda(program_id: &Pubkey, community: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[community.as_ref(), user.as_ref(), MEMBERSHIP_SEED], program_id)
+}
+
+/// Block record PDA: `[blocker, blocked, "block"]`
+pub fn block_record_pda(program_id: &Pubkey, blocker: &Pubkey, blocked: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[blocker.as_ref(), blocked.as_ref(), BLOCK_RECORD_SEED], program_id)
+}
+
+/// Follow record PDA: `[follower, followed, "follow"]`
+pub fn follow_record_pda(program_id: &Pubkey, follower: &Pubkey, followed: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[follower.as_ref(), followed.as_ref(), FOLLOW_RECORD_SEED], program_id)
+}
+
+/// Follow request PDA: `[follower, followed, "follow_request"]`
+pub fn follow_request_pda(program_id: &Pubkey, follower: &Pubkey, followed: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[follower.as_ref(), followed.as_ref(), FOLLOW_REQUEST_SEED], program_id)
+}
+
+/// Post PDA: `[author, "post", post_id (u64 LE)]`; comments and quotes share the author's id sequence
+///
+/// Clients can fetch any post from its author and id alone, with no off-chain index:
+/// derive the address, fetch the account, and decode it. A missing account means the id
+/// was never issued (ids run from 1 to the profile's `last_post_id`) or the post was closed.
+///
+/// ```
+/// use blocks_contracts::{pda::post_pda, state::unpack_post_from_slice};
+/// use solana_program::pubkey::Pubkey;
+///
+/// # fn fetch_account_data(_address: &Pubkey) -> Option<Vec<u8>> { None }
+/// let program_id = Pubkey::new_unique();
+/// let author = Pubkey::new_unique();
+///
+/// let (address, _bump) = post_pda(&program_id, &author, 7);
+/// // e.g. `RpcClient::get_account_data(&address)`
+/// if let Some(data) = fetch_account_data(&address) {
+///     let post = unpack_post_from_slice(&data).unwrap();
+///     assert_eq!((post.author, post.id), (author, 7));
+/// }
+/// ```
+pub fn post_pda(program_id: &Pubkey, author: &Pubkey, post_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[author.as_ref(), POST_SEED, &post_id.to_le_bytes()], program_id)
+}
+
+/// Post index PDA: `[user, "posts"]`
+pub fn post_index_pda(program_id: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[user.as_ref(), POST_INDEX_SEED], program_id)
+}
+
+/// Report PDA: `[post, reporter, "report"]`
+pub fn report_pda(program_id: &Pubkey, post: &Pubkey, reporter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[post.as_ref(), reporter.as_ref(), REPORT_SEED], program_id)
+}
+
+/// Canonical form of a username for uniqueness checks
+pub fn normalize_username(username: &str) -> String {
+    username.to_ascii_lowercase()
+}
+
+/// Username registry PDA: `["username", normalized username]`
+pub fn username_registry_pda(program_id: &Pubkey, username: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[USERNAME_SEED, normalize_username(username).as_bytes()], program_id)
+}
+
+/// Canonical form of a community name for uniqueness checks
+pub fn normalize_community_name(name: &str) -> String {
+    name.to_ascii_lowercase()
+}
+
+/// Seed identifying a community name: the SHA-256 digest of its normalized form, which keeps
+/// "sb/" names within the 32-byte seed limit
+pub fn community_name_seed(name: &str) -> [u8; 32] {
+    hash(normalize_community_name(name).as_bytes()).to_bytes()
+}
+
+/// Community PDA: `["community", sha256(normalized name)]`
+///
+/// Names are unique up to ASCII case, so clients can find a community from its name alone:
+/// derive the address, fetch the account, and decode it. A missing account means no
+/// community has claimed the name.
+///
+/// ```
+/// use blocks_contracts::{pda::community_pda, state::unpack_community_from_slice};
+/// use solana_program::pubkey::Pubkey;
+///
+/// # fn fetch_account_data(_address: &Pubkey) -> Option<Vec<u8>> { None }
+/// let program_id = Pubkey::new_unique();
+///
+/// let (address, _bump) = community_pda(&program_id, "sb/Rust");
+/// assert_eq!(address, community_pda(&program_id, "sb/rust").0);
+/// // e.g. `RpcClient::get_account_data(&address)`
+/// if let Some(data) = fetch_account_data(&address) {
+///     let community = unpack_community_from_slice(&data).unwrap();
+///     assert!(community.name.eq_ignore_ascii_case("sb/rust"));
+/// }
+/// ```
+pub fn community_pda(program_id: &Pubkey, name: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[COMMUNITY_SEED, &community_name_seed(name)], program_id)
+}
+
+/// Comment receipt PDA: `[post, commenter, "comment"]`
+pub fn comment_receipt_pda(program_id: &Pubkey, post: &Pubkey, commenter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[post.as_ref(), commenter.as_ref(), COMMENT_RECEIPT_SEED], program_id)
+}
+
+/// Bookmark PDA: `[user, post, "bookmark"]`
+pub fn bookmark_pda(program_id: &Pubkey, user: &Pubkey, post: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[user.as_ref(), post.as_ref(), BOOKMARK_SEED], program_id)
+}
+
+/// Paged index PDA: `[key, seed, page (u32 LE)]`
+pub fn paged_index_pda(program_id: &Pubkey, key: &Pubkey, seed: &[u8], page: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[key.as_ref(), seed, &page.to_le_bytes()], program_id)
+}
+
+/// Member index page PDA: `[community, "members", page (u32 LE)]`
+pub fn member_index_pda(program_id: &Pubkey, community: &Pubkey, page: u32) -> (Pubkey, u8) {
+    paged_index_pda(program_id, community, MEMBER_INDEX_SEED, page)
+}
+
+/// Follower index page PDA: `[user, "followers", page (u32 LE)]`
+pub fn follower_index_pda(program_id: &Pubkey, user: &Pubkey, page: u32) -> (Pubkey, u8) {
+    paged_index_pda(program_id, user, FOLLOWER_INDEX_SEED, page)
+}
+
+/// Following index page PDA: `[user, "following", page (u32 LE)]`
+pub fn following_index_pda(program_id: &Pubkey, user: &Pubkey, page: u32) -> (Pubkey, u8) {
+    paged_index_pda(program_id, user, FOLLOWING_INDEX_SEED, page)
+}
+
+/// Community feed PDA: `[community, "feed"]`
+pub fn community_feed_pda(program_id: &Pubkey, community: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[community.as_ref(), COMMUNITY_FEED_SEED], program_id)
+}
+
+/// Mute preferences PDA: `[user, "mutes"]`
+pub fn mute_preferences_pda(program_id: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[user.as_ref(), MUTE_PREFERENCES_SEED], program_id)
+}
+
+/// Leaderboard PDA: `["leaderboard"]`
+pub fn leaderboard_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LEADERBOARD_SEED], program_id)
+}
+
+/// Like record PDA: `[post, liker, "like"]`
+pub fn like_record_pda(program_id: &Pubkey, post: &Pubkey, liker: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[post.as_ref(), liker.as_ref(), LIKE_RECORD_SEED], program_id)
+}
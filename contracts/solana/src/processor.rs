@@ -1,25 +1,64 @@
 
 use crate::{
     error::BlocksError,
+    event::Event,
     instruction::ContractInstruction,
+    pda::{
+        block_record_pda, bookmark_pda, follow_request_pda, FOLLOW_REQUEST_SEED, community_pda, community_name_seed, normalize_community_name, COMMUNITY_SEED, leaderboard_pda, LEADERBOARD_SEED, like_record_pda, LIKE_RECORD_SEED, comment_receipt_pda, community_feed_pda, follow_record_pda, membership_pda, mute_preferences_pda, paged_index_pda, post_index_pda, post_pda, profile_pda,
+        program_state_pda, report_pda, normalize_username, username_registry_pda, BLOCK_RECORD_SEED, BOOKMARK_SEED, COMMENT_RECEIPT_SEED, COMMUNITY_FEED_SEED, FOLLOW_RECORD_SEED, MEMBERSHIP_SEED,
+        MUTE_PREFERENCES_SEED, POST_INDEX_SEED, POST_SEED, PROFILE_SEED, PROGRAM_STATE_SEED, REPORT_SEED, USERNAME_SEED,
+    },
     state::{
         pack_profile_into_slice, pack_post_into_slice, pack_community_into_slice, 
-        Profile, Post, Community, PostRating, 
-        unpack_profile_from_slice, unpack_post_from_slice, unpack_community_from_slice
+        pack_membership_into_slice, pack_program_state_into_slice, pack_block_record_into_slice,
+        pack_follow_record_into_slice, pack_post_index_into_slice, pack_report_into_slice, Profile,
+        Post, Community, Membership, PostRating, ProgramState, Role, UcrTier, BlockRecord,
+        FollowRecord, PostIndex, Report, UsernameRegistry, REPORT_HIDE_THRESHOLD, KILL_ZONE_REPORT_THRESHOLD,
+        starts_in_kill_zone,
+        pack_username_registry_into_slice, unpack_username_registry_from_slice,
+        pack_comment_receipt_into_slice, unpack_comment_receipt_from_slice, CommentReceipt,
+        pack_bookmark_into_slice, Bookmark, account_version, decode_account, pack_decoded_account, DecodedAccount,
+        ACCOUNT_VERSION, EDIT_WINDOW_SECONDS, SECONDS_PER_DAY, pack_paged_index_into_slice, unpack_paged_index_from_slice, MemberIndex,
+        FollowerIndex, FollowingIndex, PagedIndex, CommunityFeed, pack_community_feed_into_slice,
+        unpack_community_feed_from_slice, MutePreferences, pack_mute_preferences_into_slice, unpack_mute_preferences_from_slice,
+        MAX_MUTED_WORDS, MAX_MUTED_WORD_LEN,
+        RatingConfig, MAX_BATCH_LIKES, MAX_COMMENTS_PER_USER_PER_POST, MAX_REPLY_DEPTH, UCR_PER_COMMENT, UCR_PER_MIRROR, UCR_PER_TIP,
+        CONQUEROR_MILESTONE_UCR_BONUS, CommunityPostRules, FollowRequest, pack_follow_request_into_slice,
+        unpack_follow_request_from_slice,
+        IMAGE_URI_SCHEMES, MAX_BIO_LEN, MAX_URI_LEN, MAX_USERNAME_LEN, MAX_POST_CONTENT_LEN, MAX_POST_IMAGES, MAX_COMMUNITY_NAME_LEN, MAX_COMMUNITY_MEMBERS, MIN_COMMUNITY_NAME_LEN,
+        MAX_COMMUNITY_DESCRIPTION_LEN, MAX_COMMUNITY_RULES, MAX_COMMUNITY_RULE_LEN, DUPLICATE_CONTENT_WINDOW, VerificationLevel, MAX_INITIAL_MEMBERS,
+        LikeRecord, pack_like_record_into_slice, Leaderboard, LEADERBOARD_LEN, pack_leaderboard_into_slice, unpack_leaderboard_from_slice,
+        SB_COMMUNITY_PREFIX, TIP_LAMPORTS_PER_UCR, UCR_DECAY_FLOOR, UCR_DECAY_PER_DAY, VERIFICATION_MIN_POSTS, VERIFICATION_THRESHOLD,
+        meets_verification_threshold, profile_stats,
+        unpack_profile_from_slice, unpack_post_from_slice, unpack_community_from_slice,
+        unpack_membership_from_slice, unpack_program_state_from_slice, unpack_block_record_from_slice,
+        unpack_follow_record_from_slice, unpack_post_index_from_slice,
+        unpack_live_profile_from_slice, unpack_live_post_from_slice, unpack_live_community_from_slice,
     },
 };
 use borsh::{BorshDeserialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
-    entrypoint::ProgramResult,
+    entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE},
+    hash::hash,
     msg,
     program_error::ProgramError,
     program::{invoke, invoke_signed},
     pubkey::Pubkey,
-    system_instruction,
+    system_instruction::{self, MAX_PERMITTED_DATA_LENGTH},
     sysvar::{rent::Rent, Sysvar},
     clock::Clock,
 };
+#[cfg(feature = "profiling")]
+use solana_program::log::sol_log_compute_units;
+
+// `msg!` for tracing that isn't worth its compute cost outside debug-logs builds
+macro_rules! debug_msg {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "debug-logs")]
+        msg!($($arg)*);
+    };
+}
 
 pub struct Processor {}
 
@@ -29,24 +68,44 @@ impl Processor {
         accounts: &[AccountInfo],
         instruction_data: &[u8],
     ) -> ProgramResult {
-        let instruction = ContractInstruction::try_from_slice(&instruction_data)
+        let instruction = ContractInstruction::try_from_slice(instruction_data)
             .map_err(|_| ProgramError::InvalidInstructionData)?;
 
-        match instruction {
-            ContractInstruction::CreateProfile { username, bio, profile_image, cover_image } => {
-                Self::process_create_profile(program_id, accounts, username, bio, profile_image, cover_image)
+        // Bracket the handler with the remaining compute units so its cost shows in the logs
+        #[cfg(feature = "profiling")]
+        sol_log_compute_units();
+
+        let result = match instruction {
+            ContractInstruction::CreateProfile {
+                username,
+                bio,
+                profile_image,
+                cover_image,
+                profile_image_hash,
+                cover_image_hash,
+            } => {
+                Self::process_create_profile(
+                    program_id,
+                    accounts,
+                    username,
+                    bio,
+                    profile_image,
+                    cover_image,
+                    profile_image_hash,
+                    cover_image_hash,
+                )
             }
             ContractInstruction::UpdateProfile { bio, profile_image, cover_image } => {
                 Self::process_update_profile(program_id, accounts, bio, profile_image, cover_image)
             }
-            ContractInstruction::CreatePost { content, images } => {
-                Self::process_create_post(program_id, accounts, content, images)
+            ContractInstruction::CreatePost { content, images, content_hash, allow_comments } => {
+                Self::process_create_post(program_id, accounts, content, images, content_hash, allow_comments)
             }
             ContractInstruction::LikePost { post_id } => {
                 Self::process_like_post(program_id, accounts, post_id)
             }
-            ContractInstruction::CommentOnPost { content, parent_id } => {
-                Self::process_comment(program_id, accounts, content, parent_id)
+            ContractInstruction::CommentOnPost { content, parent_id, images } => {
+                Self::process_comment(program_id, accounts, content, parent_id, images)
             }
             ContractInstruction::FollowProfile { profile_id } => {
                 Self::process_follow(program_id, accounts, profile_id)
@@ -58,16 +117,140 @@ impl Processor {
                 Self::process_create_community(program_id, accounts, name, description, avatar, rules)
             }
             ContractInstruction::JoinCommunity { community_id } => {
-                // Here we need to correctly handle the type mismatch
-                // The instruction expects u64, but the function expects Pubkey
-                // Let's extract the Pubkey from the account info
-                let accounts_iter = &mut accounts.iter();
-                let community_account = next_account_info(accounts_iter)?;
-                Self::process_join_community(program_id, accounts, *community_account.key)
+                Self::process_join_community(program_id, accounts, community_id)
             }
-        }
+            ContractInstruction::LeaveCommunity { community_id } => {
+                Self::process_leave_community(program_id, accounts, community_id)
+            }
+            ContractInstruction::VerifyProfile => {
+                Self::process_verify_profile(program_id, accounts)
+            }
+            ContractInstruction::DeletePost { post_id, force } => {
+                Self::process_delete_post(program_id, accounts, post_id, force)
+            }
+            ContractInstruction::DeleteProfile => {
+                Self::process_delete_profile(program_id, accounts)
+            }
+            ContractInstruction::EditPost { post_id, content, images } => {
+                Self::process_edit_post(program_id, accounts, post_id, content, images)
+            }
+            ContractInstruction::BlockUser { target } => {
+                Self::process_block_user(program_id, accounts, target)
+            }
+            ContractInstruction::UnblockUser { target } => {
+                Self::process_unblock_user(program_id, accounts, target)
+            }
+            ContractInstruction::AddModerator { target } => {
+                Self::process_add_moderator(program_id, accounts, target)
+            }
+            ContractInstruction::ModeratePost { post_id } => {
+                Self::process_moderate_post(program_id, accounts, post_id)
+            }
+            ContractInstruction::TransferCommunityOwnership { new_owner } => {
+                Self::process_transfer_community_ownership(program_id, accounts, new_owner)
+            }
+            ContractInstruction::TipPost { post_id, amount } => {
+                Self::process_tip_post(program_id, accounts, post_id, amount)
+            }
+            ContractInstruction::DecayUcr => {
+                Self::process_decay_ucr(program_id, accounts)
+            }
+            ContractInstruction::QuotePost { post_id, content } => {
+                Self::process_quote_post(program_id, accounts, post_id, content)
+            }
+            ContractInstruction::ReportPost { post_id, reason } => {
+                Self::process_report_post(program_id, accounts, post_id, reason)
+            }
+            ContractInstruction::ChangeUsername { new_username } => {
+                Self::process_change_username(program_id, accounts, new_username)
+            }
+            ContractInstruction::LikePosts { post_ids } => {
+                Self::process_like_posts(program_id, accounts, post_ids)
+            }
+            ContractInstruction::SetRatingConfig { config } => {
+                Self::process_set_rating_config(program_id, accounts, config)
+            }
+            ContractInstruction::InitializeProgram => {
+                Self::process_initialize_program(program_id, accounts)
+            }
+            ContractInstruction::CreatePostInCommunity { content, images, community_id, content_hash } => {
+                Self::process_create_post_in_community(program_id, accounts, content, images, community_id, content_hash)
+            }
+            ContractInstruction::PinPost { post_id } => {
+                Self::process_pin_post(program_id, accounts, post_id)
+            }
+            ContractInstruction::UnpinPost => {
+                Self::process_unpin_post(program_id, accounts)
+            }
+            ContractInstruction::PinCommunityPost { post_id } => {
+                Self::process_pin_community_post(program_id, accounts, post_id)
+            }
+            ContractInstruction::UnpinCommunityPost => {
+                Self::process_unpin_community_post(program_id, accounts)
+            }
+            ContractInstruction::BookmarkPost { post_id } => {
+                Self::process_bookmark_post(program_id, accounts, post_id)
+            }
+            ContractInstruction::RemoveBookmark => {
+                Self::process_remove_bookmark(program_id, accounts)
+            }
+            ContractInstruction::MigrateAccount => {
+                Self::process_migrate_account(program_id, accounts)
+            }
+            ContractInstruction::SetVerification { target, verified } => {
+                Self::process_set_verification(program_id, accounts, target, verified)
+            }
+            ContractInstruction::ReconcileState { profiles, posts, communities } => {
+                Self::process_reconcile_state(program_id, accounts, profiles, posts, communities)
+            }
+            ContractInstruction::DeleteComment { comment_id, parent_id, force } => {
+                Self::process_delete_comment(program_id, accounts, comment_id, parent_id, force)
+            }
+            ContractInstruction::AddMutedWord { word } => {
+                Self::process_add_muted_word(program_id, accounts, word)
+            }
+            ContractInstruction::RemoveMutedWord { word } => {
+                Self::process_remove_muted_word(program_id, accounts, word)
+            }
+            ContractInstruction::SoftDeleteAccount => {
+                Self::process_soft_delete_account(program_id, accounts)
+            }
+            ContractInstruction::SetCommunityPostRules { rules } => {
+                Self::process_set_community_post_rules(program_id, accounts, rules)
+            }
+            ContractInstruction::SetProfilePrivacy { is_private } => {
+                Self::process_set_profile_privacy(program_id, accounts, is_private)
+            }
+            ContractInstruction::ApproveFollow { follower } => {
+                Self::process_approve_follow(program_id, accounts, follower)
+            }
+            ContractInstruction::RejectFollow { follower } => {
+                Self::process_reject_follow(program_id, accounts, follower)
+            }
+            ContractInstruction::SetCommunityMaxMembers { max_members } => {
+                Self::process_set_community_max_members(program_id, accounts, max_members)
+            }
+            ContractInstruction::UpdateCommunity { description, avatar, rules } => {
+                Self::process_update_community(program_id, accounts, description, avatar, rules)
+            }
+            ContractInstruction::SetVerificationLevel { target, level } => {
+                Self::process_set_verification_level(program_id, accounts, target, level)
+            }
+            ContractInstruction::CreateCommunityWithMembers { name, description, avatar, rules, members } => {
+                Self::process_create_community_with_members(program_id, accounts, name, description, avatar, rules, members)
+            }
+            ContractInstruction::UpdateLeaderboard => {
+                Self::process_update_leaderboard(program_id, accounts)
+            }
+        };
+
+        #[cfg(feature = "profiling")]
+        sol_log_compute_units();
+
+        result
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn process_create_profile(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
@@ -75,38 +258,41 @@ impl Processor {
         bio: String,
         profile_image: String,
         cover_image: String,
+        profile_image_hash: Option<[u8; 32]>,
+        cover_image_hash: Option<[u8; 32]>,
     ) -> ProgramResult {
         msg!("Instruction: CreateProfile");
+        Self::expect_accounts(accounts, 5, "CreateProfile")?;
         let accounts_iter = &mut accounts.iter();
         
         // Parse accounts
         let user_account = next_account_info(accounts_iter)?;
         let profile_account = next_account_info(accounts_iter)?;
         let system_program = next_account_info(accounts_iter)?;
+        let program_state_account = next_account_info(accounts_iter)?;
+        let username_registry_account = next_account_info(accounts_iter)?;
 
         // Verify the user account is the signer
         if !user_account.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
-        // Verify system program is correct
-        if system_program.key != &solana_program::system_program::id() {
-            return Err(ProgramError::InvalidAccountData);
-        }
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[user_account, profile_account, program_state_account, username_registry_account])?;
 
-        // Generate PDA for profile account
-        let seeds = [
-            user_account.key.as_ref(),
-            b"profile",
-            username.as_bytes(),
-        ];
-        
-        // Find the PDA - this should match what the client calculated
-        let (expected_pda, bump_seed) = Pubkey::find_program_address(&seeds, program_id);
+        // Verify the system program is the real one
+        Self::assert_system_program(system_program)?;
+
+        // Enforce the profile field limits
+        Self::validate_username(&username)?;
+        Self::validate_profile_fields(&bio, &profile_image, &cover_image)?;
+
+        // Find the profile PDA - this should match what the client calculated
+        let (expected_pda, bump_seed) = profile_pda(program_id, user_account.key, &username);
         
-        // Debug logs
-        msg!("Expected PDA: {}", expected_pda);
-        msg!("Provided profile account: {}", profile_account.key);
+        // Debug logs, only in debug-logs builds
+        debug_msg!("Expected PDA: {}", expected_pda);
+        debug_msg!("Provided profile account: {}", profile_account.key);
         
         // Verify the PDA matches the profile account
         if expected_pda != *profile_account.key {
@@ -114,96 +300,30 @@ impl Processor {
             return Err(ProgramError::InvalidArgument);
         }
 
-        // Check if the profile account needs to be created
-        if profile_account.owner != program_id {
-            msg!("Creating profile account as a PDA");
-            
-            // Calculate rent - REDUCED SPACE FOR MEMORY MANAGEMENT
-            let rent = Rent::get()?;
-            // Reduced from 1024 to a more reasonable size
-            let space = 512; // Reduced space for the profile data to avoid out of memory errors
-            let lamports = rent.minimum_balance(space);
-            
-            msg!("Creating account with space: {} bytes, lamports: {}", space, lamports);
-            
-            // Create signer seeds array for PDA
-            let signer_seeds = [
-                user_account.key.as_ref(),
-                b"profile",
-                username.as_bytes(),
-                &[bump_seed],
-            ];
-            
-            // IMPROVED APPROACH: Split account creation into 3 steps:
-            // 1. Transfer lamports to the PDA
-            msg!("Step 1: Transferring lamports to PDA");
-            let transfer_ix = system_instruction::transfer(
-                user_account.key,
-                profile_account.key,
-                lamports,
-            );
-            
-            invoke(
-                &transfer_ix,
-                &[
-                    user_account.clone(),
-                    profile_account.clone(),
-                    system_program.clone(),
-                ],
-            )?;
-            msg!("Lamports transferred successfully");
-            
-            // 2. Allocate space for the account - REDUCED SPACE
-            msg!("Step 2: Allocating space for PDA");
-            let allocate_ix = system_instruction::allocate(
-                profile_account.key,
-                space as u64,
-            );
-            
-            invoke_signed(
-                &allocate_ix,
-                &[
-                    profile_account.clone(),
-                    system_program.clone(),
-                ],
-                &[&signer_seeds],
-            )?;
-            msg!("Space allocated successfully");
-            
-            // 3. Assign the account to our program
-            msg!("Step 3: Assigning PDA ownership to program");
-            let assign_ix = system_instruction::assign(
-                profile_account.key,
-                program_id,
-            );
-            
-            invoke_signed(
-                &assign_ix,
-                &[
-                    profile_account.clone(),
-                    system_program.clone(),
-                ],
-                &[&signer_seeds],
-            )?;
-            msg!("Ownership assigned successfully");
-            
-            msg!("PDA account created successfully with 3-step approach");
-        } else {
-            msg!("Profile account already exists, proceeding to initialize");
+        // Never overwrite an existing profile; changes go through UpdateProfile
+        if profile_account.owner == program_id {
+            msg!("Profile {} already exists", profile_account.key);
+            return Err(BlocksError::ProfileAlreadyExists.into());
         }
 
-        // Initialize the Profile struct
-        // Limit the lengths of strings to prevent memory issues
-        let max_len = 128; // Maximum length for string fields
-        let username = if username.len() > max_len { username[0..max_len].to_string() } else { username };
-        let bio = if bio.len() > max_len { bio[0..max_len].to_string() } else { bio };
-        let profile_image = if profile_image.len() > max_len { profile_image[0..max_len].to_string() } else { profile_image };
-        let cover_image = if cover_image.len() > max_len { cover_image[0..max_len].to_string() } else { cover_image };
+        // Verify the username registry account is the expected PDA
+        let normalized_username = normalize_username(&username);
+        let (expected_registry, registry_bump) = username_registry_pda(program_id, &username);
+        if expected_registry != *username_registry_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Usernames are unique across the program
+        if username_registry_account.owner == program_id {
+            msg!("Username {} is already taken", normalized_username);
+            return Err(BlocksError::ProfileAlreadyExists.into());
+        }
 
+        // Initialize the Profile struct
         let clock = Clock::get()?;
         let current_timestamp = clock.unix_timestamp as u64;
-
-        msg!("Initializing profile data with limited string lengths");
+        
+        debug_msg!("Initializing profile data");
         
         let profile = Profile {
             is_initialized: true,
@@ -219,13 +339,70 @@ impl Processor {
             posts_count: 0,
             last_post_timestamp: 0,
             daily_post_count: 0,
-            is_verified: false,
+            legacy_verified: false,
+            ucr_tier: UcrTier::from_score(100),
+            last_decay_timestamp: 0,
+            is_spam: false,
+            profile_image_hash,
+            cover_image_hash,
+            pinned_post: None,
+            follower_index_pages: 0,
+            following_index_pages: 0,
+            total_likes_received: 0,
+            total_comments_received: 0,
+            last_post_id: 0,
+            is_deleted: false,
+            deleted_at: 0,
+            milestone_awarded: false,
+            is_private: false,
+            updated_at: current_timestamp,
+            recent_content: Default::default(),
+            verification_level: VerificationLevel::None,
         };
 
+        // Create the profile account at its PDA; the address is predictable, so it may be pre-funded
+        let space = profile.required_space()?;
+        debug_msg!("Creating profile account with space: {} bytes", space);
+        Self::allocate_pda_account(
+            program_id,
+            user_account,
+            profile_account,
+            system_program,
+            space,
+            &[user_account.key.as_ref(), PROFILE_SEED, profile.username.as_bytes(), &[bump_seed]],
+        )?;
+        
+        // Pre-existing accounts must end up rent exempt
+        Self::assert_rent_exempt(profile_account)?;
+
         // Serialize and save the profile data
-        msg!("Serializing profile data to account");
+        debug_msg!("Serializing profile data to account");
         pack_profile_into_slice(&profile, &mut profile_account.data.borrow_mut())?;
 
+        // Claim the username
+        let registry = UsernameRegistry {
+            is_initialized: true,
+            username: normalized_username,
+            owner: *user_account.key,
+            profile: *profile_account.key,
+        };
+        Self::create_pda_account(
+            program_id,
+            user_account,
+            username_registry_account,
+            system_program,
+            registry.required_space()?,
+            &[USERNAME_SEED, registry.username.as_bytes(), &[registry_bump]],
+        )?;
+        pack_username_registry_into_slice(&registry, &mut username_registry_account.data.borrow_mut())?;
+
+        // Track the new profile in the program state
+        let mut state = Self::load_program_state(program_id, program_state_account)?;
+        state.profiles_count = state.profiles_count.checked_add(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        pack_program_state_into_slice(&state, &mut program_state_account.data.borrow_mut())?;
+
+        Event::ProfileCreated { profile: *profile_account.key, owner: *user_account.key }.emit()?;
+        
         msg!("Profile created successfully");
         Ok(())
     }
@@ -238,23 +415,34 @@ impl Processor {
         cover_image: String,
     ) -> ProgramResult {
         msg!("Instruction: UpdateProfile");
+        Self::expect_accounts(accounts, 3, "UpdateProfile")?;
         let accounts_iter = &mut accounts.iter();
         
         let user_account = next_account_info(accounts_iter)?;
         let profile_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
         
         // Verify the user account is the signer
         if !user_account.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
         
+        // Verify the system program is the real one
+        Self::assert_system_program(system_program)?;
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[user_account, profile_account])?;
+        
+        // Enforce the profile field limits
+        Self::validate_profile_fields(&bio, &profile_image, &cover_image)?;
+        
         // Verify the profile account is owned by our program
         if profile_account.owner != program_id {
             return Err(ProgramError::IncorrectProgramId);
         }
         
         // Deserialize the profile data
-        let mut profile = unpack_profile_from_slice(&profile_account.data.borrow())?;
+        let mut profile = unpack_live_profile_from_slice(&profile_account.data.borrow())?;
         
         // Verify the profile is owned by the user
         if profile.owner != *user_account.key {
@@ -266,9 +454,22 @@ impl Processor {
         profile.profile_image = profile_image;
         profile.cover_image = cover_image;
         
+        // Grow the account if the longer fields no longer fit
+        let space = profile.required_space()?;
+        if space > profile_account.data_len() {
+            Self::resize_account(profile_account, user_account, system_program, space)?;
+        }
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
         // Serialize and save the updated profile data
+        profile.updated_at = current_timestamp;
         pack_profile_into_slice(&profile, &mut profile_account.data.borrow_mut())?;
         
+        Event::ProfileUpdated { profile: *profile_account.key }.emit()?;
+        
         msg!("Profile updated successfully");
         Ok(())
     }
@@ -278,496 +479,4630 @@ impl Processor {
         accounts: &[AccountInfo],
         content: String,
         images: Vec<String>,
+        content_hash: Option<[u8; 32]>,
+        allow_comments: bool,
     ) -> ProgramResult {
         msg!("Instruction: CreatePost");
-        let accounts_iter = &mut accounts.iter();
+        Self::expect_accounts(accounts, 7, "CreatePost")?;
+        Self::create_post(program_id, accounts, &accounts[6], content, images, content_hash, None, None, allow_comments)
+    }
+
+    fn process_create_post_in_community(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        content: String,
+        images: Vec<String>,
+        community_id: u64,
+        content_hash: Option<[u8; 32]>,
+    ) -> ProgramResult {
+        msg!("Instruction: CreatePostInCommunity");
+        Self::expect_accounts(accounts, 10, "CreatePostInCommunity")?;
         
-        let user_account = next_account_info(accounts_iter)?;
-        let post_account = next_account_info(accounts_iter)?;
-        let profile_account = next_account_info(accounts_iter)?;
-        let system_program = next_account_info(accounts_iter)?;
+        let user_account = &accounts[0];
+        let post_account = &accounts[1];
+        let system_program = &accounts[3];
+        let community_account = &accounts[6];
+        let membership_account = &accounts[7];
+        let community_feed_account = &accounts[8];
+        let username_registry_account = &accounts[9];
         
-        // Verify the user account is the signer
-        if !user_account.is_signer {
-            return Err(ProgramError::MissingRequiredSignature);
-        }
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[community_feed_account])?;
         
-        // Verify the profile account is owned by our program
-        if profile_account.owner != program_id {
+        // Verify the community account is owned by our program
+        if community_account.owner != program_id {
             return Err(ProgramError::IncorrectProgramId);
         }
         
-        // Deserialize the profile data
-        let mut profile = unpack_profile_from_slice(&profile_account.data.borrow())?;
-        
-        // Verify the profile is owned by the user
-        if profile.owner != *user_account.key {
-            return Err(ProgramError::InvalidAccountData);
-        }
+        // Deserialize the community data
+        let community = unpack_live_community_from_slice(&community_account.data.borrow())?;
         
-        // Create the post account if it doesn't exist
-        if post_account.owner != program_id {
-            // Calculate rent
-            let rent = Rent::get()?;
-            let space = 2048; // Adjust as needed for your post struct
-            let lamports = rent.minimum_balance(space);
-            
-            // Create account
-            invoke(
-                &system_instruction::create_account(
-                    user_account.key,
-                    post_account.key,
-                    lamports,
-                    space as u64,
-                    program_id,
-                ),
-                &[
-                    user_account.clone(),
-                    post_account.clone(),
-                    system_program.clone(),
-                ],
-            )?;
+        // Verify the community ID matches
+        if community.id != community_id {
+            return Err(ProgramError::InvalidArgument);
         }
         
-        // Get current timestamp
-        let clock = Clock::get()?;
-        let current_timestamp = clock.unix_timestamp as u64;
-        
-        // Check if this is a new day for post count tracking
-        let seconds_in_day = 86400;
-        if current_timestamp - profile.last_post_timestamp > seconds_in_day {
-            profile.daily_post_count = 0;
-        }
+        // Only members can post to a community
+        Self::load_membership(program_id, community_account.key, user_account.key, membership_account)?;
         
-        // Increment post count
-        profile.posts_count += 1;
-        profile.daily_post_count += 1;
-        profile.last_post_timestamp = current_timestamp;
+        // Enforce the community's posting rules; create_post verifies the profile itself
+        let profile = unpack_live_profile_from_slice(&accounts[2].data.borrow())?;
+        community.post_rules.check(&profile)?;
         
-        // Initialize the Post struct
-        let post = Post {
-            is_initialized: true,
-            id: profile.posts_count,
-            author: *user_account.key,
+        Self::create_post(
+            program_id,
+            accounts,
+            username_registry_account,
             content,
-            timestamp: current_timestamp,
-            likes: 0,
-            comments: 0,
-            mirrors: 0,
             images,
-            rating: PostRating::None,
-            in_kill_zone: false,
-        };
-        
-        // Serialize and save the post data
-        pack_post_into_slice(&post, &mut post_account.data.borrow_mut())?;
-        
-        // Update the profile
-        pack_profile_into_slice(&profile, &mut profile_account.data.borrow_mut())?;
+            content_hash,
+            Some(*community_account.key),
+            None,
+            true,
+        )?;
         
-        msg!("Post created successfully");
-        Ok(())
+        // Record the post in the community's recent feed
+        let mut feed = Self::load_community_feed(program_id, community_account.key, user_account, community_feed_account, system_program)?;
+        feed.push(*post_account.key);
+        pack_community_feed_into_slice(&feed, &mut community_feed_account.data.borrow_mut())
     }
 
-    fn process_like_post(
+    fn process_pin_post(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
         post_id: u64,
     ) -> ProgramResult {
-        msg!("Instruction: LikePost");
+        msg!("Instruction: PinPost");
+        Self::expect_accounts(accounts, 3, "PinPost")?;
         let accounts_iter = &mut accounts.iter();
         
         let user_account = next_account_info(accounts_iter)?;
+        let profile_account = next_account_info(accounts_iter)?;
         let post_account = next_account_info(accounts_iter)?;
-        let author_profile_account = next_account_info(accounts_iter)?;
         
         // Verify the user account is the signer
         if !user_account.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
         
-        // Verify the post account is owned by our program
-        if post_account.owner != program_id {
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[profile_account])?;
+        
+        // Verify the profile and post accounts are owned by our program
+        if profile_account.owner != program_id || post_account.owner != program_id {
             return Err(ProgramError::IncorrectProgramId);
         }
         
-        // Verify the author profile account is owned by our program
-        if author_profile_account.owner != program_id {
-            return Err(ProgramError::IncorrectProgramId);
+        // Deserialize the profile data
+        let mut profile = unpack_live_profile_from_slice(&profile_account.data.borrow())?;
+        
+        // Verify the profile is owned by the user
+        if profile.owner != *user_account.key {
+            return Err(ProgramError::InvalidAccountData);
         }
         
         // Deserialize the post data
-        let mut post = unpack_post_from_slice(&post_account.data.borrow())?;
+        let post = unpack_live_post_from_slice(&post_account.data.borrow())?;
         
         // Verify the post ID matches
         if post.id != post_id {
             return Err(ProgramError::InvalidArgument);
         }
         
-        // Deserialize the author profile data
-        let mut author_profile = unpack_profile_from_slice(&author_profile_account.data.borrow())?;
-        
-        // Verify the author profile matches the post author
-        if author_profile.owner != post.author {
-            return Err(ProgramError::InvalidArgument);
+        // Users can only pin their own posts
+        if post.author != *user_account.key {
+            return Err(BlocksError::NotPostOwner.into());
         }
         
-        // Increment like count
-        post.likes += 1;
-        
-        // Update post rating based on new like count
-        post.rating = PostRating::from_likes(post.likes);
-        
-        // Update kill zone status
-        post.in_kill_zone = post.likes < 0;
+        profile.pinned_post = Some(*post_account.key);
         
-        // Update author's UCR score based on the like
-        // Simple algorithm: +1 UCR point per like
-        author_profile.user_credit_rating += 1;
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
         
-        // Serialize and save the updated post data
-        pack_post_into_slice(&post, &mut post_account.data.borrow_mut())?;
+        // Serialize and save the updated profile data
+        profile.updated_at = current_timestamp;
+        pack_profile_into_slice(&profile, &mut profile_account.data.borrow_mut())?;
         
-        // Serialize and save the updated author profile data
-        pack_profile_into_slice(&author_profile, &mut author_profile_account.data.borrow_mut())?;
+        Event::PinnedPostChanged { account: *profile_account.key, pinned_post: profile.pinned_post }.emit()?;
         
-        msg!("Post liked successfully");
+        msg!("Post pinned successfully");
         Ok(())
     }
 
-    fn process_comment(
+    fn process_unpin_post(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        content: String,
-        parent_id: u64,
     ) -> ProgramResult {
-        msg!("Instruction: CommentOnPost");
+        msg!("Instruction: UnpinPost");
+        Self::expect_accounts(accounts, 2, "UnpinPost")?;
         let accounts_iter = &mut accounts.iter();
         
         let user_account = next_account_info(accounts_iter)?;
-        let comment_account = next_account_info(accounts_iter)?;
-        let parent_post_account = next_account_info(accounts_iter)?;
-        let user_profile_account = next_account_info(accounts_iter)?;
-        let system_program = next_account_info(accounts_iter)?;
+        let profile_account = next_account_info(accounts_iter)?;
         
         // Verify the user account is the signer
         if !user_account.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
         
-        // Verify the parent post account is owned by our program
-        if parent_post_account.owner != program_id {
-            return Err(ProgramError::IncorrectProgramId);
-        }
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[profile_account])?;
         
-        // Verify the user profile account is owned by our program
-        if user_profile_account.owner != program_id {
+        // Verify the profile account is owned by our program
+        if profile_account.owner != program_id {
             return Err(ProgramError::IncorrectProgramId);
         }
         
-        // Deserialize the parent post data
-        let mut parent_post = unpack_post_from_slice(&parent_post_account.data.borrow())?;
-        
-        // Verify the parent post ID matches
-        if parent_post.id != parent_id {
-            return Err(ProgramError::InvalidArgument);
-        }
-        
-        // Deserialize the user profile data
-        let mut user_profile = unpack_profile_from_slice(&user_profile_account.data.borrow())?;
+        // Deserialize the profile data
+        let mut profile = unpack_live_profile_from_slice(&profile_account.data.borrow())?;
         
-        // Verify the user profile is owned by the user
-        if user_profile.owner != *user_account.key {
-            return Err(ProgramError::InvalidArgument);
+        // Verify the profile is owned by the user
+        if profile.owner != *user_account.key {
+            return Err(ProgramError::InvalidAccountData);
         }
         
-        // Create the comment account if it doesn't exist
-        if comment_account.owner != program_id {
-            // Calculate rent
-            let rent = Rent::get()?;
-            let space = 1024; // Adjust as needed for your comment struct
-            let lamports = rent.minimum_balance(space);
-            
-            // Create account
-            invoke(
-                &system_instruction::create_account(
-                    user_account.key,
-                    comment_account.key,
-                    lamports,
-                    space as u64,
-                    program_id,
-                ),
-                &[
-                    user_account.clone(),
-                    comment_account.clone(),
-                    system_program.clone(),
-                ],
-            )?;
-        }
+        profile.pinned_post = None;
         
         // Get current timestamp
         let clock = Clock::get()?;
         let current_timestamp = clock.unix_timestamp as u64;
         
-        // Increment post count for the user
-        user_profile.posts_count += 1;
-        
-        // Increment comment count for the parent post
-        parent_post.comments += 1;
+        // Serialize and save the updated profile data
+        profile.updated_at = current_timestamp;
+        pack_profile_into_slice(&profile, &mut profile_account.data.borrow_mut())?;
         
-        // Initialize the Comment as a Post struct
-        let comment = Post {
-            is_initialized: true,
-            id: user_profile.posts_count,
-            author: *user_account.key,
-            content,
-            timestamp: current_timestamp,
-            likes: 0,
-            comments: 0,
-            mirrors: 0,
-            images: vec![],
-            rating: PostRating::None,
-            in_kill_zone: false,
-        };
+        Event::PinnedPostChanged { account: *profile_account.key, pinned_post: None }.emit()?;
         
-        // Serialize and save the comment data
-        pack_post_into_slice(&comment, &mut comment_account.data.borrow_mut())?;
-        
-        // Update the parent post
-        pack_post_into_slice(&parent_post, &mut parent_post_account.data.borrow_mut())?;
-        
-        // Update the user profile
-        pack_profile_into_slice(&user_profile, &mut user_profile_account.data.borrow_mut())?;
-        
-        msg!("Comment created successfully");
+        msg!("Post unpinned successfully");
         Ok(())
     }
 
-    fn process_follow(
+    fn process_pin_community_post(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        profile_id: Pubkey,
+        post_id: u64,
     ) -> ProgramResult {
-        msg!("Instruction: FollowProfile");
+        msg!("Instruction: PinCommunityPost");
+        Self::expect_accounts(accounts, 3, "PinCommunityPost")?;
         let accounts_iter = &mut accounts.iter();
         
-        let follower_account = next_account_info(accounts_iter)?;
-        let followed_profile_account = next_account_info(accounts_iter)?;
-        let follower_profile_account = next_account_info(accounts_iter)?;
+        let owner_account = next_account_info(accounts_iter)?;
+        let community_account = next_account_info(accounts_iter)?;
+        let post_account = next_account_info(accounts_iter)?;
         
-        // Verify the follower account is the signer
-        if !follower_account.is_signer {
+        // Verify the owner account is the signer
+        if !owner_account.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
         
-        // Verify the followed profile account is owned by our program
-        if followed_profile_account.owner != program_id {
-            return Err(ProgramError::IncorrectProgramId);
-        }
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[community_account])?;
         
-        // Verify the follower profile account is owned by our program
-        if follower_profile_account.owner != program_id {
+        // Verify the community and post accounts are owned by our program
+        if community_account.owner != program_id || post_account.owner != program_id {
             return Err(ProgramError::IncorrectProgramId);
         }
         
-        // Verify the followed profile account key matches the profile_id
-        if *followed_profile_account.key != profile_id {
-            return Err(ProgramError::InvalidArgument);
+        // Deserialize the community data
+        let mut community = unpack_live_community_from_slice(&community_account.data.borrow())?;
+        
+        // Only the community owner may pin posts
+        if community.owner != *owner_account.key {
+            return Err(BlocksError::NotCommunityOwner.into());
         }
         
-        // Deserialize the followed profile data
-        let mut followed_profile = unpack_profile_from_slice(&followed_profile_account.data.borrow())?;
+        // Deserialize the post data
+        let post = unpack_live_post_from_slice(&post_account.data.borrow())?;
         
-        // Deserialize the follower profile data
-        let mut follower_profile = unpack_profile_from_slice(&follower_profile_account.data.borrow())?;
+        // Verify the post ID matches
+        if post.id != post_id {
+            return Err(ProgramError::InvalidArgument);
+        }
         
-        // Verify the follower profile is owned by the follower
-        if follower_profile.owner != *follower_account.key {
+        // Only posts within the community can be pinned to it
+        if post.community != Some(*community_account.key) {
             return Err(ProgramError::InvalidArgument);
         }
         
-        // Increment followers count for the followed profile
-        followed_profile.followers_count += 1;
+        community.pinned_post = Some(*post_account.key);
         
-        // Increment following count for the follower profile
-        follower_profile.following_count += 1;
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
         
-        // Serialize and save the updated followed profile data
-        pack_profile_into_slice(&followed_profile, &mut followed_profile_account.data.borrow_mut())?;
+        // Serialize and save the updated community data
+        community.updated_at = current_timestamp;
+        pack_community_into_slice(&community, &mut community_account.data.borrow_mut())?;
         
-        // Serialize and save the updated follower profile data
-        pack_profile_into_slice(&follower_profile, &mut follower_profile_account.data.borrow_mut())?;
+        Event::PinnedPostChanged { account: *community_account.key, pinned_post: community.pinned_post }.emit()?;
         
-        msg!("Follow successful");
+        msg!("Community post pinned successfully");
         Ok(())
     }
 
-    fn process_unfollow(
+    fn process_unpin_community_post(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        profile_id: Pubkey,
     ) -> ProgramResult {
-        msg!("Instruction: UnfollowProfile");
+        msg!("Instruction: UnpinCommunityPost");
+        Self::expect_accounts(accounts, 2, "UnpinCommunityPost")?;
         let accounts_iter = &mut accounts.iter();
         
-        let follower_account = next_account_info(accounts_iter)?;
-        let followed_profile_account = next_account_info(accounts_iter)?;
-        let follower_profile_account = next_account_info(accounts_iter)?;
+        let owner_account = next_account_info(accounts_iter)?;
+        let community_account = next_account_info(accounts_iter)?;
         
-        // Verify the follower account is the signer
-        if !follower_account.is_signer {
+        // Verify the owner account is the signer
+        if !owner_account.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
         
-        // Verify the followed profile account is owned by our program
-        if followed_profile_account.owner != program_id {
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[community_account])?;
+        
+        // Verify the community account is owned by our program
+        if community_account.owner != program_id {
             return Err(ProgramError::IncorrectProgramId);
         }
         
-        // Verify the follower profile account is owned by our program
-        if follower_profile_account.owner != program_id {
-            return Err(ProgramError::IncorrectProgramId);
+        // Deserialize the community data
+        let mut community = unpack_live_community_from_slice(&community_account.data.borrow())?;
+        
+        // Only the community owner may unpin posts
+        if community.owner != *owner_account.key {
+            return Err(BlocksError::NotCommunityOwner.into());
         }
         
-        // Verify the followed profile account key matches the profile_id
-        if *followed_profile_account.key != profile_id {
-            return Err(ProgramError::InvalidArgument);
+        community.pinned_post = None;
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        // Serialize and save the updated community data
+        community.updated_at = current_timestamp;
+        pack_community_into_slice(&community, &mut community_account.data.borrow_mut())?;
+        
+        Event::PinnedPostChanged { account: *community_account.key, pinned_post: None }.emit()?;
+        
+        msg!("Community post unpinned successfully");
+        Ok(())
+    }
+
+    fn process_bookmark_post(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        post_id: u64,
+    ) -> ProgramResult {
+        msg!("Instruction: BookmarkPost");
+        Self::expect_accounts(accounts, 4, "BookmarkPost")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let user_account = next_account_info(accounts_iter)?;
+        let post_account = next_account_info(accounts_iter)?;
+        let bookmark_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        
+        // Verify the user account is the signer
+        if !user_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
         }
         
-        // Deserialize the followed profile data
-        let mut followed_profile = unpack_profile_from_slice(&followed_profile_account.data.borrow())?;
+        // Verify the system program is the real one
+        Self::assert_system_program(system_program)?;
         
-        // Deserialize the follower profile data
-        let mut follower_profile = unpack_profile_from_slice(&follower_profile_account.data.borrow())?;
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[user_account, bookmark_account])?;
         
-        // Verify the follower profile is owned by the follower
-        if follower_profile.owner != *follower_account.key {
+        // Verify the post account is owned by our program
+        if post_account.owner != program_id {
+            return Err(BlocksError::PostNotFound.into());
+        }
+        
+        // Deserialize the post data
+        let post = unpack_live_post_from_slice(&post_account.data.borrow())?;
+        
+        // Verify the post ID matches
+        if post.id != post_id {
             return Err(ProgramError::InvalidArgument);
         }
         
-        // Decrement followers count for the followed profile
-        if followed_profile.followers_count > 0 {
-            followed_profile.followers_count -= 1;
+        // Verify the bookmark account is the expected PDA
+        let (expected_pda, bump_seed) = bookmark_pda(program_id, user_account.key, post_account.key);
+        if expected_pda != *bookmark_account.key {
+            return Err(ProgramError::InvalidArgument);
         }
         
-        // Decrement following count for the follower profile
-        if follower_profile.following_count > 0 {
-            follower_profile.following_count -= 1;
+        // Reject duplicate bookmarks
+        if bookmark_account.owner == program_id {
+            return Err(BlocksError::AlreadyBookmarked.into());
         }
         
-        // Serialize and save the updated followed profile data
-        pack_profile_into_slice(&followed_profile, &mut followed_profile_account.data.borrow_mut())?;
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
         
-        // Serialize and save the updated follower profile data
-        pack_profile_into_slice(&follower_profile, &mut follower_profile_account.data.borrow_mut())?;
+        let bookmark = Bookmark {
+            is_initialized: true,
+            user: *user_account.key,
+            post: *post_account.key,
+            created_at: current_timestamp,
+        };
         
-        msg!("Unfollow successful");
+        let signer_seeds = [
+            user_account.key.as_ref(),
+            post_account.key.as_ref(),
+            BOOKMARK_SEED,
+            &[bump_seed],
+        ];
+        
+        // Create account
+        Self::create_pda_account(
+            program_id,
+            user_account,
+            bookmark_account,
+            system_program,
+            bookmark.required_space()?,
+            &signer_seeds,
+        )?;
+        
+        // Serialize and save the bookmark
+        pack_bookmark_into_slice(&bookmark, &mut bookmark_account.data.borrow_mut())?;
+        
+        Event::PostBookmarked { user: *user_account.key, post: *post_account.key }.emit()?;
+        
+        msg!("Post bookmarked successfully");
         Ok(())
     }
 
-    fn process_create_community(
+    fn process_remove_bookmark(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        name: String,
-        description: String,
-        avatar: String,
-        rules: Vec<String>,
     ) -> ProgramResult {
-        msg!("Instruction: CreateCommunity");
+        msg!("Instruction: RemoveBookmark");
+        Self::expect_accounts(accounts, 3, "RemoveBookmark")?;
         let accounts_iter = &mut accounts.iter();
         
-        let owner_account = next_account_info(accounts_iter)?;
-        let community_account = next_account_info(accounts_iter)?;
+        let user_account = next_account_info(accounts_iter)?;
+        let post_account = next_account_info(accounts_iter)?;
+        let bookmark_account = next_account_info(accounts_iter)?;
+        
+        // Verify the user account is the signer
+        if !user_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[user_account, bookmark_account])?;
+        
+        // Verify the bookmark account is the expected PDA
+        let (expected_pda, _bump_seed) = bookmark_pda(program_id, user_account.key, post_account.key);
+        if expected_pda != *bookmark_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Only existing bookmarks can be removed
+        if bookmark_account.owner != program_id {
+            return Err(BlocksError::BookmarkNotFound.into());
+        }
+        
+        // Close the bookmark and refund its rent to the user
+        Self::close_account(bookmark_account, user_account)?;
+        
+        Event::BookmarkRemoved { user: *user_account.key, post: *post_account.key }.emit()?;
+        
+        msg!("Bookmark removed successfully");
+        Ok(())
+    }
+
+    fn process_add_muted_word(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        word: String,
+    ) -> ProgramResult {
+        msg!("Instruction: AddMutedWord");
+        Self::expect_accounts(accounts, 3, "AddMutedWord")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let user_account = next_account_info(accounts_iter)?;
+        let preferences_account = next_account_info(accounts_iter)?;
         let system_program = next_account_info(accounts_iter)?;
         
-        // Verify the owner account is the signer
-        if !owner_account.is_signer {
+        // Verify the user account is the signer
+        if !user_account.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
         
-        // Create the community account if it doesn't exist
-        if community_account.owner != program_id {
-            // Calculate rent
-            let rent = Rent::get()?;
-            let space = 2048; // Adjust as needed for your community struct
-            let lamports = rent.minimum_balance(space);
-            
-            // Create account
-            invoke(
-                &system_instruction::create_account(
-                    owner_account.key,
-                    community_account.key,
-                    lamports,
-                    space as u64,
-                    program_id,
-                ),
-                &[
-                    owner_account.clone(),
-                    community_account.clone(),
-                    system_program.clone(),
-                ],
-            )?;
+        // Verify the system program is the real one
+        Self::assert_system_program(system_program)?;
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[user_account, preferences_account])?;
+        
+        // Muted words must be non-empty and fit the fixed-size list
+        if word.is_empty() {
+            return Err(ProgramError::InvalidArgument);
+        }
+        if word.len() > MAX_MUTED_WORD_LEN {
+            msg!("Muted words may not exceed {} bytes", MAX_MUTED_WORD_LEN);
+            return Err(BlocksError::FieldTooLong.into());
         }
         
-        // Check if this is a subBlocks community
-        let is_sb_community = name.starts_with("sb/");
+        // Verify the mute preferences account is the expected PDA
+        let (expected_pda, bump_seed) = mute_preferences_pda(program_id, user_account.key);
+        if expected_pda != *preferences_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
         
-        // Initialize the Community struct
-        let community = Community {
-            is_initialized: true,
-            id: 0, // This should be assigned by the program state
-            name,
-            description,
-            avatar,
-            owner: *owner_account.key,
-            member_count: 1, // Owner is the first member
-            rules,
-            is_sb_community,
+        // Load the existing list, or create it on first use
+        let mut preferences = if preferences_account.owner == program_id {
+            unpack_mute_preferences_from_slice(&preferences_account.data.borrow())?
+        } else {
+            Self::create_pda_account(
+                program_id,
+                user_account,
+                preferences_account,
+                system_program,
+                MutePreferences::required_space(),
+                &[user_account.key.as_ref(), MUTE_PREFERENCES_SEED, &[bump_seed]],
+            )?;
+            MutePreferences {
+                is_initialized: true,
+                owner: *user_account.key,
+                words: Vec::new(),
+            }
         };
         
-        // Serialize and save the community data
-        pack_community_into_slice(&community, &mut community_account.data.borrow_mut())?;
+        // Reject duplicates and keep the list bounded
+        if preferences.contains(&word) {
+            return Err(BlocksError::AlreadyMuted.into());
+        }
+        if preferences.words.len() >= MAX_MUTED_WORDS {
+            msg!("At most {} words can be muted", MAX_MUTED_WORDS);
+            return Err(BlocksError::MutedWordsFull.into());
+        }
+        preferences.words.push(word.clone());
         
-        msg!("Community created successfully");
+        // Serialize and save the mute preferences
+        pack_mute_preferences_into_slice(&preferences, &mut preferences_account.data.borrow_mut())?;
+        
+        Event::MutedWordAdded { user: *user_account.key, word }.emit()?;
+        
+        msg!("Muted word added successfully");
         Ok(())
     }
 
-    fn process_join_community(
+    fn process_remove_muted_word(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        community_id: Pubkey,
+        word: String,
     ) -> ProgramResult {
-        msg!("Instruction: JoinCommunity");
+        msg!("Instruction: RemoveMutedWord");
+        Self::expect_accounts(accounts, 2, "RemoveMutedWord")?;
         let accounts_iter = &mut accounts.iter();
         
         let user_account = next_account_info(accounts_iter)?;
-        let community_account = next_account_info(accounts_iter)?;
+        let preferences_account = next_account_info(accounts_iter)?;
         
         // Verify the user account is the signer
         if !user_account.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
         
-        // Verify the community account is owned by our program
-        if community_account.owner != program_id {
-            return Err(ProgramError::IncorrectProgramId);
-        }
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[user_account, preferences_account])?;
         
-        // Verify the community account key matches the community_id
-        if *community_account.key != community_id {
+        // Verify the mute preferences account is the expected PDA
+        let (expected_pda, _bump_seed) = mute_preferences_pda(program_id, user_account.key);
+        if expected_pda != *preferences_account.key {
             return Err(ProgramError::InvalidArgument);
         }
         
-        // Deserialize the community data
-        let mut community = unpack_community_from_slice(&community_account.data.borrow())?;
+        // Users without a list have nothing muted
+        if preferences_account.owner != program_id {
+            return Err(BlocksError::MutedWordNotFound.into());
+        }
         
-        // Increment member count
-        community.member_count += 1;
+        // Deserialize the mute preferences
+        let mut preferences = unpack_mute_preferences_from_slice(&preferences_account.data.borrow())?;
         
-        // Serialize and save the updated community data
-        pack_community_into_slice(&community, &mut community_account.data.borrow_mut())?;
+        if !preferences.remove(&word) {
+            return Err(BlocksError::MutedWordNotFound.into());
+        }
         
-        msg!("Joined community successfully");
+        // Close the emptied list and refund its rent, otherwise save it
+        if preferences.words.is_empty() {
+            Self::close_account(preferences_account, user_account)?;
+        } else {
+            pack_mute_preferences_into_slice(&preferences, &mut preferences_account.data.borrow_mut())?;
+        }
+        
+        Event::MutedWordRemoved { user: *user_account.key, word }.emit()?;
+        
+        msg!("Muted word removed successfully");
         Ok(())
     }
-}
+
+    fn process_soft_delete_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        msg!("Instruction: SoftDeleteAccount");
+        Self::expect_accounts(accounts, 2, "SoftDeleteAccount")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let owner_account = next_account_info(accounts_iter)?;
+        let target_account = next_account_info(accounts_iter)?;
+        
+        // Verify the owner account is the signer
+        if !owner_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[target_account])?;
+        
+        // Verify the target account is owned by our program
+        if target_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        let mut decoded = decode_account(&target_account.data.borrow())?;
+        
+        // Only the wallet that owns the record may delete it
+        if decoded.authority() != *owner_account.key {
+            return Err(ProgramError::IllegalOwner);
+        }
+        
+        // Records on an older schema have no room for the deletion flag until migrated
+        if decoded.required_space()? > target_account.data_len() {
+            msg!("Account must be migrated before it can be deleted");
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+        
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        let (is_deleted, deleted_at) = match &mut decoded {
+            DecodedAccount::Profile(profile) => (&mut profile.is_deleted, &mut profile.deleted_at),
+            DecodedAccount::Post(post) => (&mut post.is_deleted, &mut post.deleted_at),
+            DecodedAccount::Community(community) => (&mut community.is_deleted, &mut community.deleted_at),
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        if *is_deleted {
+            return Err(BlocksError::AlreadyDeleted.into());
+        }
+        *is_deleted = true;
+        *deleted_at = current_timestamp;
+        match &mut decoded {
+            DecodedAccount::Profile(profile) => profile.updated_at = current_timestamp,
+            DecodedAccount::Community(community) => community.updated_at = current_timestamp,
+            _ => {}
+        }
+        
+        // Serialize and save the flagged record
+        pack_decoded_account(&decoded, &mut target_account.data.borrow_mut())?;
+        
+        Event::AccountSoftDeleted { account: *target_account.key, owner: *owner_account.key }.emit()?;
+        
+        msg!("Account soft-deleted successfully");
+        Ok(())
+    }
+
+    fn process_set_community_post_rules(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        rules: CommunityPostRules,
+    ) -> ProgramResult {
+        msg!("Instruction: SetCommunityPostRules");
+        Self::expect_accounts(accounts, 2, "SetCommunityPostRules")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let owner_account = next_account_info(accounts_iter)?;
+        let community_account = next_account_info(accounts_iter)?;
+        
+        // Verify the owner account is the signer
+        if !owner_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[community_account])?;
+        
+        // Verify the community account is owned by our program
+        if community_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Deserialize the community data
+        let mut community = unpack_live_community_from_slice(&community_account.data.borrow())?;
+        
+        // Only the owner may change the posting rules
+        if community.owner != *owner_account.key {
+            return Err(BlocksError::NotCommunityOwner.into());
+        }
+        
+        community.post_rules = rules;
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        // Serialize and save the updated community data
+        community.updated_at = current_timestamp;
+        pack_community_into_slice(&community, &mut community_account.data.borrow_mut())?;
+        
+        Event::CommunityPostRulesUpdated { community: *community_account.key, rules }.emit()?;
+        
+        msg!("Community post rules updated successfully");
+        Ok(())
+    }
+
+    fn process_set_community_max_members(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        max_members: u64,
+    ) -> ProgramResult {
+        msg!("Instruction: SetCommunityMaxMembers");
+        Self::expect_accounts(accounts, 2, "SetCommunityMaxMembers")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let owner_account = next_account_info(accounts_iter)?;
+        let community_account = next_account_info(accounts_iter)?;
+        
+        // Verify the owner account is the signer
+        if !owner_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[community_account])?;
+        
+        // The owner is always a member, and no community grows past the global bound
+        if max_members == 0 || max_members > MAX_COMMUNITY_MEMBERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Verify the community account is owned by our program
+        if community_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Deserialize the community data
+        let mut community = unpack_live_community_from_slice(&community_account.data.borrow())?;
+        
+        // Only the owner may cap the community
+        if community.owner != *owner_account.key {
+            return Err(BlocksError::NotCommunityOwner.into());
+        }
+        
+        // Lowering the cap below the current size only stops new joins
+        community.max_members = max_members;
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        // Serialize and save the updated community data
+        community.updated_at = current_timestamp;
+        pack_community_into_slice(&community, &mut community_account.data.borrow_mut())?;
+        
+        Event::CommunityMaxMembersUpdated { community: *community_account.key, max_members }.emit()?;
+        
+        msg!("Community member cap updated successfully");
+        Ok(())
+    }
+
+    fn process_update_community(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        description: Option<String>,
+        avatar: Option<String>,
+        rules: Option<Vec<String>>,
+    ) -> ProgramResult {
+        msg!("Instruction: UpdateCommunity");
+        Self::expect_accounts(accounts, 3, "UpdateCommunity")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let owner_account = next_account_info(accounts_iter)?;
+        let community_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        
+        // Verify the owner account is the signer
+        if !owner_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the system program is the real one
+        Self::assert_system_program(system_program)?;
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[owner_account, community_account])?;
+        
+        // Verify the community account is owned by our program
+        if community_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Deserialize the community data
+        let mut community = unpack_live_community_from_slice(&community_account.data.borrow())?;
+        
+        // Only the owner may edit the community
+        if community.owner != *owner_account.key {
+            return Err(BlocksError::NotCommunityOwner.into());
+        }
+        
+        // Apply the provided fields and leave the rest unchanged
+        if let Some(description) = description {
+            community.description = description;
+        }
+        if let Some(avatar) = avatar {
+            community.avatar = avatar;
+        }
+        if let Some(rules) = rules {
+            community.rules = rules;
+        }
+        
+        // Enforce the community field limits
+        Self::validate_community_fields(&community.description, &community.avatar, &community.rules)?;
+        
+        // Grow the account if the longer fields no longer fit
+        let space = community.required_space()?;
+        if space > community_account.data_len() {
+            Self::resize_account(community_account, owner_account, system_program, space)?;
+        }
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        // Serialize and save the updated community data
+        community.updated_at = current_timestamp;
+        pack_community_into_slice(&community, &mut community_account.data.borrow_mut())?;
+        
+        Event::CommunityUpdated { community: *community_account.key }.emit()?;
+        
+        msg!("Community updated successfully");
+        Ok(())
+    }
+
+    fn process_migrate_account(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        msg!("Instruction: MigrateAccount");
+        Self::expect_accounts(accounts, 3, "MigrateAccount")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let owner_account = next_account_info(accounts_iter)?;
+        let target_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        
+        // Verify the owner account is the signer
+        if !owner_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the system program is the real one
+        Self::assert_system_program(system_program)?;
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[owner_account, target_account])?;
+        
+        // Verify the target account is owned by our program
+        if target_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Accounts already on the current schema are left untouched
+        let from_version = account_version(&target_account.data.borrow()).ok_or(ProgramError::InvalidAccountData)?;
+        if from_version == ACCOUNT_VERSION {
+            msg!("Account is already at version {}", ACCOUNT_VERSION);
+            return Ok(());
+        }
+        
+        // Decoding upgrades the record in memory
+        let decoded = decode_account(&target_account.data.borrow())?;
+        
+        // Only the wallet that owns the record may migrate it
+        if decoded.authority() != *owner_account.key {
+            return Err(ProgramError::IllegalOwner);
+        }
+        
+        // Grow the account if the current schema needs more room
+        let space = decoded.required_space()?;
+        if space > target_account.data_len() {
+            Self::resize_account(target_account, owner_account, system_program, space)?;
+        }
+        
+        // Serialize and save the upgraded record
+        pack_decoded_account(&decoded, &mut target_account.data.borrow_mut())?;
+        
+        Event::AccountMigrated { account: *target_account.key, from_version, to_version: ACCOUNT_VERSION }.emit()?;
+        
+        msg!("Account migrated from version {} to {}", from_version, ACCOUNT_VERSION);
+        Ok(())
+    }
+
+    // Shared by CreatePost, CreatePostInCommunity and QuotePost, which list the same leading accounts
+    #[allow(clippy::too_many_arguments)]
+    fn create_post(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        username_registry_account: &AccountInfo,
+        content: String,
+        images: Vec<String>,
+        content_hash: Option<[u8; 32]>,
+        community: Option<Pubkey>,
+        quoted_post: Option<Pubkey>,
+        allow_comments: bool,
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        
+        let user_account = next_account_info(accounts_iter)?;
+        let post_account = next_account_info(accounts_iter)?;
+        let profile_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        let program_state_account = next_account_info(accounts_iter)?;
+        let post_index_account = next_account_info(accounts_iter)?;
+        
+        // Verify the user account is the signer
+        if !user_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the system program is the real one
+        Self::assert_system_program(system_program)?;
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[user_account, post_account, profile_account, program_state_account, post_index_account])?;
+        
+        // Enforce the post content limits
+        Self::validate_post_content(&content, &images)?;
+        
+        // Verify the profile account is owned by our program
+        if profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Deserialize the profile data
+        let mut profile = unpack_live_profile_from_slice(&profile_account.data.borrow())?;
+        
+        // Verify the profile is owned by the user
+        if profile.owner != *user_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        
+        // Verify the profile account is the user's real profile PDA, not a look-alike
+        Self::assert_registered_profile(program_id, profile_account, &profile, username_registry_account)?;
+        
+        // Spam accounts can't post
+        if profile.is_spam_user() {
+            return Err(BlocksError::SpamUser.into());
+        }
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        // Enforce the tier's daily posting cap, resetting the count on a new day
+        let stats = profile_stats(&profile, current_timestamp);
+        if stats.remaining_daily_posts == 0 {
+            msg!("Daily limit of {} posts reached", stats.tier.daily_post_limit());
+            return Err(BlocksError::DailyPostLimitReached.into());
+        }
+        profile.daily_post_count = profile.daily_posts_used(current_timestamp);
+        
+        // Reject a copy of a body the user posted within the duplicate window
+        let body_hash = hash(content.as_bytes()).to_bytes();
+        if profile.is_duplicate_content(&body_hash, current_timestamp) {
+            msg!("Identical content was posted in the last {} seconds", DUPLICATE_CONTENT_WINDOW);
+            return Err(BlocksError::DuplicateContent.into());
+        }
+        profile.record_content(body_hash, current_timestamp);
+        
+        // Increment post count and issue the next post id
+        profile.posts_count = profile.posts_count.checked_add(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        profile.last_post_id = profile.last_post_id.checked_add(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        profile.daily_post_count = profile.daily_post_count.checked_add(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        profile.last_post_timestamp = current_timestamp;
+        
+        // Initialize the Post struct
+        let post = Post {
+            is_initialized: true,
+            id: profile.last_post_id,
+            author: *user_account.key,
+            content,
+            timestamp: current_timestamp,
+            likes: 0,
+            comments: 0,
+            mirrors: 0,
+            images,
+            rating: PostRating::None,
+            in_kill_zone: starts_in_kill_zone(&profile),
+            edited_at: 0,
+            community,
+            hidden: false,
+            total_tips: 0,
+            quoted_post,
+            report_count: 0,
+            content_hash,
+            parent: None,
+            thread_root: *post_account.key,
+            depth: 0,
+            is_deleted: false,
+            deleted_at: 0,
+            ucr_window_start: current_timestamp,
+            ucr_window_earned: 0,
+            allow_comments,
+            earned_milestone: false,
+        };
+        
+        // Create the post account at its PDA
+        Self::create_post_account(program_id, user_account, post_account, system_program, &post)?;
+        
+        // Serialize and save the post data
+        pack_post_into_slice(&post, &mut post_account.data.borrow_mut())?;
+        
+        // Update the profile
+        profile.updated_at = current_timestamp;
+        pack_profile_into_slice(&profile, &mut profile_account.data.borrow_mut())?;
+        
+        // Track the new post in the program state
+        let mut state = Self::load_program_state(program_id, program_state_account)?;
+        state.posts_count = state.posts_count.checked_add(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        pack_program_state_into_slice(&state, &mut program_state_account.data.borrow_mut())?;
+        
+        // Record the post in the user's recent post index
+        let mut post_index = Self::load_post_index(program_id, user_account, post_index_account, system_program)?;
+        post_index.push(*post_account.key);
+        pack_post_index_into_slice(&post_index, &mut post_index_account.data.borrow_mut())?;
+        
+        Event::PostCreated { post: *post_account.key, author: *user_account.key, id: post.id }.emit()?;
+        
+        msg!("Post created successfully");
+        Ok(())
+    }
+
+    fn process_like_post(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        post_id: u64,
+    ) -> ProgramResult {
+        msg!("Instruction: LikePost");
+        Self::expect_accounts(accounts, 8, "LikePost")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let user_account = next_account_info(accounts_iter)?;
+        let post_account = next_account_info(accounts_iter)?;
+        let author_profile_account = next_account_info(accounts_iter)?;
+        let liker_profile_account = next_account_info(accounts_iter)?;
+        let program_state_account = next_account_info(accounts_iter)?;
+        let author_registry_account = next_account_info(accounts_iter)?;
+        let like_record_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        
+        // Verify the user account is the signer
+        if !user_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the system program is the real one
+        Self::assert_system_program(system_program)?;
+        
+        // The user pays for the like record
+        Self::assert_writable(&[user_account])?;
+        
+        // Verify the liker profile account is owned by our program
+        if liker_profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Deserialize the liker profile data
+        let liker_profile = unpack_live_profile_from_slice(&liker_profile_account.data.borrow())?;
+        
+        // Verify the liker profile is owned by the user
+        if liker_profile.owner != *user_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        
+        // Likes are weighted by the liker's tier
+        let weight = liker_profile.tier().like_weight();
+        let config = Self::load_rating_config(program_id, program_state_account)?;
+        Self::apply_like(
+            program_id,
+            user_account,
+            weight,
+            &config,
+            post_account,
+            author_profile_account,
+            author_registry_account,
+            like_record_account,
+            system_program,
+            post_id,
+        )?;
+        
+        msg!("Post liked successfully");
+        Ok(())
+    }
+
+    fn process_like_posts(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        post_ids: Vec<u64>,
+    ) -> ProgramResult {
+        msg!("Instruction: LikePosts");
+        
+        // Bound the batch so it fits in a single transaction's compute budget
+        if post_ids.is_empty() || post_ids.len() > MAX_BATCH_LIKES {
+            msg!("LikePosts takes 1-{} posts", MAX_BATCH_LIKES);
+            return Err(BlocksError::BatchTooLarge.into());
+        }
+        Self::expect_accounts(accounts, 4 + 4 * post_ids.len(), "LikePosts")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let user_account = next_account_info(accounts_iter)?;
+        let liker_profile_account = next_account_info(accounts_iter)?;
+        let program_state_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        
+        // Verify the user account is the signer
+        if !user_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the system program is the real one
+        Self::assert_system_program(system_program)?;
+        
+        // The user pays for the like records
+        Self::assert_writable(&[user_account])?;
+        
+        // Verify the liker profile account is owned by our program
+        if liker_profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Deserialize the liker profile data
+        let liker_profile = unpack_live_profile_from_slice(&liker_profile_account.data.borrow())?;
+        
+        // Verify the liker profile is owned by the user
+        if liker_profile.owner != *user_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        
+        // Apply each like; any failure aborts the whole batch
+        let weight = liker_profile.tier().like_weight();
+        let config = Self::load_rating_config(program_id, program_state_account)?;
+        let mut liked: Vec<&Pubkey> = Vec::with_capacity(post_ids.len());
+        for post_id in post_ids {
+            let post_account = next_account_info(accounts_iter)?;
+            let author_profile_account = next_account_info(accounts_iter)?;
+            let author_registry_account = next_account_info(accounts_iter)?;
+            let like_record_account = next_account_info(accounts_iter)?;
+            
+            // A batch can't like the same post twice
+            if liked.contains(&post_account.key) {
+                return Err(BlocksError::AlreadyLiked.into());
+            }
+            liked.push(post_account.key);
+            
+            Self::apply_like(
+                program_id,
+                user_account,
+                weight,
+                &config,
+                post_account,
+                author_profile_account,
+                author_registry_account,
+                like_record_account,
+                system_program,
+                post_id,
+            )?;
+        }
+        
+        msg!("Posts liked successfully");
+        Ok(())
+    }
+
+    fn process_set_rating_config(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        config: RatingConfig,
+    ) -> ProgramResult {
+        msg!("Instruction: SetRatingConfig");
+        Self::expect_accounts(accounts, 2, "SetRatingConfig")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let authority_account = next_account_info(accounts_iter)?;
+        let program_state_account = next_account_info(accounts_iter)?;
+        
+        // Verify the authority account is the signer
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[program_state_account])?;
+        
+        // Deserialize the program state
+        let mut state = Self::load_program_state(program_id, program_state_account)?;
+        
+        // Only the program authority can change settings
+        Self::require_authority(authority_account, &state)?;
+        
+        // Reject thresholds that would make ratings ambiguous
+        if !config.is_valid() {
+            msg!("Rating thresholds must be non-zero and strictly increasing");
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        state.rating_config = config;
+        
+        // Serialize and save the updated program state
+        pack_program_state_into_slice(&state, &mut program_state_account.data.borrow_mut())?;
+        
+        Event::RatingConfigUpdated { config }.emit()?;
+        
+        msg!("Rating config updated successfully");
+        Ok(())
+    }
+
+    fn process_set_verification(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        target: Pubkey,
+        verified: bool,
+    ) -> ProgramResult {
+        msg!("Instruction: SetVerification");
+        Self::expect_accounts(accounts, 3, "SetVerification")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let authority_account = next_account_info(accounts_iter)?;
+        let program_state_account = next_account_info(accounts_iter)?;
+        let profile_account = next_account_info(accounts_iter)?;
+        
+        // Verify the authority account is the signer
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[profile_account])?;
+        
+        // Only the program authority can override verification
+        let state = Self::load_program_state(program_id, program_state_account)?;
+        Self::require_authority(authority_account, &state)?;
+        
+        // Verify the profile account is owned by our program
+        if profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Verify the profile account key matches the target
+        if *profile_account.key != target {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Deserialize the profile data
+        let mut profile = unpack_live_profile_from_slice(&profile_account.data.borrow())?;
+        
+        // Nothing to record when the profile already has the requested status; granting
+        // therefore keeps any higher badge the profile holds
+        if verified == profile.is_verified() {
+            msg!("Verification is already {}", verified);
+            return Ok(());
+        }
+        let level = if verified { VerificationLevel::Identity } else { VerificationLevel::None };
+        profile.set_verification_level(level);
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        // Serialize and save the updated profile data
+        profile.updated_at = current_timestamp;
+        pack_profile_into_slice(&profile, &mut profile_account.data.borrow_mut())?;
+        
+        Event::VerificationChanged { profile: target, verified }.emit()?;
+        
+        msg!("Verification set to {}", verified);
+        Ok(())
+    }
+
+    fn process_set_verification_level(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        target: Pubkey,
+        level: VerificationLevel,
+    ) -> ProgramResult {
+        msg!("Instruction: SetVerificationLevel");
+        Self::expect_accounts(accounts, 3, "SetVerificationLevel")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let authority_account = next_account_info(accounts_iter)?;
+        let program_state_account = next_account_info(accounts_iter)?;
+        let profile_account = next_account_info(accounts_iter)?;
+        
+        // Verify the authority account is the signer
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[profile_account])?;
+        
+        // Only the program authority can assign badges
+        let state = Self::load_program_state(program_id, program_state_account)?;
+        Self::require_authority(authority_account, &state)?;
+        
+        // Verify the profile account is owned by our program
+        if profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Verify the profile account key matches the target
+        if *profile_account.key != target {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Deserialize the profile data
+        let mut profile = unpack_live_profile_from_slice(&profile_account.data.borrow())?;
+        
+        profile.set_verification_level(level);
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        // Serialize and save the updated profile data
+        profile.updated_at = current_timestamp;
+        pack_profile_into_slice(&profile, &mut profile_account.data.borrow_mut())?;
+        
+        Event::VerificationLevelChanged { profile: target, level }.emit()?;
+        
+        msg!("Verification level set to {:?}", level);
+        Ok(())
+    }
+
+    fn process_reconcile_state(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        profiles: u64,
+        posts: u64,
+        communities: u64,
+    ) -> ProgramResult {
+        msg!("Instruction: ReconcileState");
+        Self::expect_accounts(accounts, 2, "ReconcileState")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let authority_account = next_account_info(accounts_iter)?;
+        let program_state_account = next_account_info(accounts_iter)?;
+        
+        // Verify the authority account is the signer
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[program_state_account])?;
+        
+        // Deserialize the program state
+        let mut state = Self::load_program_state(program_id, program_state_account)?;
+        
+        // Only the program authority can repair counters
+        Self::require_authority(authority_account, &state)?;
+        
+        msg!(
+            "Reconciling counters: profiles {} -> {}, posts {} -> {}, communities {} -> {}",
+            state.profiles_count,
+            profiles,
+            state.posts_count,
+            posts,
+            state.communities_count,
+            communities
+        );
+        state.profiles_count = profiles;
+        state.posts_count = posts;
+        state.communities_count = communities;
+        
+        // Serialize and save the updated program state
+        pack_program_state_into_slice(&state, &mut program_state_account.data.borrow_mut())?;
+        
+        Event::StateReconciled { profiles, posts, communities }.emit()?;
+        
+        msg!("Program state reconciled successfully");
+        Ok(())
+    }
+
+    fn process_initialize_program(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        msg!("Instruction: InitializeProgram");
+        Self::expect_accounts(accounts, 3, "InitializeProgram")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let authority_account = next_account_info(accounts_iter)?;
+        let program_state_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        
+        // Verify the authority account is the signer
+        if !authority_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the system program is the real one
+        Self::assert_system_program(system_program)?;
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[authority_account, program_state_account])?;
+        
+        // Verify the program state account is the expected PDA
+        let (expected_pda, bump_seed) = program_state_pda(program_id);
+        if expected_pda != *program_state_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // The program can only be initialized once
+        if program_state_account.owner == program_id || program_state_account.lamports() > 0 {
+            msg!("Program state already exists");
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        
+        let state = ProgramState {
+            is_initialized: true,
+            profiles_count: 0,
+            posts_count: 0,
+            communities_count: 0,
+            authority: *authority_account.key,
+            rating_config: RatingConfig::default(),
+        };
+        
+        // Create account
+        Self::create_pda_account(
+            program_id,
+            authority_account,
+            program_state_account,
+            system_program,
+            state.required_space()?,
+            &[PROGRAM_STATE_SEED, &[bump_seed]],
+        )?;
+        
+        // Serialize and save the program state
+        pack_program_state_into_slice(&state, &mut program_state_account.data.borrow_mut())?;
+        
+        Event::ProgramInitialized { authority: state.authority }.emit()?;
+        
+        msg!("Program initialized successfully");
+        Ok(())
+    }
+
+    fn process_comment(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        content: String,
+        parent_id: u64,
+        images: Vec<String>,
+    ) -> ProgramResult {
+        msg!("Instruction: CommentOnPost");
+        Self::expect_accounts(accounts, 10, "CommentOnPost")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let user_account = next_account_info(accounts_iter)?;
+        let comment_account = next_account_info(accounts_iter)?;
+        let parent_post_account = next_account_info(accounts_iter)?;
+        let user_profile_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        let program_state_account = next_account_info(accounts_iter)?;
+        let block_record_account = next_account_info(accounts_iter)?;
+        let comment_receipt_account = next_account_info(accounts_iter)?;
+        let parent_author_profile_account = next_account_info(accounts_iter)?;
+        let parent_author_registry_account = next_account_info(accounts_iter)?;
+        
+        // Verify the user account is the signer
+        if !user_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the system program is the real one
+        Self::assert_system_program(system_program)?;
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[user_account, comment_account, parent_post_account, user_profile_account, program_state_account, comment_receipt_account, parent_author_profile_account])?;
+        
+        // Enforce the comment content and image limits
+        Self::validate_post_content(&content, &images)?;
+        
+        // Verify the parent post account is owned by our program
+        if parent_post_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Verify the user profile account is owned by our program
+        if user_profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Deserialize the parent post data
+        let mut parent_post = unpack_live_post_from_slice(&parent_post_account.data.borrow())?;
+        
+        // Verify the parent post ID matches
+        if parent_post.id != parent_id {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Respect the author's choice to turn off replies
+        if !parent_post.allow_comments {
+            return Err(BlocksError::CommentsDisabled.into());
+        }
+        
+        // Deserialize the user profile data
+        let mut user_profile = unpack_live_profile_from_slice(&user_profile_account.data.borrow())?;
+        
+        // Verify the user profile is owned by the user
+        if user_profile.owner != *user_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Spam accounts can't comment
+        if user_profile.is_spam_user() {
+            return Err(BlocksError::SpamUser.into());
+        }
+        
+        // Reject comments from users the parent author has blocked
+        if Self::is_blocked(program_id, &parent_post.author, user_account.key, block_record_account)? {
+            return Err(BlocksError::UserBlocked.into());
+        }
+        
+        // Keep reply chains bounded; comments on a top-level post start a new thread
+        let depth = parent_post.depth.checked_add(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        if depth > MAX_REPLY_DEPTH {
+            return Err(BlocksError::ReplyDepthExceeded.into());
+        }
+        let thread_root = if parent_post.parent.is_some() {
+            parent_post.thread_root
+        } else {
+            *parent_post_account.key
+        };
+        
+        // Only members can comment on community posts
+        if let Some(community) = parent_post.community {
+            let membership_account = next_account_info(accounts_iter)?;
+            Self::load_membership(program_id, &community, user_account.key, membership_account)?;
+        }
+        
+        // Verify the comment receipt account is the expected PDA
+        let (expected_receipt, receipt_bump) = comment_receipt_pda(program_id, parent_post_account.key, user_account.key);
+        if expected_receipt != *comment_receipt_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Cap how many times a user can comment on the same post
+        let existing_receipt = if comment_receipt_account.owner == program_id {
+            let receipt = unpack_comment_receipt_from_slice(&comment_receipt_account.data.borrow())?;
+            if receipt.count >= MAX_COMMENTS_PER_USER_PER_POST {
+                return Err(BlocksError::CommentLimitReached.into());
+            }
+            Some(receipt)
+        } else {
+            None
+        };
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        // Increment post count for the user and issue the comment's id
+        user_profile.posts_count = user_profile.posts_count.checked_add(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        user_profile.last_post_id = user_profile.last_post_id.checked_add(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        
+        // Increment comment count for the parent post
+        parent_post.comments = parent_post.comments.checked_add(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        
+        // Comments from other users earn the parent author UCR under the post's daily cap
+        let comment_reward = if parent_post.author != *user_account.key {
+            parent_post.capped_ucr_gain(UCR_PER_COMMENT, current_timestamp)
+        } else {
+            0
+        };
+        
+        // Initialize the Comment as a Post struct
+        let comment = Post {
+            is_initialized: true,
+            id: user_profile.last_post_id,
+            author: *user_account.key,
+            content,
+            timestamp: current_timestamp,
+            likes: 0,
+            comments: 0,
+            mirrors: 0,
+            images,
+            rating: PostRating::None,
+            in_kill_zone: starts_in_kill_zone(&user_profile),
+            edited_at: 0,
+            community: parent_post.community,
+            hidden: false,
+            total_tips: 0,
+            quoted_post: None,
+            report_count: 0,
+            content_hash: None,
+            parent: Some(*parent_post_account.key),
+            thread_root,
+            depth,
+            is_deleted: false,
+            deleted_at: 0,
+            ucr_window_start: current_timestamp,
+            ucr_window_earned: 0,
+            allow_comments: true,
+            earned_milestone: false,
+        };
+        
+        // Create the comment account at its PDA
+        Self::create_post_account(program_id, user_account, comment_account, system_program, &comment)?;
+        
+        // Serialize and save the comment data
+        pack_post_into_slice(&comment, &mut comment_account.data.borrow_mut())?;
+        
+        // Update the parent post
+        pack_post_into_slice(&parent_post, &mut parent_post_account.data.borrow_mut())?;
+        
+        // Update the user profile
+        user_profile.updated_at = current_timestamp;
+        pack_profile_into_slice(&user_profile, &mut user_profile_account.data.borrow_mut())?;
+        
+        // Track the new comment in the program state
+        let mut state = Self::load_program_state(program_id, program_state_account)?;
+        state.posts_count = state.posts_count.checked_add(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        pack_program_state_into_slice(&state, &mut program_state_account.data.borrow_mut())?;
+        
+        // Record the comment on the user's receipt for this post
+        match existing_receipt {
+            Some(mut receipt) => {
+                receipt.count = receipt.count.checked_add(1).ok_or(BlocksError::ArithmeticOverflow)?;
+                pack_comment_receipt_into_slice(&receipt, &mut comment_receipt_account.data.borrow_mut())?;
+            }
+            None => {
+                let receipt = CommentReceipt {
+                    is_initialized: true,
+                    post: *parent_post_account.key,
+                    commenter: *user_account.key,
+                    count: 1,
+                };
+                Self::create_pda_account(
+                    program_id,
+                    user_account,
+                    comment_receipt_account,
+                    system_program,
+                    receipt.required_space()?,
+                    &[
+                        parent_post_account.key.as_ref(),
+                        user_account.key.as_ref(),
+                        COMMENT_RECEIPT_SEED,
+                        &[receipt_bump],
+                    ],
+                )?;
+                pack_comment_receipt_into_slice(&receipt, &mut comment_receipt_account.data.borrow_mut())?;
+            }
+        }
+        
+        // Reward the parent author for comments from other users
+        if parent_post.author != *user_account.key {
+            if parent_author_profile_account.owner != program_id {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            let mut author_profile = unpack_live_profile_from_slice(&parent_author_profile_account.data.borrow())?;
+            if author_profile.owner != parent_post.author {
+                return Err(ProgramError::InvalidArgument);
+            }
+            Self::assert_registered_profile(program_id, parent_author_profile_account, &author_profile, parent_author_registry_account)?;
+            author_profile.user_credit_rating = author_profile
+                .user_credit_rating
+                .checked_add(comment_reward)
+                .ok_or(BlocksError::ArithmeticOverflow)?;
+            author_profile.total_comments_received = author_profile
+                .total_comments_received
+                .checked_add(1)
+                .ok_or(BlocksError::ArithmeticOverflow)?;
+            author_profile.refresh_ucr_status();
+            author_profile.updated_at = current_timestamp;
+            pack_profile_into_slice(&author_profile, &mut parent_author_profile_account.data.borrow_mut())?;
+        }
+        
+        Event::CommentCreated {
+            comment: *comment_account.key,
+            parent: *parent_post_account.key,
+            author: *user_account.key,
+        }.emit()?;
+        
+        msg!("Comment created successfully");
+        Ok(())
+    }
+
+    fn process_follow(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        profile_id: Pubkey,
+    ) -> ProgramResult {
+        msg!("Instruction: FollowProfile");
+        Self::expect_accounts(accounts, 8, "FollowProfile")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let follower_account = next_account_info(accounts_iter)?;
+        let followed_profile_account = next_account_info(accounts_iter)?;
+        let follower_profile_account = next_account_info(accounts_iter)?;
+        let block_record_account = next_account_info(accounts_iter)?;
+        let follow_record_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        let following_index_account = next_account_info(accounts_iter)?;
+        let follower_index_account = next_account_info(accounts_iter)?;
+        
+        // Verify the follower account is the signer
+        if !follower_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the system program is the real one
+        Self::assert_system_program(system_program)?;
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[follower_account, followed_profile_account, follower_profile_account, follow_record_account, following_index_account, follower_index_account])?;
+        
+        // Verify the followed profile account is owned by our program
+        if followed_profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Verify the follower profile account is owned by our program
+        if follower_profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Verify the followed profile account key matches the profile_id
+        if *followed_profile_account.key != profile_id {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Deserialize the followed profile data
+        let mut followed_profile = unpack_live_profile_from_slice(&followed_profile_account.data.borrow())?;
+        
+        // Deserialize the follower profile data
+        let mut follower_profile = unpack_live_profile_from_slice(&follower_profile_account.data.borrow())?;
+        
+        // Verify the follower profile is owned by the follower
+        if follower_profile.owner != *follower_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Reject follows from users the followed user has blocked
+        if Self::is_blocked(program_id, &followed_profile.owner, follower_account.key, block_record_account)? {
+            return Err(BlocksError::UserBlocked.into());
+        }
+        
+        // Reject duplicate follows
+        let bump_seed = Self::check_not_following(program_id, follower_account.key, &followed_profile.owner, follow_record_account)?;
+        
+        // Private profiles get a pending request instead of a follow
+        if followed_profile.is_private {
+            let follow_request_account = next_account_info(accounts_iter)?;
+            return Self::request_follow(program_id, follower_account, followed_profile_account, &followed_profile, follow_request_account, system_program);
+        }
+        
+        Self::establish_follow(
+            program_id,
+            follower_account,
+            follower_account.key,
+            &mut follower_profile,
+            &mut followed_profile,
+            follow_record_account,
+            bump_seed,
+            following_index_account,
+            follower_index_account,
+            system_program,
+        )?;
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        // Serialize and save the updated followed profile data
+        followed_profile.updated_at = current_timestamp;
+        pack_profile_into_slice(&followed_profile, &mut followed_profile_account.data.borrow_mut())?;
+        
+        // Serialize and save the updated follower profile data
+        follower_profile.updated_at = current_timestamp;
+        pack_profile_into_slice(&follower_profile, &mut follower_profile_account.data.borrow_mut())?;
+        
+        Event::ProfileFollowed { follower: *follower_account.key, followed: *followed_profile_account.key }.emit()?;
+        
+        msg!("Follow successful");
+        Ok(())
+    }
+
+    // Verify the follow record PDA and that the follow doesn't exist yet, returning the PDA's bump
+    fn check_not_following(
+        program_id: &Pubkey,
+        follower: &Pubkey,
+        followed: &Pubkey,
+        follow_record_account: &AccountInfo,
+    ) -> Result<u8, ProgramError> {
+        let (expected_pda, bump_seed) = follow_record_pda(program_id, follower, followed);
+        if expected_pda != *follow_record_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        if follow_record_account.owner == program_id {
+            let record = unpack_follow_record_from_slice(&follow_record_account.data.borrow())?;
+            if record.is_initialized {
+                return Err(BlocksError::AlreadyFollowing.into());
+            }
+        }
+        Ok(bump_seed)
+    }
+
+    // Record a follow: list it on both users' indexes, create the follow record and bump the
+    // counts. Shared by FollowProfile and ApproveFollow; the caller saves both profiles.
+    #[allow(clippy::too_many_arguments)]
+    fn establish_follow<'a>(
+        program_id: &Pubkey,
+        payer: &AccountInfo<'a>,
+        follower: &Pubkey,
+        follower_profile: &mut Profile,
+        followed_profile: &mut Profile,
+        follow_record_account: &AccountInfo<'a>,
+        bump_seed: u8,
+        following_index_account: &AccountInfo<'a>,
+        follower_index_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        // List the followed user on the follower's following index
+        let following_page = Self::add_to_index::<FollowingIndex>(
+            program_id,
+            &mut follower_profile.following_index_pages,
+            follower,
+            &followed_profile.owner,
+            payer,
+            following_index_account,
+            system_program,
+        )?;
+        
+        // List the follower on the followed user's follower index
+        let follower_page = Self::add_to_index::<FollowerIndex>(
+            program_id,
+            &mut followed_profile.follower_index_pages,
+            &followed_profile.owner,
+            follower,
+            payer,
+            follower_index_account,
+            system_program,
+        )?;
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        let record = FollowRecord {
+            is_initialized: true,
+            follower: *follower,
+            followed: followed_profile.owner,
+            created_at: current_timestamp,
+            follower_page: Some(follower_page),
+            following_page: Some(following_page),
+        };
+        
+        // Create the follow record account if it doesn't exist
+        if follow_record_account.owner != program_id {
+            let signer_seeds = [
+                follower.as_ref(),
+                followed_profile.owner.as_ref(),
+                FOLLOW_RECORD_SEED,
+                &[bump_seed],
+            ];
+            
+            // Create account
+            Self::create_pda_account(
+                program_id,
+                payer,
+                follow_record_account,
+                system_program,
+                record.required_space()?,
+                &signer_seeds,
+            )?;
+        }
+        
+        // Serialize and save the follow record
+        pack_follow_record_into_slice(&record, &mut follow_record_account.data.borrow_mut())?;
+        
+        // Increment followers count for the followed profile
+        followed_profile.followers_count = followed_profile.followers_count.checked_add(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        
+        // Increment following count for the follower profile
+        follower_profile.following_count = follower_profile.following_count.checked_add(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        
+        Ok(())
+    }
+
+    // Open a pending follow request on a private profile
+    fn request_follow<'a>(
+        program_id: &Pubkey,
+        follower_account: &AccountInfo<'a>,
+        followed_profile_account: &AccountInfo<'a>,
+        followed_profile: &Profile,
+        follow_request_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        Self::assert_writable(&[follow_request_account])?;
+        
+        // Verify the follow request account is the expected PDA
+        let (expected_pda, bump_seed) = follow_request_pda(program_id, follower_account.key, &followed_profile.owner);
+        if expected_pda != *follow_request_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // One pending request per pair of users
+        if follow_request_account.owner == program_id {
+            return Err(BlocksError::FollowRequestPending.into());
+        }
+        
+        let clock = Clock::get()?;
+        let request = FollowRequest {
+            is_initialized: true,
+            follower: *follower_account.key,
+            followed: followed_profile.owner,
+            created_at: clock.unix_timestamp as u64,
+        };
+        
+        let signer_seeds = [
+            follower_account.key.as_ref(),
+            followed_profile.owner.as_ref(),
+            FOLLOW_REQUEST_SEED,
+            &[bump_seed],
+        ];
+        Self::create_pda_account(
+            program_id,
+            follower_account,
+            follow_request_account,
+            system_program,
+            request.required_space()?,
+            &signer_seeds,
+        )?;
+        pack_follow_request_into_slice(&request, &mut follow_request_account.data.borrow_mut())?;
+        
+        Event::FollowRequested { follower: *follower_account.key, followed: *followed_profile_account.key }.emit()?;
+        
+        msg!("Follow request sent");
+        Ok(())
+    }
+
+    fn process_set_profile_privacy(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        is_private: bool,
+    ) -> ProgramResult {
+        msg!("Instruction: SetProfilePrivacy");
+        Self::expect_accounts(accounts, 2, "SetProfilePrivacy")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let user_account = next_account_info(accounts_iter)?;
+        let profile_account = next_account_info(accounts_iter)?;
+        
+        // Verify the user account is the signer
+        if !user_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[profile_account])?;
+        
+        // Verify the profile account is owned by our program
+        if profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Deserialize the profile data
+        let mut profile = unpack_live_profile_from_slice(&profile_account.data.borrow())?;
+        
+        // Verify the profile is owned by the user
+        if profile.owner != *user_account.key {
+            return Err(BlocksError::NotProfileOwner.into());
+        }
+        
+        profile.is_private = is_private;
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        // Serialize and save the updated profile data
+        profile.updated_at = current_timestamp;
+        pack_profile_into_slice(&profile, &mut profile_account.data.borrow_mut())?;
+        
+        Event::ProfilePrivacyUpdated { profile: *profile_account.key, is_private }.emit()?;
+        
+        msg!("Profile privacy updated successfully");
+        Ok(())
+    }
+
+    fn process_approve_follow(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        follower: Pubkey,
+    ) -> ProgramResult {
+        msg!("Instruction: ApproveFollow");
+        Self::expect_accounts(accounts, 11, "ApproveFollow")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let followed_account = next_account_info(accounts_iter)?;
+        let followed_profile_account = next_account_info(accounts_iter)?;
+        let follower_profile_account = next_account_info(accounts_iter)?;
+        let follow_request_account = next_account_info(accounts_iter)?;
+        let follower_account = next_account_info(accounts_iter)?;
+        let follow_record_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        let following_index_account = next_account_info(accounts_iter)?;
+        let follower_index_account = next_account_info(accounts_iter)?;
+        let block_record_account = next_account_info(accounts_iter)?;
+        let reverse_block_record_account = next_account_info(accounts_iter)?;
+        
+        // Verify the followed account is the signer
+        if !followed_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the system program is the real one
+        Self::assert_system_program(system_program)?;
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[followed_account, followed_profile_account, follower_profile_account, follow_request_account, follower_account, follow_record_account, following_index_account, follower_index_account])?;
+        
+        // Verify both profile accounts are owned by our program
+        if followed_profile_account.owner != program_id || follower_profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Only the owner of the requested profile may approve
+        let mut followed_profile = unpack_live_profile_from_slice(&followed_profile_account.data.borrow())?;
+        if followed_profile.owner != *followed_account.key {
+            return Err(BlocksError::NotProfileOwner.into());
+        }
+        
+        // Verify the follower's wallet and profile match the request
+        if *follower_account.key != follower {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let mut follower_profile = unpack_live_profile_from_slice(&follower_profile_account.data.borrow())?;
+        if follower_profile.owner != follower {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        Self::load_follow_request(program_id, &follower, followed_account.key, follow_request_account)?;
+        let bump_seed = Self::check_not_following(program_id, &follower, followed_account.key, follow_record_account)?;
+        
+        // A block placed since the request was made, by either side, stops it becoming a follow
+        if Self::is_blocked(program_id, followed_account.key, &follower, block_record_account)?
+            || Self::is_blocked(program_id, &follower, followed_account.key, reverse_block_record_account)?
+        {
+            return Err(BlocksError::UserBlocked.into());
+        }
+        
+        Self::establish_follow(
+            program_id,
+            followed_account,
+            &follower,
+            &mut follower_profile,
+            &mut followed_profile,
+            follow_record_account,
+            bump_seed,
+            following_index_account,
+            follower_index_account,
+            system_program,
+        )?;
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        // Serialize and save both profiles
+        followed_profile.updated_at = current_timestamp;
+        pack_profile_into_slice(&followed_profile, &mut followed_profile_account.data.borrow_mut())?;
+        follower_profile.updated_at = current_timestamp;
+        pack_profile_into_slice(&follower_profile, &mut follower_profile_account.data.borrow_mut())?;
+        
+        // The request is settled; refund its rent to the follower who paid it
+        Self::close_account(follow_request_account, follower_account)?;
+        
+        Event::FollowRequestApproved { follower, followed: *followed_profile_account.key }.emit()?;
+        Event::ProfileFollowed { follower, followed: *followed_profile_account.key }.emit()?;
+        
+        msg!("Follow request approved");
+        Ok(())
+    }
+
+    fn process_reject_follow(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        follower: Pubkey,
+    ) -> ProgramResult {
+        msg!("Instruction: RejectFollow");
+        Self::expect_accounts(accounts, 4, "RejectFollow")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let followed_account = next_account_info(accounts_iter)?;
+        let followed_profile_account = next_account_info(accounts_iter)?;
+        let follow_request_account = next_account_info(accounts_iter)?;
+        let follower_account = next_account_info(accounts_iter)?;
+        
+        // Verify the followed account is the signer
+        if !followed_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[follow_request_account, follower_account])?;
+        
+        // Verify the profile account is owned by our program
+        if followed_profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Only the owner of the requested profile may reject
+        let followed_profile = unpack_live_profile_from_slice(&followed_profile_account.data.borrow())?;
+        if followed_profile.owner != *followed_account.key {
+            return Err(BlocksError::NotProfileOwner.into());
+        }
+        
+        // The refund goes to the follower who paid for the request
+        if *follower_account.key != follower {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        Self::load_follow_request(program_id, &follower, followed_account.key, follow_request_account)?;
+        Self::close_account(follow_request_account, follower_account)?;
+        
+        Event::FollowRequestRejected { follower, followed: *followed_profile_account.key }.emit()?;
+        
+        msg!("Follow request rejected");
+        Ok(())
+    }
+
+    // Load the pending request from `follower` to `followed`, verifying its PDA
+    fn load_follow_request(
+        program_id: &Pubkey,
+        follower: &Pubkey,
+        followed: &Pubkey,
+        follow_request_account: &AccountInfo,
+    ) -> Result<FollowRequest, ProgramError> {
+        let (expected_pda, _bump_seed) = follow_request_pda(program_id, follower, followed);
+        if expected_pda != *follow_request_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        if follow_request_account.owner != program_id {
+            return Err(BlocksError::FollowRequestNotFound.into());
+        }
+        unpack_follow_request_from_slice(&follow_request_account.data.borrow())
+    }
+
+    fn process_unfollow(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        profile_id: Pubkey,
+    ) -> ProgramResult {
+        msg!("Instruction: UnfollowProfile");
+        Self::expect_accounts(accounts, 6, "UnfollowProfile")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let follower_account = next_account_info(accounts_iter)?;
+        let followed_profile_account = next_account_info(accounts_iter)?;
+        let follower_profile_account = next_account_info(accounts_iter)?;
+        let follow_record_account = next_account_info(accounts_iter)?;
+        let following_index_account = next_account_info(accounts_iter)?;
+        let follower_index_account = next_account_info(accounts_iter)?;
+        
+        // Verify the follower account is the signer
+        if !follower_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[follower_account, followed_profile_account, follower_profile_account, follow_record_account, following_index_account, follower_index_account])?;
+        
+        // Verify the followed profile account is owned by our program
+        if followed_profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Verify the follower profile account is owned by our program
+        if follower_profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Verify the followed profile account key matches the profile_id
+        if *followed_profile_account.key != profile_id {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Either profile may be soft-deleted; unfollowing still has to release the follow
+        let mut followed_profile = unpack_profile_from_slice(&followed_profile_account.data.borrow())?;
+        
+        // Deserialize the follower profile data
+        let mut follower_profile = unpack_profile_from_slice(&follower_profile_account.data.borrow())?;
+        
+        // Verify the follower profile is owned by the follower
+        if follower_profile.owner != *follower_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Verify the follow record account is the expected PDA
+        let (expected_pda, _bump_seed) = follow_record_pda(program_id, follower_account.key, &followed_profile.owner);
+        if expected_pda != *follow_record_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Only existing follows can be undone
+        if follow_record_account.owner != program_id {
+            return Err(BlocksError::NotFollowing.into());
+        }
+        let record = unpack_follow_record_from_slice(&follow_record_account.data.borrow())?;
+        if !record.is_initialized {
+            return Err(BlocksError::NotFollowing.into());
+        }
+        
+        // Drop the follow from both users' indexes
+        Self::remove_from_index::<FollowingIndex>(
+            program_id,
+            follower_account.key,
+            record.following_page,
+            &followed_profile.owner,
+            following_index_account,
+        )?;
+        Self::remove_from_index::<FollowerIndex>(
+            program_id,
+            &followed_profile.owner,
+            record.follower_page,
+            follower_account.key,
+            follower_index_account,
+        )?;
+        
+        // Decrement followers count for the followed profile
+        followed_profile.followers_count = followed_profile.followers_count.checked_sub(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        
+        // Decrement following count for the follower profile
+        follower_profile.following_count = follower_profile.following_count.checked_sub(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        // Serialize and save the updated followed profile data
+        followed_profile.updated_at = current_timestamp;
+        pack_profile_into_slice(&followed_profile, &mut followed_profile_account.data.borrow_mut())?;
+        
+        // Serialize and save the updated follower profile data
+        follower_profile.updated_at = current_timestamp;
+        pack_profile_into_slice(&follower_profile, &mut follower_profile_account.data.borrow_mut())?;
+        
+        // Close the follow record and refund its rent to the follower
+        Self::close_account(follow_record_account, follower_account)?;
+        
+        Event::ProfileUnfollowed { follower: *follower_account.key, followed: *followed_profile_account.key }.emit()?;
+        
+        msg!("Unfollow successful");
+        Ok(())
+    }
+
+    fn process_create_community(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        name: String,
+        description: String,
+        avatar: String,
+        rules: Vec<String>,
+    ) -> ProgramResult {
+        msg!("Instruction: CreateCommunity");
+        Self::expect_accounts(accounts, 6, "CreateCommunity")?;
+        Self::create_community(program_id, accounts, name, description, avatar, rules, &[])
+    }
+
+    fn process_create_community_with_members(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        name: String,
+        description: String,
+        avatar: String,
+        rules: Vec<String>,
+        members: Vec<Pubkey>,
+    ) -> ProgramResult {
+        msg!("Instruction: CreateCommunityWithMembers");
+        Self::expect_accounts(accounts, 6 + members.len(), "CreateCommunityWithMembers")?;
+        
+        // Bound the batch so it fits in a single transaction's compute budget
+        if members.is_empty() || members.len() > MAX_INITIAL_MEMBERS {
+            msg!("CreateCommunityWithMembers takes 1-{} members", MAX_INITIAL_MEMBERS);
+            return Err(BlocksError::BatchTooLarge.into());
+        }
+        
+        Self::create_community(program_id, accounts, name, description, avatar, rules, &members)
+    }
+
+    fn process_update_leaderboard(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        msg!("Instruction: UpdateLeaderboard");
+        Self::expect_accounts(accounts, 4, "UpdateLeaderboard")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let payer_account = next_account_info(accounts_iter)?;
+        let leaderboard_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        let profile_accounts = accounts_iter.as_slice();
+        
+        // Verify the payer account is the signer
+        if !payer_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the system program is the real one
+        Self::assert_system_program(system_program)?;
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[payer_account, leaderboard_account])?;
+        
+        // Bound the batch so each update stays O(LEADERBOARD_LEN) per profile
+        if profile_accounts.len() > LEADERBOARD_LEN {
+            msg!("UpdateLeaderboard takes 1-{} profiles", LEADERBOARD_LEN);
+            return Err(BlocksError::BatchTooLarge.into());
+        }
+        
+        let mut leaderboard = Self::load_leaderboard(program_id, payer_account, leaderboard_account, system_program)?;
+        
+        // Re-rank each profile by its stored UCR; deleted profiles leave the board
+        for profile_account in profile_accounts {
+            if profile_account.owner != program_id {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            let profile = unpack_profile_from_slice(&profile_account.data.borrow())?;
+            let rank = if profile.is_deleted {
+                leaderboard.remove(profile_account.key);
+                None
+            } else {
+                leaderboard.record(*profile_account.key, profile.user_credit_rating)
+            };
+            Event::LeaderboardUpdated {
+                profile: *profile_account.key,
+                ucr: profile.user_credit_rating,
+                rank: rank.map(|rank| rank as u8),
+            }
+            .emit()?;
+        }
+        
+        // Serialize and save the leaderboard
+        pack_leaderboard_into_slice(&leaderboard, &mut leaderboard_account.data.borrow_mut())?;
+        
+        msg!("Leaderboard updated");
+        Ok(())
+    }
+
+    // Create a community owned by the signer, then register `members` with the membership
+    // accounts that follow the six CreateCommunity accounts, one per member in order
+    #[allow(clippy::too_many_arguments)]
+    fn create_community(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        name: String,
+        description: String,
+        avatar: String,
+        rules: Vec<String>,
+        members: &[Pubkey],
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        
+        let owner_account = next_account_info(accounts_iter)?;
+        let community_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        let program_state_account = next_account_info(accounts_iter)?;
+        let membership_account = next_account_info(accounts_iter)?;
+        let member_index_account = next_account_info(accounts_iter)?;
+        
+        // Verify the owner account is the signer
+        if !owner_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the system program is the real one
+        Self::assert_system_program(system_program)?;
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[owner_account, community_account, program_state_account, membership_account, member_index_account])?;
+        
+        // Validate the community name and metadata
+        Self::validate_community_name(&name)?;
+        Self::validate_community_fields(&description, &avatar, &rules)?;
+        
+        // Verify the community account is the PDA of its name, which no other community holds
+        let (expected_pda, bump_seed) = community_pda(program_id, &name);
+        if expected_pda != *community_account.key {
+            msg!("Community {} must be created at {}", name, expected_pda);
+            return Err(ProgramError::InvalidArgument);
+        }
+        if community_account.owner == program_id {
+            msg!("Community name {} is already taken", normalize_community_name(&name));
+            return Err(BlocksError::CommunityAlreadyExists.into());
+        }
+        
+        // Assign the next community id from the program state
+        let mut state = Self::load_program_state(program_id, program_state_account)?;
+        let community_id = state.communities_count;
+        state.communities_count = state.communities_count.checked_add(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        
+        // Check if this is a subBlocks community
+        let is_sb_community = name.starts_with(SB_COMMUNITY_PREFIX);
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        // Initialize the Community struct
+        let mut community = Community {
+            is_initialized: true,
+            id: community_id,
+            name,
+            description,
+            avatar,
+            owner: *owner_account.key,
+            member_count: 1, // Owner is the first member
+            rules,
+            is_sb_community,
+            pinned_post: None,
+            member_index_pages: 0,
+            is_deleted: false,
+            deleted_at: 0,
+            post_rules: CommunityPostRules::default(),
+            max_members: MAX_COMMUNITY_MEMBERS,
+            updated_at: current_timestamp,
+        };
+        
+        // Create the community account at the PDA of its name
+        let name_seed = community_name_seed(&community.name);
+        Self::allocate_pda_account(
+            program_id,
+            owner_account,
+            community_account,
+            system_program,
+            community.required_space()?,
+            &[COMMUNITY_SEED, &name_seed, &[bump_seed]],
+        )?;
+        
+        // Pre-existing accounts must end up rent exempt
+        Self::assert_rent_exempt(community_account)?;
+        
+        // Update the program state
+        pack_program_state_into_slice(&state, &mut program_state_account.data.borrow_mut())?;
+        
+        // List the owner on the first member index page
+        let index_page = Self::add_to_index::<MemberIndex>(
+            program_id,
+            &mut community.member_index_pages,
+            community_account.key,
+            owner_account.key,
+            owner_account,
+            member_index_account,
+            system_program,
+        )?;
+        
+        // Register the owner as the first member
+        Self::register_membership(
+            program_id,
+            owner_account,
+            community_account.key,
+            owner_account.key,
+            Role::Owner,
+            index_page,
+            current_timestamp,
+            membership_account,
+            system_program,
+        )?;
+        
+        // Register the initial members, paid for by the owner
+        for (index, (member, member_membership_account)) in members.iter().zip(accounts_iter).enumerate() {
+            if *member == *owner_account.key || members[..index].contains(member) {
+                msg!("{} is listed more than once", member);
+                return Err(BlocksError::AlreadyMember.into());
+            }
+            
+            let index_page = Self::add_to_index::<MemberIndex>(
+                program_id,
+                &mut community.member_index_pages,
+                community_account.key,
+                member,
+                owner_account,
+                member_index_account,
+                system_program,
+            )?;
+            Self::register_membership(
+                program_id,
+                owner_account,
+                community_account.key,
+                member,
+                Role::Member,
+                index_page,
+                current_timestamp,
+                member_membership_account,
+                system_program,
+            )?;
+            community.member_count = community.member_count.checked_add(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        }
+        
+        // Serialize and save the community data
+        pack_community_into_slice(&community, &mut community_account.data.borrow_mut())?;
+        
+        Event::CommunityCreated { community: *community_account.key, owner: *owner_account.key }.emit()?;
+        for member in members {
+            Event::CommunityJoined { community: *community_account.key, user: *member }.emit()?;
+        }
+        
+        msg!("Community created successfully");
+        Ok(())
+    }
+
+    // Create and write the membership PDA for `user`, listed on member index page `index_page`
+    #[allow(clippy::too_many_arguments)]
+    fn register_membership<'a>(
+        program_id: &Pubkey,
+        payer: &AccountInfo<'a>,
+        community: &Pubkey,
+        user: &Pubkey,
+        role: Role,
+        index_page: u32,
+        joined_at: u64,
+        membership_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        // Verify the membership account is the expected PDA
+        let (expected_pda, bump_seed) = membership_pda(program_id, community, user);
+        if expected_pda != *membership_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        let membership = Membership {
+            is_initialized: true,
+            community: *community,
+            user: *user,
+            joined_at,
+            role,
+            index_page: Some(index_page),
+        };
+        
+        // Create the membership account if it doesn't exist
+        if membership_account.owner != program_id {
+            let signer_seeds = [
+                community.as_ref(),
+                user.as_ref(),
+                MEMBERSHIP_SEED,
+                &[bump_seed],
+            ];
+            
+            // Create account
+            Self::create_pda_account(
+                program_id,
+                payer,
+                membership_account,
+                system_program,
+                membership.required_space()?,
+                &signer_seeds,
+            )?;
+        }
+        
+        // Serialize and save the membership data
+        pack_membership_into_slice(&membership, &mut membership_account.data.borrow_mut())
+    }
+
+    fn process_join_community(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        community_id: u64,
+    ) -> ProgramResult {
+        msg!("Instruction: JoinCommunity");
+        Self::expect_accounts(accounts, 5, "JoinCommunity")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let user_account = next_account_info(accounts_iter)?;
+        let community_account = next_account_info(accounts_iter)?;
+        let membership_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        let member_index_account = next_account_info(accounts_iter)?;
+        
+        // Verify the user account is the signer
+        if !user_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the system program is the real one
+        Self::assert_system_program(system_program)?;
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[user_account, community_account, membership_account, member_index_account])?;
+        
+        // Verify the community account is owned by our program
+        if community_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Deserialize the community data
+        let mut community = unpack_live_community_from_slice(&community_account.data.borrow())?;
+        
+        // Verify the community ID matches
+        if community.id != community_id {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Verify the membership account is the expected PDA
+        let (expected_pda, bump_seed) = membership_pda(program_id, community_account.key, user_account.key);
+        if expected_pda != *membership_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Reject duplicate joins
+        if membership_account.owner == program_id {
+            let membership = unpack_membership_from_slice(&membership_account.data.borrow())?;
+            if membership.is_initialized {
+                return Err(BlocksError::AlreadyMember.into());
+            }
+        }
+        
+        // Refuse joins once the community is full
+        if community.member_count >= community.max_members {
+            return Err(BlocksError::CommunityLimitExceeded.into());
+        }
+        
+        // List the user on the community's member index
+        let index_page = Self::add_to_index::<MemberIndex>(
+            program_id,
+            &mut community.member_index_pages,
+            community_account.key,
+            user_account.key,
+            user_account,
+            member_index_account,
+            system_program,
+        )?;
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        let membership = Membership {
+            is_initialized: true,
+            community: *community_account.key,
+            user: *user_account.key,
+            joined_at: current_timestamp,
+            role: Role::Member,
+            index_page: Some(index_page),
+        };
+        
+        // Create the membership account if it doesn't exist
+        if membership_account.owner != program_id {
+            let signer_seeds = [
+                community_account.key.as_ref(),
+                user_account.key.as_ref(),
+                MEMBERSHIP_SEED,
+                &[bump_seed],
+            ];
+            
+            // Create account
+            Self::create_pda_account(
+                program_id,
+                user_account,
+                membership_account,
+                system_program,
+                membership.required_space()?,
+                &signer_seeds,
+            )?;
+        }
+        
+        // Increment member count
+        community.member_count = community.member_count.checked_add(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        
+        // Serialize and save the membership data
+        pack_membership_into_slice(&membership, &mut membership_account.data.borrow_mut())?;
+        
+        // Serialize and save the updated community data
+        community.updated_at = current_timestamp;
+        pack_community_into_slice(&community, &mut community_account.data.borrow_mut())?;
+        
+        Event::CommunityJoined { community: *community_account.key, user: *user_account.key }.emit()?;
+        
+        msg!("Joined community successfully");
+        Ok(())
+    }
+
+    fn process_leave_community(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        community_id: u64,
+    ) -> ProgramResult {
+        msg!("Instruction: LeaveCommunity");
+        Self::expect_accounts(accounts, 4, "LeaveCommunity")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let user_account = next_account_info(accounts_iter)?;
+        let community_account = next_account_info(accounts_iter)?;
+        let membership_account = next_account_info(accounts_iter)?;
+        let member_index_account = next_account_info(accounts_iter)?;
+        
+        // Verify the user account is the signer
+        if !user_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[user_account, community_account, membership_account, member_index_account])?;
+        
+        // Verify the community account is owned by our program
+        if community_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Members can still leave a soft-deleted community and reclaim their membership rent
+        let mut community = unpack_community_from_slice(&community_account.data.borrow())?;
+        
+        // Verify the community ID matches
+        if community.id != community_id {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Only actual members can leave
+        let membership = Self::load_membership(program_id, community_account.key, user_account.key, membership_account)?;
+        
+        // The owner must hand off the community before leaving
+        if membership.role == Role::Owner {
+            msg!("Transfer community ownership before leaving");
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Drop the user from the member index page that lists them
+        Self::remove_from_index::<MemberIndex>(
+            program_id,
+            community_account.key,
+            membership.index_page,
+            user_account.key,
+            member_index_account,
+        )?;
+        
+        // Decrement member count
+        community.member_count = community.member_count.checked_sub(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        // Serialize and save the updated community data
+        community.updated_at = current_timestamp;
+        pack_community_into_slice(&community, &mut community_account.data.borrow_mut())?;
+        
+        // Close the membership account and refund its rent to the user
+        Self::close_account(membership_account, user_account)?;
+        
+        Event::CommunityLeft { community: *community_account.key, user: *user_account.key }.emit()?;
+        
+        msg!("Left community successfully");
+        Ok(())
+    }
+
+    fn process_verify_profile(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        msg!("Instruction: VerifyProfile");
+        Self::expect_accounts(accounts, 2, "VerifyProfile")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let user_account = next_account_info(accounts_iter)?;
+        let profile_account = next_account_info(accounts_iter)?;
+        
+        // Verify the user account is the signer
+        if !user_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[profile_account])?;
+        
+        // Verify the profile account is owned by our program
+        if profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Deserialize the profile data
+        let mut profile = unpack_live_profile_from_slice(&profile_account.data.borrow())?;
+        
+        // Verify the profile is owned by the user
+        if profile.owner != *user_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        
+        // Require enough posts that a handful of likes can't game the threshold
+        let post_count = profile.posts_count;
+        if post_count < VERIFICATION_MIN_POSTS {
+            msg!("Verification requires at least {} posts", VERIFICATION_MIN_POSTS);
+            return Err(BlocksError::VerificationThresholdNotMet.into());
+        }
+        
+        // Like rate as a percentage of posts, from the likes received across all of them
+        if !meets_verification_threshold(profile.total_likes_received, post_count) {
+            msg!("Like rate {}% is below the {}% threshold", profile.engagement_rate(), VERIFICATION_THRESHOLD);
+            return Err(BlocksError::VerificationThresholdNotMet.into());
+        }
+        
+        // Earning verification never lowers a badge the authority granted
+        if !profile.is_verified() {
+            profile.set_verification_level(VerificationLevel::Identity);
+        }
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        // Serialize and save the updated profile data
+        profile.updated_at = current_timestamp;
+        pack_profile_into_slice(&profile, &mut profile_account.data.borrow_mut())?;
+        
+        Event::ProfileVerified { profile: *profile_account.key }.emit()?;
+        
+        msg!("Profile verified successfully");
+        Ok(())
+    }
+
+    fn process_delete_post(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        post_id: u64,
+        force: bool,
+    ) -> ProgramResult {
+        msg!("Instruction: DeletePost");
+        Self::expect_accounts(accounts, 4, "DeletePost")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let user_account = next_account_info(accounts_iter)?;
+        let post_account = next_account_info(accounts_iter)?;
+        let profile_account = next_account_info(accounts_iter)?;
+        let program_state_account = next_account_info(accounts_iter)?;
+        
+        // Verify the user account is the signer
+        if !user_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[user_account, post_account, profile_account, program_state_account])?;
+        
+        // Verify the post account is owned by our program
+        if post_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Verify the profile account is owned by our program
+        if profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Soft-deleted posts are closed here too; this is how they are garbage collected
+        let post = unpack_post_from_slice(&post_account.data.borrow())?;
+        
+        // Verify the post ID matches
+        if post.id != post_id {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Only the author may delete the post
+        if post.author != *user_account.key {
+            return Err(BlocksError::NotPostOwner.into());
+        }
+        
+        // Comments go through DeleteComment, which also decrements the parent's comment count
+        if post.parent.is_some() {
+            msg!("Post {} is a comment; delete it with DeleteComment", post_account.key);
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Don't strand comments that still point at the post
+        Self::assert_unreferenced(&post, force)?;
+        
+        // The profile may be soft-deleted; its owner can still clear out its posts
+        let mut profile = unpack_profile_from_slice(&profile_account.data.borrow())?;
+        
+        // Verify the profile is owned by the user
+        if profile.owner != *user_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        
+        // Decrement post count and drop the post's engagement from the aggregates
+        profile.posts_count = profile.posts_count.checked_sub(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        profile.remove_engagement(&post);
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        // Update the profile
+        profile.updated_at = current_timestamp;
+        pack_profile_into_slice(&profile, &mut profile_account.data.borrow_mut())?;
+        
+        // Close the post account and refund its rent to the author
+        Self::close_account(post_account, user_account)?;
+        
+        // Stop counting the post in the program state
+        let mut state = Self::load_program_state(program_id, program_state_account)?;
+        state.posts_count = state.posts_count.checked_sub(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        pack_program_state_into_slice(&state, &mut program_state_account.data.borrow_mut())?;
+        
+        Event::PostDeleted { post: *post_account.key, author: *user_account.key }.emit()?;
+        
+        msg!("Post deleted successfully");
+        Ok(())
+    }
+
+    fn process_delete_comment(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        comment_id: u64,
+        parent_id: u64,
+        force: bool,
+    ) -> ProgramResult {
+        msg!("Instruction: DeleteComment");
+        Self::expect_accounts(accounts, 5, "DeleteComment")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let user_account = next_account_info(accounts_iter)?;
+        let comment_account = next_account_info(accounts_iter)?;
+        let parent_post_account = next_account_info(accounts_iter)?;
+        let profile_account = next_account_info(accounts_iter)?;
+        let program_state_account = next_account_info(accounts_iter)?;
+        
+        // Verify the user account is the signer
+        if !user_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[user_account, comment_account, parent_post_account, profile_account, program_state_account])?;
+        
+        // Verify the comment account is owned by our program
+        if comment_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Verify the profile account is owned by our program
+        if profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Soft-deleted comments are closed here too; this is how they are garbage collected
+        let comment = unpack_post_from_slice(&comment_account.data.borrow())?;
+        
+        // Verify the comment ID matches
+        if comment.id != comment_id {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Only the author may delete the comment
+        if comment.author != *user_account.key {
+            return Err(BlocksError::NotPostOwner.into());
+        }
+        
+        // Don't strand replies that still point at the comment
+        Self::assert_unreferenced(&comment, force)?;
+        
+        // Verify the parent account is the one the comment replies to
+        if comment.parent != Some(*parent_post_account.key) {
+            msg!("Account {} is not the parent of this comment", parent_post_account.key);
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // The profile may be soft-deleted; its owner can still clear out its comments
+        let mut profile = unpack_profile_from_slice(&profile_account.data.borrow())?;
+        
+        // Verify the profile is owned by the user
+        if profile.owner != *user_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        
+        // Decrement the parent's comment count unless the parent was already closed; a soft-deleted parent still counts it
+        if parent_post_account.owner == program_id {
+            let mut parent_post = unpack_post_from_slice(&parent_post_account.data.borrow())?;
+            
+            // Verify the parent post ID matches
+            if parent_post.id != parent_id {
+                return Err(ProgramError::InvalidArgument);
+            }
+            
+            parent_post.comments = parent_post.comments.checked_sub(1).ok_or(BlocksError::ArithmeticOverflow)?;
+            pack_post_into_slice(&parent_post, &mut parent_post_account.data.borrow_mut())?;
+        }
+        
+        // Decrement post count and drop the comment's engagement from the aggregates
+        profile.posts_count = profile.posts_count.checked_sub(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        profile.remove_engagement(&comment);
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        // Update the profile
+        profile.updated_at = current_timestamp;
+        pack_profile_into_slice(&profile, &mut profile_account.data.borrow_mut())?;
+        
+        // Close the comment account and refund its rent to the author
+        Self::close_account(comment_account, user_account)?;
+        
+        // Stop counting the comment in the program state
+        let mut state = Self::load_program_state(program_id, program_state_account)?;
+        state.posts_count = state.posts_count.checked_sub(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        pack_program_state_into_slice(&state, &mut program_state_account.data.borrow_mut())?;
+        
+        Event::CommentDeleted {
+            comment: *comment_account.key,
+            parent: *parent_post_account.key,
+            author: *user_account.key,
+        }.emit()?;
+        
+        msg!("Comment deleted successfully");
+        Ok(())
+    }
+
+    fn process_delete_profile(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        msg!("Instruction: DeleteProfile");
+        Self::expect_accounts(accounts, 4, "DeleteProfile")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let user_account = next_account_info(accounts_iter)?;
+        let profile_account = next_account_info(accounts_iter)?;
+        let username_registry_account = next_account_info(accounts_iter)?;
+        let program_state_account = next_account_info(accounts_iter)?;
+        
+        // Verify the user account is the signer
+        if !user_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[user_account, profile_account, username_registry_account, program_state_account])?;
+        
+        // Verify the profile account is owned by our program
+        if profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // A soft-deleted profile can still be closed for good
+        let profile = unpack_profile_from_slice(&profile_account.data.borrow())?;
+        
+        // Only the owner may delete the profile
+        if profile.owner != *user_account.key {
+            return Err(BlocksError::NotProfileOwner.into());
+        }
+        
+        // Posts would be orphaned without an author profile
+        if profile.posts_count > 0 {
+            msg!("Profile still has {} posts", profile.posts_count);
+            return Err(BlocksError::ProfileNotEmpty.into());
+        }
+        
+        // Followed profiles would keep counting this user as a follower
+        if profile.following_count > 0 {
+            msg!("Profile still follows {} profiles", profile.following_count);
+            return Err(BlocksError::ProfileNotEmpty.into());
+        }
+        
+        // Followers could never unfollow a closed profile, and so could never delete their own
+        if profile.followers_count > 0 {
+            msg!("Profile still has {} followers", profile.followers_count);
+            return Err(BlocksError::ProfileNotEmpty.into());
+        }
+        
+        // The registry entry is closed with the profile, so it must be the one naming this profile
+        Self::assert_registered_profile(program_id, profile_account, &profile, username_registry_account)?;
+        
+        // Close the profile account and refund its rent to the user
+        Self::close_account(profile_account, user_account)?;
+        
+        // Release the username so it can be registered again
+        Self::close_account(username_registry_account, user_account)?;
+        
+        // Stop counting the profile in the program state
+        let mut state = Self::load_program_state(program_id, program_state_account)?;
+        state.profiles_count = state.profiles_count.checked_sub(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        pack_program_state_into_slice(&state, &mut program_state_account.data.borrow_mut())?;
+        
+        Event::ProfileDeleted { profile: *profile_account.key, owner: *user_account.key }.emit()?;
+        
+        msg!("Profile deleted successfully");
+        Ok(())
+    }
+
+    fn process_edit_post(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        post_id: u64,
+        content: String,
+        images: Vec<String>,
+    ) -> ProgramResult {
+        msg!("Instruction: EditPost");
+        Self::expect_accounts(accounts, 2, "EditPost")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let user_account = next_account_info(accounts_iter)?;
+        let post_account = next_account_info(accounts_iter)?;
+        
+        // Verify the user account is the signer
+        if !user_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[post_account])?;
+        
+        // Verify the post account is owned by our program
+        if post_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Deserialize the post data
+        let mut post = unpack_live_post_from_slice(&post_account.data.borrow())?;
+        
+        // Verify the post ID matches
+        if post.id != post_id {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Only the author may edit the post
+        if post.author != *user_account.key {
+            return Err(BlocksError::NotPostOwner.into());
+        }
+        
+        // Posts in the kill zone are frozen
+        if post.in_kill_zone {
+            return Err(BlocksError::PostInKillZone.into());
+        }
+        
+        // Enforce the post content limits
+        Self::validate_post_content(&content, &images)?;
+        
+        // The author's registered profile, when supplied, sets the edit window by tier
+        let edit_window = match accounts_iter.next() {
+            Some(profile_account) => {
+                let username_registry_account = next_account_info(accounts_iter)?;
+                if profile_account.owner != program_id {
+                    return Err(ProgramError::IncorrectProgramId);
+                }
+                let profile = unpack_live_profile_from_slice(&profile_account.data.borrow())?;
+                if profile.owner != *user_account.key {
+                    return Err(ProgramError::InvalidArgument);
+                }
+                // Another profile the author owns can't lend its tier
+                Self::assert_registered_profile(program_id, profile_account, &profile, username_registry_account)?;
+                profile.tier().edit_window()
+            }
+            None => EDIT_WINDOW_SECONDS,
+        };
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        // Posts can only be edited shortly after creation
+        if current_timestamp > post.timestamp.saturating_add(edit_window) {
+            msg!("Edit window of {} seconds has expired", edit_window);
+            return Err(BlocksError::EditWindowExpired.into());
+        }
+        
+        // Update the post fields
+        post.content = content;
+        post.images = images;
+        post.edited_at = current_timestamp;
+        
+        // Serialize and save the updated post data
+        pack_post_into_slice(&post, &mut post_account.data.borrow_mut())?;
+        
+        Event::PostEdited { post: *post_account.key }.emit()?;
+        
+        msg!("Post edited successfully");
+        Ok(())
+    }
+
+    fn process_block_user(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        target: Pubkey,
+    ) -> ProgramResult {
+        msg!("Instruction: BlockUser");
+        Self::expect_accounts(accounts, 3, "BlockUser")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let user_account = next_account_info(accounts_iter)?;
+        let block_record_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        
+        // Verify the user account is the signer
+        if !user_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the system program is the real one
+        Self::assert_system_program(system_program)?;
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[user_account, block_record_account])?;
+        
+        // Users cannot block themselves
+        if target == *user_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Verify the block record account is the expected PDA
+        let (expected_pda, bump_seed) = block_record_pda(program_id, user_account.key, &target);
+        if expected_pda != *block_record_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Reject duplicate blocks
+        if block_record_account.owner == program_id {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        let record = BlockRecord {
+            is_initialized: true,
+            blocker: *user_account.key,
+            blocked: target,
+            created_at: current_timestamp,
+        };
+        
+        let signer_seeds = [
+            user_account.key.as_ref(),
+            target.as_ref(),
+            BLOCK_RECORD_SEED,
+            &[bump_seed],
+        ];
+        
+        // Create account
+        Self::create_pda_account(
+            program_id,
+            user_account,
+            block_record_account,
+            system_program,
+            record.required_space()?,
+            &signer_seeds,
+        )?;
+        
+        // Serialize and save the block record
+        pack_block_record_into_slice(&record, &mut block_record_account.data.borrow_mut())?;
+        
+        Event::UserBlocked { blocker: *user_account.key, blocked: target }.emit()?;
+        
+        msg!("User blocked successfully");
+        Ok(())
+    }
+
+    fn process_unblock_user(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        target: Pubkey,
+    ) -> ProgramResult {
+        msg!("Instruction: UnblockUser");
+        Self::expect_accounts(accounts, 2, "UnblockUser")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let user_account = next_account_info(accounts_iter)?;
+        let block_record_account = next_account_info(accounts_iter)?;
+        
+        // Verify the user account is the signer
+        if !user_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[user_account, block_record_account])?;
+        
+        // Only existing blocks can be removed
+        if !Self::is_blocked(program_id, user_account.key, &target, block_record_account)? {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        
+        // Close the block record and refund its rent to the user
+        Self::close_account(block_record_account, user_account)?;
+        
+        Event::UserUnblocked { blocker: *user_account.key, blocked: target }.emit()?;
+        
+        msg!("User unblocked successfully");
+        Ok(())
+    }
+
+    fn process_add_moderator(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        target: Pubkey,
+    ) -> ProgramResult {
+        msg!("Instruction: AddModerator");
+        Self::expect_accounts(accounts, 3, "AddModerator")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let owner_account = next_account_info(accounts_iter)?;
+        let community_account = next_account_info(accounts_iter)?;
+        let membership_account = next_account_info(accounts_iter)?;
+        
+        // Verify the owner account is the signer
+        if !owner_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[membership_account])?;
+        
+        // Verify the community account is owned by our program
+        if community_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Deserialize the community data
+        let community = unpack_live_community_from_slice(&community_account.data.borrow())?;
+        
+        // Only the community owner may grant roles
+        if community.owner != *owner_account.key {
+            return Err(BlocksError::NotCommunityOwner.into());
+        }
+        
+        // Load the target's membership
+        let mut membership = Self::load_membership(program_id, community_account.key, &target, membership_account)?;
+        
+        // The owner keeps the owner role
+        if membership.role == Role::Owner {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        membership.role = Role::Moderator;
+        
+        // Serialize and save the updated membership data
+        pack_membership_into_slice(&membership, &mut membership_account.data.borrow_mut())?;
+        
+        Event::ModeratorAdded { community: *community_account.key, moderator: target }.emit()?;
+        
+        msg!("Moderator added successfully");
+        Ok(())
+    }
+
+    fn process_moderate_post(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        post_id: u64,
+    ) -> ProgramResult {
+        msg!("Instruction: ModeratePost");
+        Self::expect_accounts(accounts, 4, "ModeratePost")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let moderator_account = next_account_info(accounts_iter)?;
+        let community_account = next_account_info(accounts_iter)?;
+        let membership_account = next_account_info(accounts_iter)?;
+        let post_account = next_account_info(accounts_iter)?;
+        
+        // Verify the moderator account is the signer
+        if !moderator_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[post_account])?;
+        
+        // Verify the community account is owned by our program
+        if community_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Verify the post account is owned by our program
+        if post_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Verify the signer moderates this community
+        let membership = Self::load_membership(program_id, community_account.key, moderator_account.key, membership_account)?;
+        if !membership.role.can_moderate() {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Deserialize the post data
+        let mut post = unpack_live_post_from_slice(&post_account.data.borrow())?;
+        
+        // Verify the post ID matches
+        if post.id != post_id {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Moderators can only act on posts within their community
+        if post.community != Some(*community_account.key) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        post.hidden = true;
+        
+        // Serialize and save the updated post data
+        pack_post_into_slice(&post, &mut post_account.data.borrow_mut())?;
+        
+        Event::PostModerated { post: *post_account.key, moderator: *moderator_account.key }.emit()?;
+        
+        msg!("Post hidden successfully");
+        Ok(())
+    }
+
+    fn process_transfer_community_ownership(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        new_owner: Pubkey,
+    ) -> ProgramResult {
+        msg!("Instruction: TransferCommunityOwnership");
+        Self::expect_accounts(accounts, 4, "TransferCommunityOwnership")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let owner_account = next_account_info(accounts_iter)?;
+        let community_account = next_account_info(accounts_iter)?;
+        let owner_membership_account = next_account_info(accounts_iter)?;
+        let new_owner_membership_account = next_account_info(accounts_iter)?;
+        
+        // Verify the owner account is the signer
+        if !owner_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[community_account, owner_membership_account, new_owner_membership_account])?;
+        
+        // Verify the community account is owned by our program
+        if community_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Deserialize the community data
+        let mut community = unpack_live_community_from_slice(&community_account.data.borrow())?;
+        
+        // Only the current owner may transfer the community
+        if community.owner != *owner_account.key {
+            return Err(BlocksError::NotCommunityOwner.into());
+        }
+        
+        if new_owner == *owner_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // The new owner must already be a member
+        let mut owner_membership = Self::load_membership(program_id, community_account.key, owner_account.key, owner_membership_account)?;
+        let mut new_owner_membership = Self::load_membership(program_id, community_account.key, &new_owner, new_owner_membership_account)?;
+        
+        // Swap roles and ownership
+        owner_membership.role = Role::Member;
+        new_owner_membership.role = Role::Owner;
+        community.owner = new_owner;
+        
+        // Serialize and save the updated membership data
+        pack_membership_into_slice(&owner_membership, &mut owner_membership_account.data.borrow_mut())?;
+        pack_membership_into_slice(&new_owner_membership, &mut new_owner_membership_account.data.borrow_mut())?;
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        // Serialize and save the updated community data
+        community.updated_at = current_timestamp;
+        pack_community_into_slice(&community, &mut community_account.data.borrow_mut())?;
+        
+        Event::CommunityOwnershipTransferred { community: *community_account.key, new_owner }.emit()?;
+        
+        msg!("Community ownership transferred successfully");
+        Ok(())
+    }
+
+    fn process_tip_post(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        post_id: u64,
+        amount: u64,
+    ) -> ProgramResult {
+        msg!("Instruction: TipPost");
+        Self::expect_accounts(accounts, 6, "TipPost")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let tipper_account = next_account_info(accounts_iter)?;
+        let post_account = next_account_info(accounts_iter)?;
+        let author_account = next_account_info(accounts_iter)?;
+        let author_profile_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        let author_registry_account = next_account_info(accounts_iter)?;
+        
+        // Verify the tipper account is the signer
+        if !tipper_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the system program is the real one
+        Self::assert_system_program(system_program)?;
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[tipper_account, post_account, author_account, author_profile_account])?;
+        
+        // Verify the post account is owned by our program
+        if post_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Verify the author profile account is owned by our program
+        if author_profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        if amount == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Deserialize the post data
+        let mut post = unpack_live_post_from_slice(&post_account.data.borrow())?;
+        
+        // Verify the post ID matches
+        if post.id != post_id {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Posts in the kill zone can't receive tips
+        if post.in_kill_zone {
+            return Err(BlocksError::PostInKillZone.into());
+        }
+        
+        // Verify the author wallet matches the post author
+        if *author_account.key != post.author {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Self-tips would mint UCR for free
+        if *tipper_account.key == post.author {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Deserialize the author profile data
+        let mut author_profile = unpack_live_profile_from_slice(&author_profile_account.data.borrow())?;
+        
+        // Verify the author profile matches the post author
+        if author_profile.owner != post.author {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Verify the author profile is the author's registered profile, not another account they own
+        Self::assert_registered_profile(program_id, author_profile_account, &author_profile, author_registry_account)?;
+        
+        // Verify the tipper can cover the tip
+        if tipper_account.lamports() < amount {
+            msg!("Tipper has {} lamports, tip is {}", tipper_account.lamports(), amount);
+            return Err(BlocksError::InsufficientFunds.into());
+        }
+        
+        // Transfer the tip to the author
+        invoke(
+            &system_instruction::transfer(
+                tipper_account.key,
+                author_account.key,
+                amount,
+            ),
+            &[
+                tipper_account.clone(),
+                author_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+        
+        // Record the tip and reward the author's UCR proportionally, under the post's daily cap
+        post.total_tips = post.total_tips.checked_add(amount).ok_or(BlocksError::ArithmeticOverflow)?;
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        let tip_reward = post.capped_ucr_gain(
+            ((amount / TIP_LAMPORTS_PER_UCR) as i64).saturating_mul(UCR_PER_TIP),
+            current_timestamp,
+        );
+        author_profile.user_credit_rating = author_profile
+            .user_credit_rating
+            .checked_add(tip_reward)
+            .ok_or(BlocksError::ArithmeticOverflow)?;
+        author_profile.refresh_ucr_status();
+        
+        // Serialize and save the updated post data
+        pack_post_into_slice(&post, &mut post_account.data.borrow_mut())?;
+        
+        // Serialize and save the updated author profile data
+        author_profile.updated_at = current_timestamp;
+        pack_profile_into_slice(&author_profile, &mut author_profile_account.data.borrow_mut())?;
+        
+        Event::PostTipped { post: *post_account.key, tipper: *tipper_account.key, amount }.emit()?;
+        
+        msg!("Post tipped successfully");
+        Ok(())
+    }
+
+    fn process_decay_ucr(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        msg!("Instruction: DecayUcr");
+        Self::expect_accounts(accounts, 1, "DecayUcr")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let profile_account = next_account_info(accounts_iter)?;
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[profile_account])?;
+        
+        // Verify the profile account is owned by our program
+        if profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Deserialize the profile data
+        let mut profile = unpack_live_profile_from_slice(&profile_account.data.borrow())?;
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        // Inactivity is measured from the latest post, decay or profile creation
+        let last_active = profile
+            .last_post_timestamp
+            .max(profile.last_decay_timestamp)
+            .max(profile.created_at);
+        let inactive_days = current_timestamp.saturating_sub(last_active) / SECONDS_PER_DAY;
+        if inactive_days == 0 {
+            msg!("No full day of inactivity to decay");
+            return Ok(());
+        }
+        
+        // Decay toward the floor without pushing lower scores further down
+        if profile.user_credit_rating > UCR_DECAY_FLOOR {
+            let decay = (inactive_days as i64).saturating_mul(UCR_DECAY_PER_DAY);
+            profile.user_credit_rating = profile
+                .user_credit_rating
+                .saturating_sub(decay)
+                .max(UCR_DECAY_FLOOR);
+            profile.refresh_ucr_status();
+        }
+        
+        // Only consume whole days so partial days carry over
+        profile.last_decay_timestamp = last_active + inactive_days * SECONDS_PER_DAY;
+        
+        // Serialize and save the updated profile data
+        profile.updated_at = current_timestamp;
+        pack_profile_into_slice(&profile, &mut profile_account.data.borrow_mut())?;
+        
+        Event::UcrDecayed { profile: *profile_account.key, user_credit_rating: profile.user_credit_rating }.emit()?;
+        
+        msg!("UCR decayed by {} days of inactivity", inactive_days);
+        Ok(())
+    }
+
+    fn process_quote_post(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        post_id: u64,
+        content: String,
+    ) -> ProgramResult {
+        msg!("Instruction: QuotePost");
+        Self::expect_accounts(accounts, 10, "QuotePost")?;
+        
+        let user_account = &accounts[0];
+        let post_account = &accounts[1];
+        let username_registry_account = &accounts[6];
+        let quoted_post_account = &accounts[7];
+        let quoted_author_profile_account = &accounts[8];
+        let quoted_author_registry_account = &accounts[9];
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[quoted_post_account, quoted_author_profile_account])?;
+        
+        // Verify the quoted post account is owned by our program
+        if quoted_post_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Deserialize the quoted post data
+        let mut quoted_post = unpack_live_post_from_slice(&quoted_post_account.data.borrow())?;
+        
+        // Verify the post ID matches
+        if quoted_post.id != post_id {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Kill zone posts can't be reshared
+        if quoted_post.in_kill_zone {
+            return Err(BlocksError::PostInKillZone.into());
+        }
+        
+        // Only originals can be quoted, so every quote chain is a single hop clients can follow
+        if quoted_post.quoted_post.is_some() {
+            return Err(BlocksError::NestedQuote.into());
+        }
+        
+        // A quote is a post: the same limits, duplicate check and post index apply
+        Self::create_post(
+            program_id,
+            accounts,
+            username_registry_account,
+            content,
+            Vec::new(),
+            None,
+            None,
+            Some(*quoted_post_account.key),
+            true,
+        )?;
+        
+        // Count the quote as a mirror of the original
+        quoted_post.mirrors = quoted_post.mirrors.checked_add(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        
+        // Mirrors by other users earn the quoted author UCR under the post's daily cap
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        let mirror_reward = if quoted_post.author != *user_account.key {
+            quoted_post.capped_ucr_gain(UCR_PER_MIRROR, current_timestamp)
+        } else {
+            0
+        };
+        pack_post_into_slice(&quoted_post, &mut quoted_post_account.data.borrow_mut())?;
+        
+        // Reward the quoted author for mirrors by other users
+        if quoted_post.author != *user_account.key {
+            if quoted_author_profile_account.owner != program_id {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            let mut quoted_author_profile = unpack_live_profile_from_slice(&quoted_author_profile_account.data.borrow())?;
+            if quoted_author_profile.owner != quoted_post.author {
+                return Err(ProgramError::InvalidArgument);
+            }
+            Self::assert_registered_profile(program_id, quoted_author_profile_account, &quoted_author_profile, quoted_author_registry_account)?;
+            quoted_author_profile.user_credit_rating = quoted_author_profile
+                .user_credit_rating
+                .checked_add(mirror_reward)
+                .ok_or(BlocksError::ArithmeticOverflow)?;
+            quoted_author_profile.refresh_ucr_status();
+            quoted_author_profile.updated_at = current_timestamp;
+            pack_profile_into_slice(&quoted_author_profile, &mut quoted_author_profile_account.data.borrow_mut())?;
+        }
+        
+        Event::PostQuoted {
+            post: *post_account.key,
+            quoted_post: *quoted_post_account.key,
+            author: *user_account.key,
+        }.emit()
+    }
+
+    fn process_report_post(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        post_id: u64,
+        reason: u8,
+    ) -> ProgramResult {
+        msg!("Instruction: ReportPost");
+        Self::expect_accounts(accounts, 4, "ReportPost")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let reporter_account = next_account_info(accounts_iter)?;
+        let post_account = next_account_info(accounts_iter)?;
+        let report_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        
+        // Verify the reporter account is the signer
+        if !reporter_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the system program is the real one
+        Self::assert_system_program(system_program)?;
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[reporter_account, post_account, report_account])?;
+        
+        // Verify the post account is owned by our program
+        if post_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Deserialize the post data
+        let mut post = unpack_live_post_from_slice(&post_account.data.borrow())?;
+        
+        // Verify the post ID matches
+        if post.id != post_id {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Verify the report account is the expected PDA
+        let (expected_pda, bump_seed) = report_pda(program_id, post_account.key, reporter_account.key);
+        if expected_pda != *report_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Each user can only report a post once
+        if report_account.owner == program_id {
+            return Err(BlocksError::AlreadyReported.into());
+        }
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        let report = Report {
+            is_initialized: true,
+            post: *post_account.key,
+            reporter: *reporter_account.key,
+            reason,
+            created_at: current_timestamp,
+        };
+        
+        let signer_seeds = [
+            post_account.key.as_ref(),
+            reporter_account.key.as_ref(),
+            REPORT_SEED,
+            &[bump_seed],
+        ];
+        
+        // Create account
+        Self::create_pda_account(
+            program_id,
+            reporter_account,
+            report_account,
+            system_program,
+            report.required_space()?,
+            &signer_seeds,
+        )?;
+        
+        // Serialize and save the report
+        pack_report_into_slice(&report, &mut report_account.data.borrow_mut())?;
+        
+        // Hide the post once enough users have reported it
+        post.report_count = post.report_count.checked_add(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        if post.report_count >= REPORT_HIDE_THRESHOLD {
+            post.hidden = true;
+        }
+        
+        // Heavily reported posts drop into the kill zone
+        if post.report_count >= KILL_ZONE_REPORT_THRESHOLD {
+            post.in_kill_zone = true;
+        }
+        
+        // Serialize and save the updated post data
+        pack_post_into_slice(&post, &mut post_account.data.borrow_mut())?;
+        
+        Event::PostReported { post: *post_account.key, reporter: *reporter_account.key, report_count: post.report_count }.emit()?;
+        
+        msg!("Post reported successfully");
+        Ok(())
+    }
+
+    fn process_change_username(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        new_username: String,
+    ) -> ProgramResult {
+        msg!("Instruction: ChangeUsername");
+        Self::expect_accounts(accounts, 5, "ChangeUsername")?;
+        let accounts_iter = &mut accounts.iter();
+        
+        let user_account = next_account_info(accounts_iter)?;
+        let profile_account = next_account_info(accounts_iter)?;
+        let old_registry_account = next_account_info(accounts_iter)?;
+        let new_registry_account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        
+        // Verify the user account is the signer
+        if !user_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        // Verify the system program is the real one
+        Self::assert_system_program(system_program)?;
+        
+        // Verify the accounts this instruction writes are writable
+        Self::assert_writable(&[user_account, profile_account, old_registry_account, new_registry_account])?;
+        
+        // Enforce the username limits
+        Self::validate_username(&new_username)?;
+        
+        // Verify the profile account is owned by our program
+        if profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Deserialize the profile data
+        let mut profile = unpack_live_profile_from_slice(&profile_account.data.borrow())?;
+        
+        // Verify the profile is owned by the user
+        if profile.owner != *user_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        
+        // Verify both registry accounts are the expected PDAs
+        let (expected_old, _old_bump) = username_registry_pda(program_id, &profile.username);
+        let (expected_new, new_bump) = username_registry_pda(program_id, &new_username);
+        if expected_old != *old_registry_account.key || expected_new != *new_registry_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // A change that normalizes to the same name keeps the existing claim
+        if expected_old != expected_new {
+            // Reject names that are already taken
+            if new_registry_account.owner == program_id {
+                msg!("Username {} is already taken", normalize_username(&new_username));
+                return Err(BlocksError::ProfileAlreadyExists.into());
+            }
+            
+            // Claim the new username
+            let registry = UsernameRegistry {
+                is_initialized: true,
+                username: normalize_username(&new_username),
+                owner: *user_account.key,
+                profile: *profile_account.key,
+            };
+            Self::create_pda_account(
+                program_id,
+                user_account,
+                new_registry_account,
+                system_program,
+                registry.required_space()?,
+                &[USERNAME_SEED, registry.username.as_bytes(), &[new_bump]],
+            )?;
+            pack_username_registry_into_slice(&registry, &mut new_registry_account.data.borrow_mut())?;
+            
+            // Free the old claim if the profile has one (older profiles may not). This runs after
+            // the create CPI so no lamports move outside the system program before it.
+            if old_registry_account.owner == program_id {
+                let old_registry = unpack_username_registry_from_slice(&old_registry_account.data.borrow())?;
+                if old_registry.owner != *user_account.key {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                Self::close_account(old_registry_account, user_account)?;
+            }
+        }
+        
+        // The profile address stays derived from the original username
+        profile.username = new_username;
+        
+        // Get current timestamp
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        
+        // Serialize and save the updated profile data
+        profile.updated_at = current_timestamp;
+        pack_profile_into_slice(&profile, &mut profile_account.data.borrow_mut())?;
+        
+        Event::UsernameChanged { profile: *profile_account.key, username: profile.username }.emit()?;
+        
+        msg!("Username changed successfully");
+        Ok(())
+    }
+
+    // Record a like on a post and credit its author with the weighted UCR
+    #[allow(clippy::too_many_arguments)]
+    fn apply_like<'a>(
+        program_id: &Pubkey,
+        user_account: &AccountInfo<'a>,
+        weight: i64,
+        config: &RatingConfig,
+        post_account: &AccountInfo<'a>,
+        author_profile_account: &AccountInfo<'a>,
+        author_registry_account: &AccountInfo<'a>,
+        like_record_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        post_id: u64,
+    ) -> ProgramResult {
+        // Verify the post and author profile accounts are owned by our program
+        if post_account.owner != program_id || author_profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        // Verify the accounts this like writes are writable
+        Self::assert_writable(&[post_account, author_profile_account, like_record_account])?;
+        
+        // Deserialize the post data
+        let mut post = unpack_live_post_from_slice(&post_account.data.borrow())?;
+        
+        // Verify the post ID matches
+        if post.id != post_id {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Verify the like record account is the expected PDA
+        let (expected_record, record_bump) = like_record_pda(program_id, post_account.key, user_account.key);
+        if expected_record != *like_record_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Each user can like a post once
+        if like_record_account.owner == program_id {
+            return Err(BlocksError::AlreadyLiked.into());
+        }
+        
+        // Deserialize the author profile data
+        let mut author_profile = unpack_live_profile_from_slice(&author_profile_account.data.borrow())?;
+        
+        // Verify the author profile matches the post author
+        if author_profile.owner != post.author {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Verify the author profile is the author's registered profile, not another account they own
+        Self::assert_registered_profile(program_id, author_profile_account, &author_profile, author_registry_account)?;
+        
+        // Increment like count
+        post.likes = post.likes.checked_add(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        
+        // Update post rating based on new like count
+        let previous_rating = post.rating;
+        post.rating = PostRating::from_likes_with_config(post.likes, config);
+        
+        // Update author's UCR score, subject to the post's daily cap, and engagement aggregate
+        let clock = Clock::get()?;
+        let current_timestamp = clock.unix_timestamp as u64;
+        let gain = post.capped_ucr_gain(weight, current_timestamp);
+        author_profile.user_credit_rating = author_profile.user_credit_rating.checked_add(gain).ok_or(BlocksError::ArithmeticOverflow)?;
+        author_profile.total_likes_received = author_profile.total_likes_received.checked_add(1).ok_or(BlocksError::ArithmeticOverflow)?;
+        
+        // The first post to reach Conqueror earns its author a one-time bonus
+        let milestone = previous_rating < PostRating::Conqueror
+            && post.rating == PostRating::Conqueror
+            && !author_profile.milestone_awarded;
+        if milestone {
+            post.earned_milestone = true;
+            author_profile.milestone_awarded = true;
+            author_profile.user_credit_rating = author_profile
+                .user_credit_rating
+                .checked_add(CONQUEROR_MILESTONE_UCR_BONUS)
+                .ok_or(BlocksError::ArithmeticOverflow)?;
+        }
+        
+        // Raised thresholds can re-rate the milestone post below Conqueror; the bonus goes with the rating
+        let milestone_revoked = post.earned_milestone && post.rating < PostRating::Conqueror;
+        if milestone_revoked {
+            post.earned_milestone = false;
+            author_profile.milestone_awarded = false;
+            author_profile.user_credit_rating = author_profile
+                .user_credit_rating
+                .checked_sub(CONQUEROR_MILESTONE_UCR_BONUS)
+                .ok_or(BlocksError::ArithmeticOverflow)?;
+        }
+        author_profile.refresh_ucr_status();
+        
+        // Serialize and save the updated post data
+        pack_post_into_slice(&post, &mut post_account.data.borrow_mut())?;
+        
+        // Serialize and save the updated author profile data
+        author_profile.updated_at = current_timestamp;
+        pack_profile_into_slice(&author_profile, &mut author_profile_account.data.borrow_mut())?;
+        
+        // Record the like so it can't be repeated; the address is predictable, so it may be pre-funded
+        let record = LikeRecord {
+            is_initialized: true,
+            post: *post_account.key,
+            liker: *user_account.key,
+            created_at: current_timestamp,
+        };
+        Self::allocate_pda_account(
+            program_id,
+            user_account,
+            like_record_account,
+            system_program,
+            record.required_space()?,
+            &[post_account.key.as_ref(), user_account.key.as_ref(), LIKE_RECORD_SEED, &[record_bump]],
+        )?;
+        pack_like_record_into_slice(&record, &mut like_record_account.data.borrow_mut())?;
+        
+        if post.rating != previous_rating {
+            Event::PostRatingChanged {
+                post: *post_account.key,
+                author: post.author,
+                previous: previous_rating,
+                rating: post.rating,
+            }.emit()?;
+        }
+        
+        if milestone {
+            Event::ConquerorMilestone {
+                post: *post_account.key,
+                author: post.author,
+                bonus: CONQUEROR_MILESTONE_UCR_BONUS,
+            }.emit()?;
+        }
+        
+        if milestone_revoked {
+            Event::ConquerorMilestoneRevoked {
+                post: *post_account.key,
+                author: post.author,
+                bonus: CONQUEROR_MILESTONE_UCR_BONUS,
+            }.emit()?;
+        }
+        
+        Event::PostLiked { post: *post_account.key, liker: *user_account.key, likes: post.likes }.emit()
+    }
+
+    // Load a user's membership in a community from its PDA, failing with NotAMember
+    fn load_membership(
+        program_id: &Pubkey,
+        community: &Pubkey,
+        user: &Pubkey,
+        membership_account: &AccountInfo,
+    ) -> Result<Membership, ProgramError> {
+        // Verify the membership account is the expected PDA
+        let (expected_pda, _bump_seed) = membership_pda(program_id, community, user);
+        if expected_pda != *membership_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        if membership_account.owner != program_id {
+            return Err(BlocksError::NotAMember.into());
+        }
+        let membership = unpack_membership_from_slice(&membership_account.data.borrow())?;
+        if !membership.is_initialized || membership.user != *user || membership.community != *community {
+            return Err(BlocksError::NotAMember.into());
+        }
+        Ok(membership)
+    }
+
+    // Check whether `blocker` has blocked `blocked` via the given block record PDA
+    fn is_blocked(
+        program_id: &Pubkey,
+        blocker: &Pubkey,
+        blocked: &Pubkey,
+        block_record_account: &AccountInfo,
+    ) -> Result<bool, ProgramError> {
+        // Verify the block record account is the expected PDA
+        let (expected_pda, _bump_seed) = block_record_pda(program_id, blocker, blocked);
+        if expected_pda != *block_record_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        if block_record_account.owner != program_id {
+            return Ok(false);
+        }
+        let record = unpack_block_record_from_slice(&block_record_account.data.borrow())?;
+        Ok(record.is_initialized)
+    }
+
+    // Reject usernames that are empty or exceed the seed-safe length
+    fn validate_username(username: &str) -> ProgramResult {
+        if username.is_empty() || username.len() > MAX_USERNAME_LEN {
+            msg!("Username must be 1-{} bytes", MAX_USERNAME_LEN);
+            return Err(BlocksError::FieldTooLong.into());
+        }
+        Ok(())
+    }
+
+    // Reject bios and image URIs over their limits instead of truncating them
+    fn validate_profile_fields(bio: &str, profile_image: &str, cover_image: &str) -> ProgramResult {
+        if bio.len() > MAX_BIO_LEN {
+            msg!("Bio exceeds {} bytes", MAX_BIO_LEN);
+            return Err(BlocksError::FieldTooLong.into());
+        }
+        if profile_image.len() > MAX_URI_LEN || cover_image.len() > MAX_URI_LEN {
+            msg!("Image URIs may not exceed {} bytes", MAX_URI_LEN);
+            return Err(BlocksError::FieldTooLong.into());
+        }
+        Ok(())
+    }
+
+    // Reject post content that exceeds the length or image limits
+    fn validate_post_content(content: &str, images: &[String]) -> ProgramResult {
+        if content.len() > MAX_POST_CONTENT_LEN {
+            msg!("Content is {} bytes, maximum is {}", content.len(), MAX_POST_CONTENT_LEN);
+            return Err(BlocksError::ContentTooLong.into());
+        }
+        if images.len() > MAX_POST_IMAGES {
+            msg!("Post has {} images, maximum is {}", images.len(), MAX_POST_IMAGES);
+            return Err(BlocksError::ContentTooLong.into());
+        }
+        for image in images {
+            if image.len() > MAX_URI_LEN {
+                msg!("Image URI is {} bytes, maximum is {}", image.len(), MAX_URI_LEN);
+                return Err(BlocksError::InvalidImageUri.into());
+            }
+            if !IMAGE_URI_SCHEMES.iter().any(|scheme| image.starts_with(scheme)) {
+                msg!("Image URIs must start with one of {:?}", IMAGE_URI_SCHEMES);
+                return Err(BlocksError::InvalidImageUri.into());
+            }
+        }
+        Ok(())
+    }
+
+    // Fail early with a descriptive message when too few accounts are passed
+    fn expect_accounts(accounts: &[AccountInfo], expected: usize, instruction: &str) -> ProgramResult {
+        if accounts.len() < expected {
+            msg!("{} expects at least {} accounts, got {}", instruction, expected, accounts.len());
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        Ok(())
+    }
+
+    // Refuse to close a post other accounts still reference unless the caller forces it
+    fn assert_unreferenced(post: &Post, force: bool) -> ProgramResult {
+        let references = post.reference_count();
+        if references > 0 && !force {
+            msg!("Post is still referenced by {} comments", references);
+            return Err(BlocksError::AccountStillReferenced.into());
+        }
+        Ok(())
+    }
+
+    // Reject accounts the instruction writes to that the client passed as read-only
+    fn assert_writable(accounts: &[&AccountInfo]) -> ProgramResult {
+        for account in accounts {
+            if !account.is_writable {
+                msg!("Account {} must be writable", account.key);
+                return Err(BlocksError::AccountNotWritable.into());
+            }
+        }
+        Ok(())
+    }
+
+    // Reject a system program account that isn't the real system program, before any CPI into it
+    fn assert_system_program(account: &AccountInfo) -> ProgramResult {
+        if !solana_program::system_program::check_id(account.key) {
+            msg!("Account {} is not the system program", account.key);
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+
+    // Reject payers that cannot fund a new account before the system program fails opaquely
+    fn assert_can_pay(payer: &AccountInfo, lamports: u64) -> ProgramResult {
+        if payer.lamports() < lamports {
+            msg!("Payer {} has {} lamports, {} needed", payer.key, payer.lamports(), lamports);
+            return Err(BlocksError::InsufficientFunds.into());
+        }
+        Ok(())
+    }
+
+    // Reject accounts that hold too few lamports to be rent exempt
+    fn assert_rent_exempt(account: &AccountInfo) -> ProgramResult {
+        let rent = Rent::get()?;
+        if account.lamports() < rent.minimum_balance(account.data_len()) {
+            msg!("Account {} is not rent exempt", account.key);
+            return Err(BlocksError::NotRentExempt.into());
+        }
+        Ok(())
+    }
+
+    // Names are 3-32 alphanumerics or hyphens, optionally prefixed with "sb/"
+    fn validate_community_name(name: &str) -> ProgramResult {
+        let base = name.strip_prefix(SB_COMMUNITY_PREFIX).unwrap_or(name);
+        if base.len() < MIN_COMMUNITY_NAME_LEN || base.len() > MAX_COMMUNITY_NAME_LEN {
+            msg!("Community name must be {}-{} characters", MIN_COMMUNITY_NAME_LEN, MAX_COMMUNITY_NAME_LEN);
+            return Err(BlocksError::InvalidCommunityName.into());
+        }
+        if !base.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            msg!("Community name may only contain letters, digits and hyphens");
+            return Err(BlocksError::InvalidCommunityName.into());
+        }
+        Ok(())
+    }
+
+    // Reject community metadata that exceeds the length or rule limits
+    fn validate_community_fields(description: &str, avatar: &str, rules: &[String]) -> ProgramResult {
+        if description.len() > MAX_COMMUNITY_DESCRIPTION_LEN {
+            msg!("Description exceeds {} bytes", MAX_COMMUNITY_DESCRIPTION_LEN);
+            return Err(BlocksError::FieldTooLong.into());
+        }
+        if avatar.len() > MAX_URI_LEN {
+            msg!("Avatar URI may not exceed {} bytes", MAX_URI_LEN);
+            return Err(BlocksError::FieldTooLong.into());
+        }
+        if rules.len() > MAX_COMMUNITY_RULES {
+            msg!("Community has {} rules, maximum is {}", rules.len(), MAX_COMMUNITY_RULES);
+            return Err(BlocksError::FieldTooLong.into());
+        }
+        if rules.iter().any(|rule| rule.len() > MAX_COMMUNITY_RULE_LEN) {
+            msg!("Rules may not exceed {} bytes", MAX_COMMUNITY_RULE_LEN);
+            return Err(BlocksError::FieldTooLong.into());
+        }
+        Ok(())
+    }
+
+    // Load the program state, which InitializeProgram must have created
+    fn load_program_state(
+        program_id: &Pubkey,
+        program_state_account: &AccountInfo,
+    ) -> Result<ProgramState, ProgramError> {
+        // Verify the program state account is the expected PDA
+        let (expected_pda, _bump_seed) = program_state_pda(program_id);
+        if expected_pda != *program_state_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        if program_state_account.owner != program_id {
+            msg!("Program state is not initialized");
+            return Err(ProgramError::UninitializedAccount);
+        }
+        Self::assert_rent_exempt(program_state_account)?;
+        
+        let state = unpack_program_state_from_slice(&program_state_account.data.borrow())?;
+        if !state.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        Ok(state)
+    }
+
+    // Reject signers other than the program authority
+    fn require_authority(signer: &AccountInfo, state: &ProgramState) -> ProgramResult {
+        if !signer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        if state.authority != *signer.key {
+            msg!("{} is not the program authority", signer.key);
+            return Err(BlocksError::Unauthorized.into());
+        }
+        Ok(())
+    }
+
+    // Read the rating thresholds, falling back to the defaults before the state exists
+    fn load_rating_config(program_id: &Pubkey, program_state_account: &AccountInfo) -> Result<RatingConfig, ProgramError> {
+        // Verify the program state account is the expected PDA
+        let (expected_pda, _bump_seed) = program_state_pda(program_id);
+        if expected_pda != *program_state_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        if program_state_account.owner != program_id {
+            return Ok(RatingConfig::default());
+        }
+        let state = unpack_program_state_from_slice(&program_state_account.data.borrow())?;
+        Ok(state.rating_config)
+    }
+
+    // Verify a profile account is the PDA registered under its username.
+    // The profile address is derived from the username it was created with, which a rename
+    // replaces, so the username registry is what links the current username to the account.
+    fn assert_registered_profile(
+        program_id: &Pubkey,
+        profile_account: &AccountInfo,
+        profile: &Profile,
+        username_registry_account: &AccountInfo,
+    ) -> ProgramResult {
+        // Verify the username registry account is the expected PDA
+        let (expected_registry, _bump_seed) = username_registry_pda(program_id, &profile.username);
+        if expected_registry != *username_registry_account.key || username_registry_account.owner != program_id {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        let registry = unpack_username_registry_from_slice(&username_registry_account.data.borrow())?;
+        if registry.profile != *profile_account.key || registry.owner != profile.owner {
+            msg!("Profile account {} is not the registered profile {}", profile_account.key, registry.profile);
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(())
+    }
+
+    // Load the user's post index, creating the PDA on first use
+    fn load_post_index<'a>(
+        program_id: &Pubkey,
+        user: &AccountInfo<'a>,
+        post_index_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> Result<PostIndex, ProgramError> {
+        // Verify the post index account is the expected PDA
+        let (expected_pda, bump_seed) = post_index_pda(program_id, user.key);
+        if expected_pda != *post_index_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        if post_index_account.owner == program_id {
+            Self::assert_rent_exempt(post_index_account)?;
+            return unpack_post_index_from_slice(&post_index_account.data.borrow());
+        }
+        
+        // Create account
+        Self::create_pda_account(
+            program_id,
+            user,
+            post_index_account,
+            system_program,
+            PostIndex::required_space(),
+            &[user.key.as_ref(), POST_INDEX_SEED, &[bump_seed]],
+        )?;
+        
+        Ok(PostIndex {
+            is_initialized: true,
+            owner: *user.key,
+            posts: Vec::new(),
+        })
+    }
+
+    // Append an entry to the last page of a paged index, opening the next page once it is full.
+    // `page_count` is the number of pages opened so far for `key`. Returns the page the entry went on.
+    #[allow(clippy::too_many_arguments)]
+    fn add_to_index<'a, T: PagedIndex>(
+        program_id: &Pubkey,
+        page_count: &mut u32,
+        key: &Pubkey,
+        entry: &Pubkey,
+        payer: &AccountInfo<'a>,
+        index_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> Result<u32, ProgramError> {
+        let is_last_page = page_count
+            .checked_sub(1)
+            .map(|page| paged_index_pda(program_id, key, T::SEED, page).0 == *index_account.key)
+            .unwrap_or(false);
+        
+        let mut index = if is_last_page {
+            let index: T = unpack_paged_index_from_slice(&index_account.data.borrow())?;
+            if index.is_full() {
+                return Err(T::FULL_ERROR.into());
+            }
+            index
+        } else {
+            // Otherwise the account must be the next page to open
+            let page = *page_count;
+            let (expected_pda, bump_seed) = paged_index_pda(program_id, key, T::SEED, page);
+            if expected_pda != *index_account.key {
+                return Err(ProgramError::InvalidArgument);
+            }
+            
+            // Create account
+            Self::create_pda_account(
+                program_id,
+                payer,
+                index_account,
+                system_program,
+                T::required_space(),
+                &[key.as_ref(), T::SEED, &page.to_le_bytes(), &[bump_seed]],
+            )?;
+            *page_count = page.checked_add(1).ok_or(BlocksError::ArithmeticOverflow)?;
+            
+            T::new_page(*key, page)
+        };
+        
+        index.entries_mut().push(*entry);
+        pack_paged_index_into_slice(&index, &mut index_account.data.borrow_mut())?;
+        
+        Ok(index.page())
+    }
+
+    // Drop an entry from the index page recorded for it; entries added before the index existed have no page
+    fn remove_from_index<T: PagedIndex>(
+        program_id: &Pubkey,
+        key: &Pubkey,
+        page: Option<u32>,
+        entry: &Pubkey,
+        index_account: &AccountInfo,
+    ) -> ProgramResult {
+        let page = match page {
+            Some(page) => page,
+            None => return Ok(()),
+        };
+        
+        // Verify the index account is the recorded page
+        let (expected_pda, _) = paged_index_pda(program_id, key, T::SEED, page);
+        if expected_pda != *index_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        let mut index: T = unpack_paged_index_from_slice(&index_account.data.borrow())?;
+        index.remove(entry);
+        pack_paged_index_into_slice(&index, &mut index_account.data.borrow_mut())
+    }
+
+    // Load the community's feed, creating the PDA on first use
+    fn load_community_feed<'a>(
+        program_id: &Pubkey,
+        community: &Pubkey,
+        payer: &AccountInfo<'a>,
+        community_feed_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> Result<CommunityFeed, ProgramError> {
+        // Verify the community feed account is the expected PDA
+        let (expected_pda, bump_seed) = community_feed_pda(program_id, community);
+        if expected_pda != *community_feed_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        if community_feed_account.owner == program_id {
+            Self::assert_rent_exempt(community_feed_account)?;
+            return unpack_community_feed_from_slice(&community_feed_account.data.borrow());
+        }
+        
+        // Create account
+        Self::create_pda_account(
+            program_id,
+            payer,
+            community_feed_account,
+            system_program,
+            CommunityFeed::required_space(),
+            &[community.as_ref(), COMMUNITY_FEED_SEED, &[bump_seed]],
+        )?;
+        
+        Ok(CommunityFeed {
+            is_initialized: true,
+            community: *community,
+            posts: Vec::new(),
+        })
+    }
+
+    // Load the leaderboard, creating the PDA on first use
+    fn load_leaderboard<'a>(
+        program_id: &Pubkey,
+        payer: &AccountInfo<'a>,
+        leaderboard_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> Result<Leaderboard, ProgramError> {
+        // Verify the leaderboard account is the expected PDA
+        let (expected_pda, bump_seed) = leaderboard_pda(program_id);
+        if expected_pda != *leaderboard_account.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        if leaderboard_account.owner == program_id {
+            Self::assert_rent_exempt(leaderboard_account)?;
+            return unpack_leaderboard_from_slice(&leaderboard_account.data.borrow());
+        }
+        
+        // Create account
+        Self::allocate_pda_account(
+            program_id,
+            payer,
+            leaderboard_account,
+            system_program,
+            Leaderboard::required_space(),
+            &[LEADERBOARD_SEED, &[bump_seed]],
+        )?;
+        
+        Ok(Leaderboard {
+            is_initialized: true,
+            entries: Vec::new(),
+        })
+    }
+
+    // Create a rent-exempt, program-owned PDA funded by the payer
+    fn create_pda_account<'a>(
+        program_id: &Pubkey,
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        // Calculate rent
+        let rent = Rent::get()?;
+        let lamports = rent.minimum_balance(space);
+        
+        // Fail clearly if the payer cannot cover the rent
+        Self::assert_can_pay(payer, lamports)?;
+        
+        invoke_signed(
+            &system_instruction::create_account(
+                payer.key,
+                new_account.key,
+                lamports,
+                space as u64,
+                program_id,
+            ),
+            &[
+                payer.clone(),
+                new_account.clone(),
+                system_program.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+
+    // Create a post or comment account at its PDA, `[author, "post", id]`
+    fn create_post_account<'a>(
+        program_id: &Pubkey,
+        payer: &AccountInfo<'a>,
+        post_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        post: &Post,
+    ) -> ProgramResult {
+        // Verify the post account is the expected PDA
+        let (expected_pda, bump_seed) = post_pda(program_id, &post.author, post.id);
+        if expected_pda != *post_account.key {
+            msg!("Post {} must be created at {}", post.id, expected_pda);
+            return Err(ProgramError::InvalidArgument);
+        }
+        
+        // Ids are never reused, so an existing account here means something is wrong
+        if post_account.owner == program_id {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        
+        let id_bytes = post.id.to_le_bytes();
+        Self::allocate_pda_account(
+            program_id,
+            payer,
+            post_account,
+            system_program,
+            post.required_space()?,
+            &[post.author.as_ref(), POST_SEED, &id_bytes, &[bump_seed]],
+        )?;
+        
+        // Pre-existing accounts must end up rent exempt
+        Self::assert_rent_exempt(post_account)
+    }
+
+    // Create a PDA that may already hold lamports.
+    // create_account fails when someone has sent lamports to a predictable address, so a funded
+    // account is created in three steps instead: top up rent, allocate, assign. That way a
+    // griefer can't block its creation, while the common unfunded case stays a single CPI.
+    fn allocate_pda_account<'a>(
+        program_id: &Pubkey,
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        if new_account.lamports() == 0 {
+            return Self::create_pda_account(program_id, payer, new_account, system_program, space, signer_seeds);
+        }
+        
+        // Only the rent the account doesn't already hold is transferred
+        let rent = Rent::get()?;
+        let lamports = rent.minimum_balance(space).saturating_sub(new_account.lamports());
+        if lamports > 0 {
+            // Fail clearly if the payer cannot cover the rent
+            Self::assert_can_pay(payer, lamports)?;
+            invoke(
+                &system_instruction::transfer(payer.key, new_account.key, lamports),
+                &[payer.clone(), new_account.clone(), system_program.clone()],
+            )?;
+        }
+        
+        invoke_signed(
+            &system_instruction::allocate(new_account.key, space as u64),
+            &[new_account.clone(), system_program.clone()],
+            &[signer_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(new_account.key, program_id),
+            &[new_account.clone(), system_program.clone()],
+            &[signer_seeds],
+        )
+    }
+
+    // Reallocate a program-owned account, topping up rent from the payer
+    fn resize_account<'a>(
+        account: &AccountInfo<'a>,
+        payer: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        new_len: usize,
+    ) -> ProgramResult {
+        // Stay within the runtime's per-instruction growth and total size limits
+        let growth = new_len.saturating_sub(account.data_len());
+        if growth > MAX_PERMITTED_DATA_INCREASE || new_len as u64 > MAX_PERMITTED_DATA_LENGTH {
+            msg!("Cannot resize account to {} bytes", new_len);
+            return Err(ProgramError::InvalidRealloc);
+        }
+        
+        // Top up lamports so the larger account stays rent exempt
+        let rent = Rent::get()?;
+        let shortfall = rent.minimum_balance(new_len).saturating_sub(account.lamports());
+        if shortfall > 0 {
+            Self::assert_can_pay(payer, shortfall)?;
+            invoke(
+                &system_instruction::transfer(payer.key, account.key, shortfall),
+                &[payer.clone(), account.clone(), system_program.clone()],
+            )?;
+        }
+        
+        account.realloc(new_len, false)
+    }
+
+    // Close a program-owned account, moving its lamports to the destination
+    fn close_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+        let lamports = account.lamports();
+        **destination.lamports.borrow_mut() = destination
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(BlocksError::ArithmeticOverflow)?;
+        **account.lamports.borrow_mut() = 0;
+        account.data.borrow_mut().fill(0);
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -1,7 +1,12 @@
 
+use crate::{
+    error::BlocksError,
+    pda::{FOLLOWER_INDEX_SEED, FOLLOWING_INDEX_SEED, MEMBER_INDEX_SEED},
+};
 use borsh::{BorshDeserialize, BorshSerialize};
+use std::{fmt, str::FromStr};
+use thiserror::Error;
 use solana_program::{
-    borsh::try_from_slice_unchecked,
     program_error::ProgramError,
     program_pack::{IsInitialized, Sealed},
     pubkey::Pubkey,
@@ -22,7 +27,32 @@ pub struct Profile {
     pub posts_count: u64,
     pub last_post_timestamp: u64,
     pub daily_post_count: u64,
-    pub is_verified: bool,            // Verification status
+    pub legacy_verified: bool,        // Mirrors is_verified() for clients reading the pre-v17 layout
+    pub ucr_tier: UcrTier,            // Tier derived from user_credit_rating
+    pub last_decay_timestamp: u64,    // Last time inactivity decay was applied
+    pub is_spam: bool,                // UCR at or below UCR_SPAM_USER
+    pub profile_image_hash: Option<[u8; 32]>, // Digest of the avatar's CID / transaction id
+    pub cover_image_hash: Option<[u8; 32]>,   // Digest of the cover image's CID / transaction id
+    pub pinned_post: Option<Pubkey>,  // Post highlighted at the top of the profile
+    pub follower_index_pages: u32,    // Number of follower index pages opened so far
+    pub following_index_pages: u32,   // Number of following index pages opened so far
+    pub total_likes_received: u64,    // Likes across all of the user's posts
+    pub total_comments_received: u64, // Comments from other users across all of the user's posts
+    pub last_post_id: u64,            // Id of the user's newest post or comment; ids are never reused
+    pub is_deleted: bool,             // Soft-deleted: hidden everywhere but kept until garbage collected
+    pub deleted_at: u64,              // Soft-deletion timestamp (0 while live)
+    pub milestone_awarded: bool,      // Conqueror milestone bonus already granted
+    pub is_private: bool,             // Follows need the owner's approval
+    pub updated_at: u64,              // Last time any handler changed the profile
+    pub recent_content: [ContentFingerprint; RECENT_CONTENT_HASHES], // Latest post bodies, for rejecting duplicate reposts
+    pub verification_level: VerificationLevel, // Badge tier; use set_verification_level to keep legacy_verified in step
+}
+
+// Digest of a post body and when it was posted
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Default, PartialEq, Debug)]
+pub struct ContentFingerprint {
+    pub hash: [u8; 32],
+    pub posted_at: u64,
 }
 
 impl Sealed for Profile {}
@@ -46,6 +76,22 @@ pub struct Post {
     pub images: Vec<String>,
     pub rating: PostRating,          // Rating based on likes
     pub in_kill_zone: bool,          // If post is in kill zone (< 0 likes)
+    pub edited_at: u64,              // Last edit timestamp (0 if never edited)
+    pub community: Option<Pubkey>,   // Community the post belongs to, if any
+    pub hidden: bool,                // Hidden by community moderators
+    pub total_tips: u64,             // Lamports tipped to the author for this post
+    pub quoted_post: Option<Pubkey>, // Original post when this is a quote post
+    pub report_count: u64,           // Number of distinct users who reported this post
+    pub content_hash: Option<[u8; 32]>, // Digest of off-chain media (IPFS CID / Arweave tx id)
+    pub parent: Option<Pubkey>,      // Post or comment this comment replies to (None for top-level posts)
+    pub thread_root: Pubkey,         // Top-level post of the thread (the post itself when top-level)
+    pub depth: u8,                   // Reply depth below the thread root (0 for top-level posts)
+    pub is_deleted: bool,            // Soft-deleted: dropped from feeds but kept until garbage collected
+    pub deleted_at: u64,             // Soft-deletion timestamp (0 while live)
+    pub ucr_window_start: u64,       // Start of the current daily UCR window
+    pub ucr_window_earned: i64,      // Undiminished UCR the post has earned its author in that window
+    pub allow_comments: bool,        // Whether the author accepts replies
+    pub earned_milestone: bool,      // Earned its author the Conqueror milestone bonus
 }
 
 impl Sealed for Post {}
@@ -56,8 +102,33 @@ impl IsInitialized for Post {
     }
 }
 
+impl Post {
+    // Accounts that point at this post as their parent; it can't be closed while any remain
+    pub fn reference_count(&self) -> u64 {
+        self.comments
+    }
+
+    // UCR the author actually gains when this post earns `amount` at `now`. Up to
+    // DAILY_POST_UCR_CAP per day counts in full; past it only every POST_UCR_DIMINISHING_DIVISOR-th point does.
+    pub fn capped_ucr_gain(&mut self, amount: i64, now: u64) -> i64 {
+        if amount <= 0 {
+            return amount;
+        }
+        if now.saturating_sub(self.ucr_window_start) >= SECONDS_PER_DAY {
+            self.ucr_window_start = now;
+            self.ucr_window_earned = 0;
+        }
+        let over_before = self.ucr_window_earned.saturating_sub(DAILY_POST_UCR_CAP).max(0);
+        self.ucr_window_earned = self.ucr_window_earned.saturating_add(amount);
+        let over_after = self.ucr_window_earned.saturating_sub(DAILY_POST_UCR_CAP).max(0);
+        let full = amount - (over_after - over_before);
+        full + over_after / POST_UCR_DIMINISHING_DIVISOR - over_before / POST_UCR_DIMINISHING_DIVISOR
+    }
+}
+
 // Rating based on like count
-#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, PartialEq)]
+// Variants are declared from lowest to highest, so the derived ordering ranks prestige
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
 pub enum PostRating {
     None,           // 0 likes
     Bronze,         // 5+ likes
@@ -69,21 +140,73 @@ pub enum PostRating {
     Conqueror,      // 1,000,000+ likes
 }
 
+// Minimum likes for each post rating, stored in the program state
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct RatingConfig {
+    pub bronze: u64,
+    pub silver: u64,
+    pub gold: u64,
+    pub platinum: u64,
+    pub diamond: u64,
+    pub ace: u64,
+    pub conqueror: u64,
+}
+
+impl Default for RatingConfig {
+    fn default() -> Self {
+        RatingConfig {
+            bronze: 5,
+            silver: 20,
+            gold: 50,
+            platinum: 150,
+            diamond: 500,
+            ace: 1_000,
+            conqueror: 1_000_000,
+        }
+    }
+}
+
+impl RatingConfig {
+    // Thresholds must be non-zero and strictly increasing
+    pub fn is_valid(&self) -> bool {
+        let thresholds = [
+            self.bronze,
+            self.silver,
+            self.gold,
+            self.platinum,
+            self.diamond,
+            self.ace,
+            self.conqueror,
+        ];
+        self.bronze > 0 && thresholds.windows(2).all(|pair| pair[0] < pair[1])
+    }
+}
+
 impl PostRating {
     // Calculate rating based on like count
     pub fn from_likes(likes: u64) -> Self {
+        Self::from_likes_with_config(likes, &RatingConfig::default())
+    }
+    
+    // Calculate rating based on like count using the given thresholds
+    pub fn from_likes_with_config(likes: u64, config: &RatingConfig) -> Self {
         match likes {
-            l if l >= 1_000_000 => PostRating::Conqueror,
-            l if l >= 1_000 => PostRating::Ace,
-            l if l >= 500 => PostRating::Diamond,
-            l if l >= 150 => PostRating::Platinum,
-            l if l >= 50 => PostRating::Gold,
-            l if l >= 20 => PostRating::Silver,
-            l if l >= 5 => PostRating::Bronze,
+            l if l >= config.conqueror => PostRating::Conqueror,
+            l if l >= config.ace => PostRating::Ace,
+            l if l >= config.diamond => PostRating::Diamond,
+            l if l >= config.platinum => PostRating::Platinum,
+            l if l >= config.gold => PostRating::Gold,
+            l if l >= config.silver => PostRating::Silver,
+            l if l >= config.bronze => PostRating::Bronze,
             _ => PostRating::None,
         }
     }
     
+    // Position on the rating ladder, 0 (none) through 7 (conqueror)
+    pub fn level(&self) -> u8 {
+        *self as u8
+    }
+    
     // Convert rating to string
     pub fn to_string(&self) -> &str {
         match self {
@@ -99,6 +222,36 @@ impl PostRating {
     }
 }
 
+impl fmt::Display for PostRating {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.to_string())
+    }
+}
+
+// Returned when parsing a string that names no post rating
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("Unknown post rating: {0}")]
+pub struct ParsePostRatingError(pub String);
+
+impl FromStr for PostRating {
+    type Err = ParsePostRatingError;
+
+    // Parse the names produced by `to_string`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(PostRating::None),
+            "bronze" => Ok(PostRating::Bronze),
+            "silver" => Ok(PostRating::Silver),
+            "gold" => Ok(PostRating::Gold),
+            "platinum" => Ok(PostRating::Platinum),
+            "diamond" => Ok(PostRating::Diamond),
+            "ace" => Ok(PostRating::Ace),
+            "conqueror" => Ok(PostRating::Conqueror),
+            _ => Err(ParsePostRatingError(s.to_string())),
+        }
+    }
+}
+
 #[derive(BorshSerialize, BorshDeserialize)]
 pub struct Community {
     pub is_initialized: bool,
@@ -110,16 +263,390 @@ pub struct Community {
     pub member_count: u64,
     pub rules: Vec<String>,          // Community rules
     pub is_sb_community: bool,       // "sb/" prefix for subBlocks communities
+    pub pinned_post: Option<Pubkey>, // Post highlighted at the top of the community
+    pub member_index_pages: u32,     // Number of member index pages opened so far
+    pub is_deleted: bool,            // Soft-deleted: closed to activity but kept until garbage collected
+    pub deleted_at: u64,             // Soft-deletion timestamp (0 while live)
+    pub post_rules: CommunityPostRules, // Enforced requirements for posting, alongside the free-text rules
+    pub max_members: u64,            // Joins are refused once member_count reaches this
+    pub updated_at: u64,             // Last time any handler changed the community
 }
 
 impl Sealed for Community {}
 
+// Requirements a member must meet to post in a community
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct CommunityPostRules {
+    pub min_ucr_to_post: i64,   // Lowest UCR allowed to post (i64::MIN for no minimum)
+    pub require_verified: bool, // Only verified profiles may post
+}
+
+impl Default for CommunityPostRules {
+    fn default() -> Self {
+        CommunityPostRules {
+            min_ucr_to_post: i64::MIN,
+            require_verified: false,
+        }
+    }
+}
+
+impl CommunityPostRules {
+    // Whether `profile` satisfies every rule, failing with the first rule it breaks
+    pub fn check(&self, profile: &Profile) -> Result<(), BlocksError> {
+        if profile.user_credit_rating < self.min_ucr_to_post {
+            return Err(BlocksError::UcrBelowCommunityMinimum);
+        }
+        if self.require_verified && !profile.is_verified() {
+            return Err(BlocksError::VerificationRequired);
+        }
+        Ok(())
+    }
+}
+
 impl IsInitialized for Community {
     fn is_initialized(&self) -> bool {
         self.is_initialized
     }
 }
 
+// Role a member holds within a community
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, PartialEq)]
+pub enum Role {
+    Member,
+    Moderator,
+    Owner,
+}
+
+impl Role {
+    // Whether this role may moderate community content
+    pub fn can_moderate(&self) -> bool {
+        matches!(self, Role::Moderator | Role::Owner)
+    }
+}
+
+// Membership record (PDA seeded on [community, user, "member"])
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct Membership {
+    pub is_initialized: bool,
+    pub community: Pubkey,
+    pub user: Pubkey,
+    pub joined_at: u64,
+    pub role: Role,
+    pub index_page: Option<u32>,     // Member index page listing this user (None for members who joined before the index)
+}
+
+impl Sealed for Membership {}
+
+impl IsInitialized for Membership {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+// Block record (PDA seeded on [blocker, blocked, "block"])
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct BlockRecord {
+    pub is_initialized: bool,
+    pub blocker: Pubkey,
+    pub blocked: Pubkey,
+    pub created_at: u64,
+}
+
+impl Sealed for BlockRecord {}
+
+impl IsInitialized for BlockRecord {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+// Follow relationship (PDA seeded on [follower, followed, "follow"])
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct FollowRecord {
+    pub is_initialized: bool,
+    pub follower: Pubkey,
+    pub followed: Pubkey,
+    pub created_at: u64,
+    pub follower_page: Option<u32>,  // Follower index page of the followed user listing the follower
+    pub following_page: Option<u32>, // Following index page of the follower listing the followed user
+}
+
+impl Sealed for FollowRecord {}
+
+impl IsInitialized for FollowRecord {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+// Pending follow of a private profile (PDA seeded on [follower, followed, "follow_request"])
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct FollowRequest {
+    pub is_initialized: bool,
+    pub follower: Pubkey,
+    pub followed: Pubkey,
+    pub created_at: u64,
+}
+
+impl Sealed for FollowRequest {}
+
+impl IsInitialized for FollowRequest {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+// Report filed against a post (PDA seeded on [post, reporter, "report"])
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct Report {
+    pub is_initialized: bool,
+    pub post: Pubkey,
+    pub reporter: Pubkey,
+    pub reason: u8,
+    pub created_at: u64,
+}
+
+impl Sealed for Report {}
+
+impl IsInitialized for Report {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+// Claim on a username (PDA seeded on ["username", normalized username])
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct UsernameRegistry {
+    pub is_initialized: bool,
+    pub username: String,             // Normalized username
+    pub owner: Pubkey,
+    pub profile: Pubkey,
+}
+
+impl Sealed for UsernameRegistry {}
+
+impl IsInitialized for UsernameRegistry {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+// Comments a user has left on a post (PDA seeded on [post, commenter, "comment"])
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct CommentReceipt {
+    pub is_initialized: bool,
+    pub post: Pubkey,
+    pub commenter: Pubkey,
+    pub count: u64,
+}
+
+impl Sealed for CommentReceipt {}
+
+impl IsInitialized for CommentReceipt {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+// Post saved by a user (PDA seeded on [user, post, "bookmark"])
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct Bookmark {
+    pub is_initialized: bool,
+    pub user: Pubkey,
+    pub post: Pubkey,
+    pub created_at: u64,
+}
+
+impl Sealed for Bookmark {}
+
+impl IsInitialized for Bookmark {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+// A user's like of a post, so each user can like a post once (PDA seeded on [post, liker, "like"])
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct LikeRecord {
+    pub is_initialized: bool,
+    pub post: Pubkey,
+    pub liker: Pubkey,
+    pub created_at: u64,
+}
+
+impl Sealed for LikeRecord {}
+
+impl IsInitialized for LikeRecord {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+// A list of wallets split across numbered pages (PDAs seeded on [key, SEED, page]).
+// Pages are opened in order and new entries always go on the last page.
+pub trait PagedIndex: BorshSerialize + BorshDeserialize + IsInitialized + Versioned {
+    const KIND: AccountKind;
+    const SEED: &'static [u8];
+    const MAX_LEN: usize;
+    const FULL_ERROR: BlocksError;
+    
+    fn new_page(key: Pubkey, page: u32) -> Self;
+    fn page(&self) -> u32;
+    fn entries(&self) -> &Vec<Pubkey>;
+    fn entries_mut(&mut self) -> &mut Vec<Pubkey>;
+    
+    fn is_full(&self) -> bool {
+        self.entries().len() >= Self::MAX_LEN
+    }
+    
+    // Drop an entry from the page, returning whether it was listed
+    fn remove(&mut self, entry: &Pubkey) -> bool {
+        let entries = self.entries_mut();
+        match entries.iter().position(|e| e == entry) {
+            Some(position) => {
+                entries.swap_remove(position);
+                true
+            }
+            None => false,
+        }
+    }
+    
+    // Account space needed to store a full page
+    fn required_space() -> usize {
+        // is_initialized + key + page + vec length prefix + entries
+        ACCOUNT_HEADER_LEN + 1 + 32 + 4 + 4 + Self::MAX_LEN * 32
+    }
+}
+
+// One page of a community's member list (PDA seeded on [community, "members", page]).
+// Pages are numbered from 0 up to Community::member_index_pages.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct MemberIndex {
+    pub is_initialized: bool,
+    pub community: Pubkey,
+    pub page: u32,
+    pub members: Vec<Pubkey>,
+}
+
+impl Sealed for MemberIndex {}
+
+impl IsInitialized for MemberIndex {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+// Number of members listed on a single member index page
+pub const MAX_MEMBER_INDEX_LEN: usize = 64;
+
+impl PagedIndex for MemberIndex {
+    const KIND: AccountKind = AccountKind::MemberIndex;
+    const SEED: &'static [u8] = MEMBER_INDEX_SEED;
+    const MAX_LEN: usize = MAX_MEMBER_INDEX_LEN;
+    const FULL_ERROR: BlocksError = BlocksError::MemberIndexPageFull;
+    
+    fn new_page(community: Pubkey, page: u32) -> Self {
+        Self { is_initialized: true, community, page, members: Vec::new() }
+    }
+    
+    fn page(&self) -> u32 {
+        self.page
+    }
+    
+    fn entries(&self) -> &Vec<Pubkey> {
+        &self.members
+    }
+    
+    fn entries_mut(&mut self) -> &mut Vec<Pubkey> {
+        &mut self.members
+    }
+}
+
+// One page of the wallets following a user (PDA seeded on [user, "followers", page]).
+// Pages are numbered from 0 up to Profile::follower_index_pages.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct FollowerIndex {
+    pub is_initialized: bool,
+    pub owner: Pubkey,
+    pub page: u32,
+    pub followers: Vec<Pubkey>,
+}
+
+impl Sealed for FollowerIndex {}
+
+impl IsInitialized for FollowerIndex {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl PagedIndex for FollowerIndex {
+    const KIND: AccountKind = AccountKind::FollowerIndex;
+    const SEED: &'static [u8] = FOLLOWER_INDEX_SEED;
+    const MAX_LEN: usize = MAX_FOLLOW_INDEX_LEN;
+    const FULL_ERROR: BlocksError = BlocksError::FollowIndexPageFull;
+    
+    fn new_page(owner: Pubkey, page: u32) -> Self {
+        Self { is_initialized: true, owner, page, followers: Vec::new() }
+    }
+    
+    fn page(&self) -> u32 {
+        self.page
+    }
+    
+    fn entries(&self) -> &Vec<Pubkey> {
+        &self.followers
+    }
+    
+    fn entries_mut(&mut self) -> &mut Vec<Pubkey> {
+        &mut self.followers
+    }
+}
+
+// One page of the wallets a user follows (PDA seeded on [user, "following", page]).
+// Pages are numbered from 0 up to Profile::following_index_pages.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct FollowingIndex {
+    pub is_initialized: bool,
+    pub owner: Pubkey,
+    pub page: u32,
+    pub following: Vec<Pubkey>,
+}
+
+impl Sealed for FollowingIndex {}
+
+impl IsInitialized for FollowingIndex {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl PagedIndex for FollowingIndex {
+    const KIND: AccountKind = AccountKind::FollowingIndex;
+    const SEED: &'static [u8] = FOLLOWING_INDEX_SEED;
+    const MAX_LEN: usize = MAX_FOLLOW_INDEX_LEN;
+    const FULL_ERROR: BlocksError = BlocksError::FollowIndexPageFull;
+    
+    fn new_page(owner: Pubkey, page: u32) -> Self {
+        Self { is_initialized: true, owner, page, following: Vec::new() }
+    }
+    
+    fn page(&self) -> u32 {
+        self.page
+    }
+    
+    fn entries(&self) -> &Vec<Pubkey> {
+        &self.following
+    }
+    
+    fn entries_mut(&mut self) -> &mut Vec<Pubkey> {
+        &mut self.following
+    }
+}
+
+// Number of wallets listed on a single follower or following index page
+pub const MAX_FOLLOW_INDEX_LEN: usize = 64;
+
 // Constants for UCR Tiers (multiplied by 100 to handle decimals as integers)
 pub const UCR_TOP_CONTRIBUTOR: i64 = 420;     // 4.20
 pub const UCR_VALUABLE_CONTRIBUTOR: i64 = 69; // 0.69
@@ -127,55 +654,1047 @@ pub const UCR_AVERAGE_CONTRIBUTOR: i64 = 1;   // 0.01 (default)
 pub const UCR_LOW_VALUE_CONTRIBUTOR: i64 = -3; // -0.03
 pub const UCR_SPAM_USER: i64 = -10;           // -0.1
 
-// Constants for verification
-pub const VERIFICATION_THRESHOLD: u64 = 70;   // 70% likes rate for verification
+// Bounds every UCR change is clamped to, so scores can't drift past the tier scale
+pub const UCR_MAX: i64 = 100_000; // 1000.00
+pub const UCR_MIN: i64 = -1_000;  // -10.00
 
-// Baseline for UCR calculations
-pub const UCR_BASELINE: u64 = 100;
+// Verification badge tiers, declared from lowest to highest
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum VerificationLevel {
+    None,           // Unverified
+    Identity,       // Identity verified, or earned through VerifyProfile
+    Notable,        // Public figure
+    Organization,   // Company or institution
+}
 
-#[derive(BorshSerialize, BorshDeserialize)]
-pub struct ProgramState {
-    pub profiles_count: u64,
-    pub posts_count: u64,
-    pub communities_count: u64,
+// Contributor tier derived from a profile's UCR score
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, PartialEq)]
+pub enum UcrTier {
+    SpamUser,               // <= -0.1
+    LowValueContributor,    // <= -0.03
+    AverageContributor,     // default
+    ValuableContributor,    // >= 0.69
+    TopContributor,         // >= 4.20
 }
 
-// Helper functions
-pub fn pack_profile_into_slice(profile: &Profile, dst: &mut [u8]) -> Result<(), ProgramError> {
-    let data = profile.try_to_vec()?;
-    if data.len() > dst.len() {
-        return Err(ProgramError::InvalidAccountData);
+impl UcrTier {
+    // Calculate tier based on UCR score
+    pub fn from_score(score: i64) -> Self {
+        match score {
+            s if s >= UCR_TOP_CONTRIBUTOR => UcrTier::TopContributor,
+            s if s >= UCR_VALUABLE_CONTRIBUTOR => UcrTier::ValuableContributor,
+            s if s <= UCR_SPAM_USER => UcrTier::SpamUser,
+            s if s <= UCR_LOW_VALUE_CONTRIBUTOR => UcrTier::LowValueContributor,
+            _ => UcrTier::AverageContributor,
+        }
     }
-    dst[0..data.len()].copy_from_slice(&data);
-    Ok(())
 }
 
-pub fn unpack_profile_from_slice(src: &[u8]) -> Result<Profile, ProgramError> {
-    try_from_slice_unchecked::<Profile>(src).map_err(|_| ProgramError::InvalidAccountData)
-}
+// UCR granted to an author per like, by the liker's tier
+pub const LIKE_WEIGHT_SPAM_USER: i64 = 0;
+pub const LIKE_WEIGHT_LOW_VALUE_CONTRIBUTOR: i64 = 0;
+pub const LIKE_WEIGHT_AVERAGE_CONTRIBUTOR: i64 = UCR_PER_LIKE;
+pub const LIKE_WEIGHT_VALUABLE_CONTRIBUTOR: i64 = 2 * UCR_PER_LIKE;
+pub const LIKE_WEIGHT_TOP_CONTRIBUTOR: i64 = 3 * UCR_PER_LIKE;
 
-pub fn pack_post_into_slice(post: &Post, dst: &mut [u8]) -> Result<(), ProgramError> {
-    let data = post.try_to_vec()?;
-    if data.len() > dst.len() {
-        return Err(ProgramError::InvalidAccountData);
+// Most likes a single LikePosts instruction may apply
+pub const MAX_BATCH_LIKES: usize = 10;
+
+impl UcrTier {
+    // UCR a like from a user in this tier is worth
+    pub fn like_weight(&self) -> i64 {
+        match self {
+            UcrTier::SpamUser => LIKE_WEIGHT_SPAM_USER,
+            UcrTier::LowValueContributor => LIKE_WEIGHT_LOW_VALUE_CONTRIBUTOR,
+            UcrTier::AverageContributor => LIKE_WEIGHT_AVERAGE_CONTRIBUTOR,
+            UcrTier::ValuableContributor => LIKE_WEIGHT_VALUABLE_CONTRIBUTOR,
+            UcrTier::TopContributor => LIKE_WEIGHT_TOP_CONTRIBUTOR,
+        }
     }
-    dst[0..data.len()].copy_from_slice(&data);
-    Ok(())
 }
 
-pub fn unpack_post_from_slice(src: &[u8]) -> Result<Post, ProgramError> {
-    try_from_slice_unchecked::<Post>(src).map_err(|_| ProgramError::InvalidAccountData)
-}
+// Posts allowed per day by tier
+pub const DAILY_POSTS_SPAM_USER: u64 = 0;
+pub const DAILY_POSTS_LOW_VALUE_CONTRIBUTOR: u64 = 3;
+pub const DAILY_POSTS_AVERAGE_CONTRIBUTOR: u64 = 10;
+pub const DAILY_POSTS_VALUABLE_CONTRIBUTOR: u64 = 25;
+pub const DAILY_POSTS_TOP_CONTRIBUTOR: u64 = 50;
 
-pub fn pack_community_into_slice(community: &Community, dst: &mut [u8]) -> Result<(), ProgramError> {
-    let data = community.try_to_vec()?;
-    if data.len() > dst.len() {
-        return Err(ProgramError::InvalidAccountData);
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+// Time after creation during which a post can still be edited
+pub const EDIT_WINDOW_SECONDS: u64 = 15 * 60;
+// Longer edit window for valuable and top contributors
+pub const EXTENDED_EDIT_WINDOW_SECONDS: u64 = 60 * 60;
+
+impl UcrTier {
+    // Seconds after creation that users in this tier may edit their posts
+    pub fn edit_window(&self) -> u64 {
+        match self {
+            UcrTier::ValuableContributor | UcrTier::TopContributor => EXTENDED_EDIT_WINDOW_SECONDS,
+            _ => EDIT_WINDOW_SECONDS,
+        }
     }
-    dst[0..data.len()].copy_from_slice(&data);
-    Ok(())
-}
+    
+
+    // Daily posting cap for users in this tier
+    pub fn daily_post_limit(&self) -> u64 {
+        match self {
+            UcrTier::SpamUser => DAILY_POSTS_SPAM_USER,
+            UcrTier::LowValueContributor => DAILY_POSTS_LOW_VALUE_CONTRIBUTOR,
+            UcrTier::AverageContributor => DAILY_POSTS_AVERAGE_CONTRIBUTOR,
+            UcrTier::ValuableContributor => DAILY_POSTS_VALUABLE_CONTRIBUTOR,
+            UcrTier::TopContributor => DAILY_POSTS_TOP_CONTRIBUTOR,
+        }
+    }
+}
+
+impl Profile {
+    // Current tier for branching in handlers (rate limits, posting caps)
+    pub fn tier(&self) -> UcrTier {
+        UcrTier::from_score(self.user_credit_rating)
+    }
+
+    // Whether the profile holds any verification badge
+    pub fn is_verified(&self) -> bool {
+        self.verification_level != VerificationLevel::None
+    }
+
+    // Change the verification badge, keeping the legacy flag in step
+    pub fn set_verification_level(&mut self, level: VerificationLevel) {
+        self.verification_level = level;
+        self.legacy_verified = self.is_verified();
+    }
+
+    // Whether the profile is barred from posting and commenting
+    pub fn is_spam_user(&self) -> bool {
+        self.user_credit_rating <= UCR_SPAM_USER
+    }
+
+    // Clamp UCR into [UCR_MIN, UCR_MAX] and refresh the stored tier and spam flag after a UCR change
+    pub fn refresh_ucr_status(&mut self) {
+        self.user_credit_rating = self.user_credit_rating.clamp(UCR_MIN, UCR_MAX);
+        self.ucr_tier = self.tier();
+        self.is_spam = self.is_spam_user();
+    }
+
+    // Posts counted against today's cap; the count resets after a day without posting
+    pub fn daily_posts_used(&self, now: u64) -> u64 {
+        if now.saturating_sub(self.last_post_timestamp) > SECONDS_PER_DAY {
+            0
+        } else {
+            self.daily_post_count
+        }
+    }
+
+    // Whether a post body with this digest was posted within the duplicate window
+    pub fn is_duplicate_content(&self, hash: &[u8; 32], now: u64) -> bool {
+        self.recent_content.iter().any(|recent| {
+            recent.hash == *hash && now.saturating_sub(recent.posted_at) < DUPLICATE_CONTENT_WINDOW
+        })
+    }
+
+    // Remember a post body's digest in place of the oldest one kept
+    pub fn record_content(&mut self, hash: [u8; 32], now: u64) {
+        if let Some(oldest) = self.recent_content.iter_mut().min_by_key(|recent| recent.posted_at) {
+            *oldest = ContentFingerprint { hash, posted_at: now };
+        }
+    }
+
+    // Likes received per post as a percentage (0 before the first post)
+    pub fn engagement_rate(&self) -> u64 {
+        if self.posts_count == 0 {
+            return 0;
+        }
+        self.total_likes_received.saturating_mul(100) / self.posts_count
+    }
+    
+    // Take a deleted post's likes and comments out of the engagement aggregates; saturating,
+    // since the user's own comments and engagement from before version 6 were never counted
+    pub fn remove_engagement(&mut self, post: &Post) {
+        self.total_likes_received = self.total_likes_received.saturating_sub(post.likes);
+        self.total_comments_received = self.total_comments_received.saturating_sub(post.comments);
+    }
+}
+
+// Constants for profile fields
+pub const MAX_USERNAME_LEN: usize = 32;  // Usernames are a PDA seed, which caps them at 32 bytes
+pub const MAX_BIO_LEN: usize = 256;
+pub const MAX_URI_LEN: usize = 256;     // Profile and cover image URIs
+
+// Constants for post content; comments are stored as posts and share these limits
+pub const MAX_POST_CONTENT_LEN: usize = 512;
+pub const MAX_POST_IMAGES: usize = 4;
+pub const IMAGE_URI_SCHEMES: [&str; 3] = ["https://", "http://", "ipfs://"];
+
+// Duplicate post detection: a body matching one of the author's last RECENT_CONTENT_HASHES
+// posts within DUPLICATE_CONTENT_WINDOW seconds is rejected
+pub const RECENT_CONTENT_HASHES: usize = 5;
+pub const DUPLICATE_CONTENT_WINDOW: u64 = 60 * 60;
+
+// Constants for community names
+pub const SB_COMMUNITY_PREFIX: &str = "sb/";
+pub const MIN_COMMUNITY_NAME_LEN: usize = 3;
+pub const MAX_COMMUNITY_NAME_LEN: usize = 32;
+pub const MAX_COMMUNITY_MEMBERS: u64 = 1_000_000; // Default and upper bound for Community::max_members
+pub const MAX_COMMUNITY_DESCRIPTION_LEN: usize = 256;
+pub const MAX_COMMUNITY_RULES: usize = 10;
+pub const MAX_COMMUNITY_RULE_LEN: usize = 128;
+pub const MAX_INITIAL_MEMBERS: usize = 16; // Members CreateCommunityWithMembers may register; all fit on the first index page
+
+// Constants for tipping
+pub const TIP_LAMPORTS_PER_UCR: u64 = 10_000_000; // 0.01 SOL tipped earns UCR_PER_TIP
+
+// UCR an author earns per engagement; the reputation economy's tuning knobs
+pub const UCR_PER_LIKE: i64 = 1;    // Per like from an average contributor, scaled by the liker's tier
+pub const UCR_PER_COMMENT: i64 = 1; // Per comment from another user
+pub const UCR_PER_MIRROR: i64 = 1;  // Per quote of the post by another user
+pub const UCR_PER_TIP: i64 = 1;     // Per TIP_LAMPORTS_PER_UCR tipped
+
+// Diminishing returns on UCR a single post earns its author
+pub const DAILY_POST_UCR_CAP: i64 = 50;           // UCR per post per day that counts in full
+pub const POST_UCR_DIMINISHING_DIVISOR: i64 = 4;  // Past the cap, UCR counts at a quarter of its value
+
+// One-time UCR bonus for an author's first post to reach PostRating::Conqueror
+pub const CONQUEROR_MILESTONE_UCR_BONUS: i64 = 100;
+
+// Constants for UCR decay
+pub const UCR_DECAY_PER_DAY: i64 = 1;                  // UCR lost per full day without posting
+pub const UCR_DECAY_FLOOR: i64 = UCR_AVERAGE_CONTRIBUTOR; // Inactivity never decays below the default tier
+
+// Constants for comments
+pub const MAX_COMMENTS_PER_USER_PER_POST: u64 = 3;
+pub const MAX_REPLY_DEPTH: u8 = 6; // Deepest reply allowed below a top-level post
+
+// Constants for reports
+pub const REPORT_HIDE_THRESHOLD: u64 = 5; // Reports needed before a post is hidden automatically
+
+// Kill zone policy: content that starts out, or ends up, suppressed
+pub const KILL_ZONE_MAX_AUTHOR_UCR: i64 = UCR_LOW_VALUE_CONTRIBUTOR; // Authors at or below this UCR post straight into the kill zone
+pub const KILL_ZONE_REPORT_THRESHOLD: u64 = 10;                      // Reports that push a post into the kill zone
+
+// Whether new posts and comments by this author start in the kill zone
+pub fn starts_in_kill_zone(author: &Profile) -> bool {
+    author.user_credit_rating <= KILL_ZONE_MAX_AUTHOR_UCR
+}
+
+// Constants for verification
+pub const VERIFICATION_THRESHOLD: u64 = 70;   // 70% likes rate for verification
+pub const VERIFICATION_MIN_POSTS: u64 = 10;   // Minimum posts before the like rate counts
+
+// Whether a like rate across `post_count` posts clears the verification bar
+pub fn meets_verification_threshold(total_likes: u64, post_count: u64) -> bool {
+    post_count >= VERIFICATION_MIN_POSTS
+        && total_likes.saturating_mul(100) / post_count >= VERIFICATION_THRESHOLD
+}
+
+// Derived profile figures shared by the program and clients
+#[derive(Clone, Copy, PartialEq)]
+pub struct ProfileStats {
+    pub tier: UcrTier,
+    pub is_verified: bool,
+    pub meets_verification_min_posts: bool, // meets_verification_threshold also checks the like rate
+    pub remaining_daily_posts: u64,
+}
+
+// Compute a profile's derived stats as of `now` (unix seconds)
+pub fn profile_stats(profile: &Profile, now: u64) -> ProfileStats {
+    let tier = profile.tier();
+    ProfileStats {
+        tier,
+        is_verified: profile.is_verified(),
+        meets_verification_min_posts: profile.posts_count >= VERIFICATION_MIN_POSTS,
+        remaining_daily_posts: tier.daily_post_limit().saturating_sub(profile.daily_posts_used(now)),
+    }
+}
+
+// Baseline for UCR calculations
+pub const UCR_BASELINE: u64 = 100;
+
+// Global program state (PDA seeded on ["state"])
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ProgramState {
+    pub is_initialized: bool,
+    pub profiles_count: u64,
+    pub posts_count: u64,
+    pub communities_count: u64,
+    pub authority: Pubkey,            // Admin allowed to change program settings
+    pub rating_config: RatingConfig,  // Like thresholds for post ratings
+}
+
+impl Sealed for ProgramState {}
+
+impl IsInitialized for ProgramState {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+// Most recent posts by a user (PDA seeded on [user, "posts"])
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct PostIndex {
+    pub is_initialized: bool,
+    pub owner: Pubkey,
+    pub posts: Vec<Pubkey>,           // Post accounts, oldest first
+}
+
+impl Sealed for PostIndex {}
+
+impl IsInitialized for PostIndex {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+// Number of recent posts kept in a post index
+pub const MAX_POST_INDEX_LEN: usize = 64;
+
+impl PostIndex {
+    // Record a new post, evicting the oldest once the index is full
+    pub fn push(&mut self, post: Pubkey) {
+        if self.posts.len() >= MAX_POST_INDEX_LEN {
+            self.posts.remove(0);
+        }
+        self.posts.push(post);
+    }
+}
+
+// Most recent posts in a community (PDA seeded on [community, "feed"])
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct CommunityFeed {
+    pub is_initialized: bool,
+    pub community: Pubkey,
+    pub posts: Vec<Pubkey>,           // Post accounts, newest first
+}
+
+impl Sealed for CommunityFeed {}
+
+impl IsInitialized for CommunityFeed {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+// Number of recent posts kept in a community feed
+pub const MAX_COMMUNITY_FEED_LEN: usize = 64;
+
+impl CommunityFeed {
+    // Record a new post at the front, evicting the oldest once the feed is full
+    pub fn push(&mut self, post: Pubkey) {
+        self.posts.insert(0, post);
+        self.posts.truncate(MAX_COMMUNITY_FEED_LEN);
+    }
+}
+
+// A profile's place on the leaderboard
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct LeaderboardEntry {
+    pub profile: Pubkey,
+    pub user_credit_rating: i64,
+}
+
+// Highest-UCR profiles submitted through UpdateLeaderboard (PDA seeded on ["leaderboard"])
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct Leaderboard {
+    pub is_initialized: bool,
+    pub entries: Vec<LeaderboardEntry>, // Highest UCR first; ties keep the earlier entry ahead
+}
+
+impl Sealed for Leaderboard {}
+
+impl IsInitialized for Leaderboard {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+// Number of profiles kept on the leaderboard, and the most UpdateLeaderboard takes at once
+pub const LEADERBOARD_LEN: usize = 10;
+
+impl Leaderboard {
+    // Place a profile by its current UCR, dropping whoever falls off the end.
+    // Returns the profile's rank, or None when it doesn't make the board.
+    pub fn record(&mut self, profile: Pubkey, user_credit_rating: i64) -> Option<usize> {
+        self.remove(&profile);
+        let position = self
+            .entries
+            .iter()
+            .position(|entry| entry.user_credit_rating < user_credit_rating)
+            .unwrap_or(self.entries.len());
+        if position >= LEADERBOARD_LEN {
+            return None;
+        }
+        self.entries.insert(position, LeaderboardEntry { profile, user_credit_rating });
+        self.entries.truncate(LEADERBOARD_LEN);
+        Some(position)
+    }
+
+    // Drop a profile from the leaderboard, returning whether it was listed
+    pub fn remove(&mut self, profile: &Pubkey) -> bool {
+        match self.entries.iter().position(|entry| entry.profile == *profile) {
+            Some(position) => {
+                self.entries.remove(position);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// Keywords a user wants hidden; clients do the filtering (PDA seeded on [user, "mutes"])
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct MutePreferences {
+    pub is_initialized: bool,
+    pub owner: Pubkey,
+    pub words: Vec<String>,
+}
+
+impl Sealed for MutePreferences {}
+
+impl IsInitialized for MutePreferences {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+// Constants for muted words
+pub const MAX_MUTED_WORDS: usize = 32;
+pub const MAX_MUTED_WORD_LEN: usize = 32;
+
+impl MutePreferences {
+    // Whether `word` is already muted, ignoring ASCII case
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.iter().any(|muted| muted.eq_ignore_ascii_case(word))
+    }
+
+    // Unmute `word`, returning whether it was muted
+    pub fn remove(&mut self, word: &str) -> bool {
+        match self.words.iter().position(|muted| muted.eq_ignore_ascii_case(word)) {
+            Some(position) => {
+                self.words.swap_remove(position);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// Extra bytes reserved beyond the serialized size so accounts can absorb small updates
+pub const ACCOUNT_SPACE_MARGIN: usize = 64;
+
+impl Profile {
+    // Account space needed to store this profile
+    pub fn required_space(&self) -> Result<usize, ProgramError> {
+        Ok(ACCOUNT_HEADER_LEN + self.try_to_vec()?.len() + ACCOUNT_SPACE_MARGIN)
+    }
+}
+
+impl Post {
+    // Account space needed to store this post
+    pub fn required_space(&self) -> Result<usize, ProgramError> {
+        Ok(ACCOUNT_HEADER_LEN + self.try_to_vec()?.len() + ACCOUNT_SPACE_MARGIN)
+    }
+}
+
+impl Community {
+    // Account space needed to store this community
+    pub fn required_space(&self) -> Result<usize, ProgramError> {
+        Ok(ACCOUNT_HEADER_LEN + self.try_to_vec()?.len() + ACCOUNT_SPACE_MARGIN)
+    }
+}
+
+impl Membership {
+    // Account space needed to store this membership
+    pub fn required_space(&self) -> Result<usize, ProgramError> {
+        Ok(ACCOUNT_HEADER_LEN + self.try_to_vec()?.len() + ACCOUNT_SPACE_MARGIN)
+    }
+}
+
+impl BlockRecord {
+    // Account space needed to store this block record
+    pub fn required_space(&self) -> Result<usize, ProgramError> {
+        Ok(ACCOUNT_HEADER_LEN + self.try_to_vec()?.len() + ACCOUNT_SPACE_MARGIN)
+    }
+}
+
+impl FollowRecord {
+    // Account space needed to store this follow record
+    pub fn required_space(&self) -> Result<usize, ProgramError> {
+        Ok(ACCOUNT_HEADER_LEN + self.try_to_vec()?.len() + ACCOUNT_SPACE_MARGIN)
+    }
+}
+
+impl FollowRequest {
+    // Account space needed to store this follow request
+    pub fn required_space(&self) -> Result<usize, ProgramError> {
+        Ok(ACCOUNT_HEADER_LEN + self.try_to_vec()?.len() + ACCOUNT_SPACE_MARGIN)
+    }
+}
+
+impl Report {
+    // Account space needed to store this report
+    pub fn required_space(&self) -> Result<usize, ProgramError> {
+        Ok(ACCOUNT_HEADER_LEN + self.try_to_vec()?.len() + ACCOUNT_SPACE_MARGIN)
+    }
+}
+
+impl UsernameRegistry {
+    // Account space needed to store this username claim
+    pub fn required_space(&self) -> Result<usize, ProgramError> {
+        Ok(ACCOUNT_HEADER_LEN + self.try_to_vec()?.len() + ACCOUNT_SPACE_MARGIN)
+    }
+}
+
+impl CommentReceipt {
+    // Account space needed to store this comment receipt
+    pub fn required_space(&self) -> Result<usize, ProgramError> {
+        Ok(ACCOUNT_HEADER_LEN + self.try_to_vec()?.len() + ACCOUNT_SPACE_MARGIN)
+    }
+}
+
+impl Bookmark {
+    // Account space needed to store this bookmark
+    pub fn required_space(&self) -> Result<usize, ProgramError> {
+        Ok(ACCOUNT_HEADER_LEN + self.try_to_vec()?.len() + ACCOUNT_SPACE_MARGIN)
+    }
+}
+
+impl LikeRecord {
+    // Account space needed to store this like record
+    pub fn required_space(&self) -> Result<usize, ProgramError> {
+        Ok(ACCOUNT_HEADER_LEN + self.try_to_vec()?.len() + ACCOUNT_SPACE_MARGIN)
+    }
+}
+
+impl PostIndex {
+    // Account space needed to store a full post index
+    pub fn required_space() -> usize {
+        // is_initialized + owner + vec length prefix + entries
+        ACCOUNT_HEADER_LEN + 1 + 32 + 4 + MAX_POST_INDEX_LEN * 32
+    }
+}
+
+impl CommunityFeed {
+    // Account space needed to store a full community feed
+    pub fn required_space() -> usize {
+        // is_initialized + community + vec length prefix + entries
+        ACCOUNT_HEADER_LEN + 1 + 32 + 4 + MAX_COMMUNITY_FEED_LEN * 32
+    }
+}
+
+impl MutePreferences {
+    // Account space needed to store a full list of the longest muted words
+    pub fn required_space() -> usize {
+        // is_initialized + owner + vec length prefix + (string length prefix + bytes) per word
+        ACCOUNT_HEADER_LEN + 1 + 32 + 4 + MAX_MUTED_WORDS * (4 + MAX_MUTED_WORD_LEN)
+    }
+}
+
+impl Leaderboard {
+    // Account space needed to store a full leaderboard
+    pub fn required_space() -> usize {
+        // is_initialized + vec length prefix + (profile + UCR) per entry
+        ACCOUNT_HEADER_LEN + 1 + 4 + LEADERBOARD_LEN * (32 + 8)
+    }
+}
+
+impl ProgramState {
+    // Account space needed to store the program state
+    pub fn required_space(&self) -> Result<usize, ProgramError> {
+        Ok(ACCOUNT_HEADER_LEN + self.try_to_vec()?.len() + ACCOUNT_SPACE_MARGIN)
+    }
+}
+
+// Discriminator byte written at the start of every account, followed by the schema version
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum AccountKind {
+    Uninitialized = 0,
+    Profile = 1,
+    Post = 2,
+    Community = 3,
+    Membership = 4,
+    ProgramState = 5,
+    BlockRecord = 6,
+    FollowRecord = 7,
+    PostIndex = 8,
+    Report = 9,
+    UsernameRegistry = 10,
+    CommentReceipt = 11,
+    Bookmark = 12,
+    MemberIndex = 13,
+    FollowerIndex = 14,
+    FollowingIndex = 15,
+    CommunityFeed = 16,
+    MutePreferences = 17,
+    FollowRequest = 18,
+    Leaderboard = 19,
+    LikeRecord = 20,
+}
+
+// Bytes preceding the serialized struct in account data: [kind, schema version]
+pub const ACCOUNT_HEADER_LEN: usize = 2;
+
+// Schema version written by this program.
+// 1: the original field set of each struct
+// 2: every field appended since (see the per-field comments on each struct)
+// 3: Community::member_index_pages and Membership::index_page
+// 4: Profile::follower_index_pages / following_index_pages and FollowRecord::follower_page / following_page
+// 5: Post::parent, thread_root and depth
+// 6: Profile::total_likes_received and total_comments_received
+// 7: Profile::last_post_id
+// 8: is_deleted and deleted_at on Profile, Post and Community
+// 9: Post::ucr_window_start and ucr_window_earned
+// 10: Profile::milestone_awarded
+// 11: Post::allow_comments
+// 12: Community::post_rules
+// 13: Profile::is_private
+// 14: Community::max_members
+// 15: updated_at on Profile and Community
+// 16: Profile::recent_content
+// 17: Profile::verification_level
+// 18: Post::earned_milestone
+pub const ACCOUNT_VERSION: u8 = 18;
+
+// Zero bytes appended when decoding an older record so newly appended fields read as defaults.
+// Must cover every field appended since version 1; the profile's additions are the largest (~360 bytes).
+const UPGRADE_PADDING: usize = 512;
+
+// Fixups applied after decoding a record written under an older schema version.
+// Appended fields decode from zero bytes, so only fields whose zero value is wrong need handling.
+pub trait Versioned {
+    fn upgrade(&mut self, _from_version: u8) {}
+}
+
+impl Versioned for Profile {
+    fn upgrade(&mut self, from_version: u8) {
+        if from_version < 2 {
+            // The stored tier and spam flag were added in version 2
+            self.refresh_ucr_status();
+        }
+        if from_version < 7 {
+            // Earlier posts weren't PDAs, so continuing from the post count can't collide
+            self.last_post_id = self.posts_count;
+        }
+        if from_version < 15 {
+            // No change has been stamped yet, so creation is the last known update
+            self.updated_at = self.created_at;
+        }
+        if from_version < 17 && self.legacy_verified {
+            // The badge was a single flag, granted for identity or earned through VerifyProfile
+            self.verification_level = VerificationLevel::Identity;
+        }
+    }
+}
+
+impl Versioned for ProgramState {
+    fn upgrade(&mut self, from_version: u8) {
+        if from_version < 2 {
+            self.rating_config = RatingConfig::default();
+        }
+    }
+}
+
+impl Versioned for Post {
+    fn upgrade(&mut self, from_version: u8) {
+        if from_version < 11 {
+            // Every post accepted comments before authors could turn them off
+            self.allow_comments = true;
+        }
+    }
+}
+
+impl Versioned for Community {
+    fn upgrade(&mut self, from_version: u8) {
+        if from_version < 12 {
+            // A zero minimum UCR would shut out members with negative scores
+            self.post_rules = CommunityPostRules::default();
+        }
+        if from_version < 14 {
+            self.max_members = MAX_COMMUNITY_MEMBERS;
+        }
+    }
+}
+impl Versioned for Membership {}
+impl Versioned for BlockRecord {}
+impl Versioned for FollowRecord {}
+impl Versioned for PostIndex {}
+impl Versioned for Report {}
+impl Versioned for UsernameRegistry {}
+impl Versioned for CommentReceipt {}
+impl Versioned for Bookmark {}
+impl Versioned for MemberIndex {}
+impl Versioned for FollowerIndex {}
+impl Versioned for FollowingIndex {}
+impl Versioned for CommunityFeed {}
+impl Versioned for MutePreferences {}
+impl Versioned for FollowRequest {}
+impl Versioned for Leaderboard {}
+impl Versioned for LikeRecord {}
+
+// Any program account decoded by its discriminator
+pub enum DecodedAccount {
+    Profile(Box<Profile>), // Boxed: the recent content hashes make it far larger than the other records
+    Post(Post),
+    Community(Community),
+    Membership(Membership),
+    ProgramState(ProgramState),
+    BlockRecord(BlockRecord),
+    FollowRecord(FollowRecord),
+    PostIndex(PostIndex),
+    Report(Report),
+    UsernameRegistry(UsernameRegistry),
+    CommentReceipt(CommentReceipt),
+    Bookmark(Bookmark),
+    MemberIndex(MemberIndex),
+    FollowerIndex(FollowerIndex),
+    FollowingIndex(FollowingIndex),
+    CommunityFeed(CommunityFeed),
+    MutePreferences(MutePreferences),
+    FollowRequest(FollowRequest),
+    Leaderboard(Leaderboard),
+    LikeRecord(LikeRecord),
+}
+
+// Decode account data into whichever struct its discriminator names
+pub fn decode_account(data: &[u8]) -> Result<DecodedAccount, ProgramError> {
+    let kind = *data.first().ok_or(ProgramError::InvalidAccountData)?;
+    match kind {
+        k if k == AccountKind::Profile as u8 => unpack_profile_from_slice(data).map(|profile| DecodedAccount::Profile(Box::new(profile))),
+        k if k == AccountKind::Post as u8 => unpack_post_from_slice(data).map(DecodedAccount::Post),
+        k if k == AccountKind::Community as u8 => unpack_community_from_slice(data).map(DecodedAccount::Community),
+        k if k == AccountKind::Membership as u8 => unpack_membership_from_slice(data).map(DecodedAccount::Membership),
+        k if k == AccountKind::ProgramState as u8 => unpack_program_state_from_slice(data).map(DecodedAccount::ProgramState),
+        k if k == AccountKind::BlockRecord as u8 => unpack_block_record_from_slice(data).map(DecodedAccount::BlockRecord),
+        k if k == AccountKind::FollowRecord as u8 => unpack_follow_record_from_slice(data).map(DecodedAccount::FollowRecord),
+        k if k == AccountKind::PostIndex as u8 => unpack_post_index_from_slice(data).map(DecodedAccount::PostIndex),
+        k if k == AccountKind::Report as u8 => unpack_report_from_slice(data).map(DecodedAccount::Report),
+        k if k == AccountKind::UsernameRegistry as u8 => unpack_username_registry_from_slice(data).map(DecodedAccount::UsernameRegistry),
+        k if k == AccountKind::CommentReceipt as u8 => unpack_comment_receipt_from_slice(data).map(DecodedAccount::CommentReceipt),
+        k if k == AccountKind::Bookmark as u8 => unpack_bookmark_from_slice(data).map(DecodedAccount::Bookmark),
+        k if k == AccountKind::MemberIndex as u8 => unpack_member_index_from_slice(data).map(DecodedAccount::MemberIndex),
+        k if k == AccountKind::FollowerIndex as u8 => unpack_paged_index_from_slice(data).map(DecodedAccount::FollowerIndex),
+        k if k == AccountKind::FollowingIndex as u8 => unpack_paged_index_from_slice(data).map(DecodedAccount::FollowingIndex),
+        k if k == AccountKind::CommunityFeed as u8 => unpack_community_feed_from_slice(data).map(DecodedAccount::CommunityFeed),
+        k if k == AccountKind::MutePreferences as u8 => unpack_mute_preferences_from_slice(data).map(DecodedAccount::MutePreferences),
+        k if k == AccountKind::FollowRequest as u8 => unpack_follow_request_from_slice(data).map(DecodedAccount::FollowRequest),
+        k if k == AccountKind::Leaderboard as u8 => unpack_leaderboard_from_slice(data).map(DecodedAccount::Leaderboard),
+        k if k == AccountKind::LikeRecord as u8 => unpack_like_record_from_slice(data).map(DecodedAccount::LikeRecord),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+impl DecodedAccount {
+    // Wallet entitled to manage the account
+    pub fn authority(&self) -> Pubkey {
+        match self {
+            DecodedAccount::Profile(profile) => profile.owner,
+            DecodedAccount::Post(post) => post.author,
+            DecodedAccount::Community(community) => community.owner,
+            DecodedAccount::Membership(membership) => membership.user,
+            DecodedAccount::ProgramState(state) => state.authority,
+            DecodedAccount::BlockRecord(record) => record.blocker,
+            DecodedAccount::FollowRecord(record) => record.follower,
+            DecodedAccount::PostIndex(index) => index.owner,
+            DecodedAccount::Report(report) => report.reporter,
+            DecodedAccount::UsernameRegistry(registry) => registry.owner,
+            DecodedAccount::CommentReceipt(receipt) => receipt.commenter,
+            DecodedAccount::Bookmark(bookmark) => bookmark.user,
+            DecodedAccount::MemberIndex(index) => index.community,
+            DecodedAccount::FollowerIndex(index) => index.owner,
+            DecodedAccount::FollowingIndex(index) => index.owner,
+            DecodedAccount::CommunityFeed(feed) => feed.community,
+            DecodedAccount::MutePreferences(preferences) => preferences.owner,
+            DecodedAccount::FollowRequest(request) => request.follower,
+            // Nobody owns the leaderboard; UpdateLeaderboard rewrites it under the current schema
+            DecodedAccount::Leaderboard(_) => Pubkey::default(),
+            DecodedAccount::LikeRecord(record) => record.liker,
+        }
+    }
+
+    // Account space needed to store the account under the current schema
+    pub fn required_space(&self) -> Result<usize, ProgramError> {
+        match self {
+            DecodedAccount::Profile(profile) => profile.required_space(),
+            DecodedAccount::Post(post) => post.required_space(),
+            DecodedAccount::Community(community) => community.required_space(),
+            DecodedAccount::Membership(membership) => membership.required_space(),
+            DecodedAccount::ProgramState(state) => state.required_space(),
+            DecodedAccount::BlockRecord(record) => record.required_space(),
+            DecodedAccount::FollowRecord(record) => record.required_space(),
+            DecodedAccount::PostIndex(_) => Ok(PostIndex::required_space()),
+            DecodedAccount::Report(report) => report.required_space(),
+            DecodedAccount::UsernameRegistry(registry) => registry.required_space(),
+            DecodedAccount::CommentReceipt(receipt) => receipt.required_space(),
+            DecodedAccount::Bookmark(bookmark) => bookmark.required_space(),
+            DecodedAccount::MemberIndex(_) => Ok(MemberIndex::required_space()),
+            DecodedAccount::FollowerIndex(_) => Ok(FollowerIndex::required_space()),
+            DecodedAccount::FollowingIndex(_) => Ok(FollowingIndex::required_space()),
+            DecodedAccount::CommunityFeed(_) => Ok(CommunityFeed::required_space()),
+            DecodedAccount::MutePreferences(_) => Ok(MutePreferences::required_space()),
+            DecodedAccount::FollowRequest(request) => request.required_space(),
+            DecodedAccount::Leaderboard(_) => Ok(Leaderboard::required_space()),
+            DecodedAccount::LikeRecord(record) => record.required_space(),
+        }
+    }
+}
+
+// Schema version an account was written with, if it has a header
+pub fn account_version(data: &[u8]) -> Option<u8> {
+    data.get(1).copied()
+}
+
+// Write any decoded account back under the current schema version
+pub fn pack_decoded_account(account: &DecodedAccount, dst: &mut [u8]) -> Result<(), ProgramError> {
+    match account {
+        DecodedAccount::Profile(profile) => pack_profile_into_slice(profile, dst),
+        DecodedAccount::Post(post) => pack_post_into_slice(post, dst),
+        DecodedAccount::Community(community) => pack_community_into_slice(community, dst),
+        DecodedAccount::Membership(membership) => pack_membership_into_slice(membership, dst),
+        DecodedAccount::ProgramState(state) => pack_program_state_into_slice(state, dst),
+        DecodedAccount::BlockRecord(record) => pack_block_record_into_slice(record, dst),
+        DecodedAccount::FollowRecord(record) => pack_follow_record_into_slice(record, dst),
+        DecodedAccount::PostIndex(index) => pack_post_index_into_slice(index, dst),
+        DecodedAccount::Report(report) => pack_report_into_slice(report, dst),
+        DecodedAccount::UsernameRegistry(registry) => pack_username_registry_into_slice(registry, dst),
+        DecodedAccount::CommentReceipt(receipt) => pack_comment_receipt_into_slice(receipt, dst),
+        DecodedAccount::Bookmark(bookmark) => pack_bookmark_into_slice(bookmark, dst),
+        DecodedAccount::MemberIndex(index) => pack_member_index_into_slice(index, dst),
+        DecodedAccount::FollowerIndex(index) => pack_paged_index_into_slice(index, dst),
+        DecodedAccount::FollowingIndex(index) => pack_paged_index_into_slice(index, dst),
+        DecodedAccount::CommunityFeed(feed) => pack_community_feed_into_slice(feed, dst),
+        DecodedAccount::MutePreferences(preferences) => pack_mute_preferences_into_slice(preferences, dst),
+        DecodedAccount::FollowRequest(request) => pack_follow_request_into_slice(request, dst),
+        DecodedAccount::Leaderboard(leaderboard) => pack_leaderboard_into_slice(leaderboard, dst),
+        DecodedAccount::LikeRecord(record) => pack_like_record_into_slice(record, dst),
+    }
+}
+
+// Helper functions
+fn pack_with_kind<T: BorshSerialize>(kind: AccountKind, value: &T, dst: &mut [u8]) -> Result<(), ProgramError> {
+    let data = value.try_to_vec()?;
+    if ACCOUNT_HEADER_LEN + data.len() > dst.len() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    dst[0] = kind as u8;
+    dst[1] = ACCOUNT_VERSION;
+    dst[ACCOUNT_HEADER_LEN..ACCOUNT_HEADER_LEN + data.len()].copy_from_slice(&data);
+    // Clear stale bytes so fields appended in later versions decode as defaults
+    dst[ACCOUNT_HEADER_LEN + data.len()..].fill(0);
+    Ok(())
+}
+
+// Empty or never-written data maps to `not_found`; anything else that fails to decode is corrupt
+fn unpack_with_kind<T: BorshDeserialize + IsInitialized + Versioned>(
+    kind: AccountKind,
+    src: &[u8],
+    not_found: ProgramError,
+) -> Result<T, ProgramError> {
+    let (&found, rest) = match src.split_first() {
+        Some(split) => split,
+        None => return Err(not_found),
+    };
+    if found == AccountKind::Uninitialized as u8 {
+        return Err(not_found);
+    }
+    if found != kind as u8 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let (&version, body) = rest.split_first().ok_or(ProgramError::InvalidAccountData)?;
+    if version == 0 || version > ACCOUNT_VERSION {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let value = if version == ACCOUNT_VERSION {
+        T::deserialize(&mut &body[..])
+    } else {
+        let mut padded = body.to_vec();
+        padded.resize(body.len() + UPGRADE_PADDING, 0);
+        T::deserialize(&mut padded.as_slice()).map(|mut value| {
+            value.upgrade(version);
+            value
+        })
+    };
+    let value = value.map_err(|_| ProgramError::InvalidAccountData)?;
+    if !value.is_initialized() {
+        return Err(not_found);
+    }
+    Ok(value)
+}
+
+pub fn pack_profile_into_slice(profile: &Profile, dst: &mut [u8]) -> Result<(), ProgramError> {
+    pack_with_kind(AccountKind::Profile, profile, dst)
+}
+
+pub fn unpack_profile_from_slice(src: &[u8]) -> Result<Profile, ProgramError> {
+    unpack_with_kind(AccountKind::Profile, src, BlocksError::ProfileNotFound.into())
+}
+
+pub fn pack_post_into_slice(post: &Post, dst: &mut [u8]) -> Result<(), ProgramError> {
+    pack_with_kind(AccountKind::Post, post, dst)
+}
+
+pub fn unpack_post_from_slice(src: &[u8]) -> Result<Post, ProgramError> {
+    unpack_with_kind(AccountKind::Post, src, BlocksError::PostNotFound.into())
+}
+
+pub fn pack_community_into_slice(community: &Community, dst: &mut [u8]) -> Result<(), ProgramError> {
+    pack_with_kind(AccountKind::Community, community, dst)
+}
 
 pub fn unpack_community_from_slice(src: &[u8]) -> Result<Community, ProgramError> {
-    try_from_slice_unchecked::<Community>(src).map_err(|_| ProgramError::InvalidAccountData)
+    unpack_with_kind(AccountKind::Community, src, BlocksError::CommunityNotFound.into())
+}
+
+// The unpack_live_* variants treat a soft-deleted record as missing; use them wherever
+// the record is acted on, and the plain variants where deleted records are still handled
+pub fn unpack_live_profile_from_slice(src: &[u8]) -> Result<Profile, ProgramError> {
+    let profile = unpack_profile_from_slice(src)?;
+    if profile.is_deleted {
+        return Err(BlocksError::ProfileNotFound.into());
+    }
+    Ok(profile)
+}
+
+pub fn unpack_live_post_from_slice(src: &[u8]) -> Result<Post, ProgramError> {
+    let post = unpack_post_from_slice(src)?;
+    if post.is_deleted {
+        return Err(BlocksError::PostNotFound.into());
+    }
+    Ok(post)
+}
+
+pub fn unpack_live_community_from_slice(src: &[u8]) -> Result<Community, ProgramError> {
+    let community = unpack_community_from_slice(src)?;
+    if community.is_deleted {
+        return Err(BlocksError::CommunityNotFound.into());
+    }
+    Ok(community)
+}
+
+pub fn pack_membership_into_slice(membership: &Membership, dst: &mut [u8]) -> Result<(), ProgramError> {
+    pack_with_kind(AccountKind::Membership, membership, dst)
+}
+
+pub fn unpack_membership_from_slice(src: &[u8]) -> Result<Membership, ProgramError> {
+    unpack_with_kind(AccountKind::Membership, src, ProgramError::UninitializedAccount)
+}
+
+pub fn pack_program_state_into_slice(state: &ProgramState, dst: &mut [u8]) -> Result<(), ProgramError> {
+    pack_with_kind(AccountKind::ProgramState, state, dst)
+}
+
+pub fn unpack_program_state_from_slice(src: &[u8]) -> Result<ProgramState, ProgramError> {
+    unpack_with_kind(AccountKind::ProgramState, src, ProgramError::UninitializedAccount)
+}
+
+pub fn pack_block_record_into_slice(record: &BlockRecord, dst: &mut [u8]) -> Result<(), ProgramError> {
+    pack_with_kind(AccountKind::BlockRecord, record, dst)
+}
+
+pub fn unpack_block_record_from_slice(src: &[u8]) -> Result<BlockRecord, ProgramError> {
+    unpack_with_kind(AccountKind::BlockRecord, src, ProgramError::UninitializedAccount)
+}
+
+pub fn pack_follow_record_into_slice(record: &FollowRecord, dst: &mut [u8]) -> Result<(), ProgramError> {
+    pack_with_kind(AccountKind::FollowRecord, record, dst)
+}
+
+pub fn unpack_follow_record_from_slice(src: &[u8]) -> Result<FollowRecord, ProgramError> {
+    unpack_with_kind(AccountKind::FollowRecord, src, ProgramError::UninitializedAccount)
+}
+
+pub fn pack_post_index_into_slice(index: &PostIndex, dst: &mut [u8]) -> Result<(), ProgramError> {
+    pack_with_kind(AccountKind::PostIndex, index, dst)
+}
+
+pub fn unpack_post_index_from_slice(src: &[u8]) -> Result<PostIndex, ProgramError> {
+    unpack_with_kind(AccountKind::PostIndex, src, ProgramError::UninitializedAccount)
+}
+
+pub fn pack_report_into_slice(report: &Report, dst: &mut [u8]) -> Result<(), ProgramError> {
+    pack_with_kind(AccountKind::Report, report, dst)
+}
+
+pub fn unpack_report_from_slice(src: &[u8]) -> Result<Report, ProgramError> {
+    unpack_with_kind(AccountKind::Report, src, ProgramError::UninitializedAccount)
+}
+
+pub fn pack_username_registry_into_slice(registry: &UsernameRegistry, dst: &mut [u8]) -> Result<(), ProgramError> {
+    pack_with_kind(AccountKind::UsernameRegistry, registry, dst)
+}
+
+pub fn unpack_username_registry_from_slice(src: &[u8]) -> Result<UsernameRegistry, ProgramError> {
+    unpack_with_kind(AccountKind::UsernameRegistry, src, ProgramError::UninitializedAccount)
+}
+
+pub fn pack_comment_receipt_into_slice(receipt: &CommentReceipt, dst: &mut [u8]) -> Result<(), ProgramError> {
+    pack_with_kind(AccountKind::CommentReceipt, receipt, dst)
+}
+
+pub fn unpack_comment_receipt_from_slice(src: &[u8]) -> Result<CommentReceipt, ProgramError> {
+    unpack_with_kind(AccountKind::CommentReceipt, src, ProgramError::UninitializedAccount)
+}
+
+pub fn pack_bookmark_into_slice(bookmark: &Bookmark, dst: &mut [u8]) -> Result<(), ProgramError> {
+    pack_with_kind(AccountKind::Bookmark, bookmark, dst)
+}
+
+pub fn unpack_bookmark_from_slice(src: &[u8]) -> Result<Bookmark, ProgramError> {
+    unpack_with_kind(AccountKind::Bookmark, src, ProgramError::UninitializedAccount)
+}
+
+pub fn pack_member_index_into_slice(index: &MemberIndex, dst: &mut [u8]) -> Result<(), ProgramError> {
+    pack_paged_index_into_slice(index, dst)
+}
+
+pub fn unpack_member_index_from_slice(src: &[u8]) -> Result<MemberIndex, ProgramError> {
+    unpack_paged_index_from_slice(src)
+}
+
+pub fn pack_paged_index_into_slice<T: PagedIndex>(index: &T, dst: &mut [u8]) -> Result<(), ProgramError> {
+    pack_with_kind(T::KIND, index, dst)
+}
+
+pub fn unpack_paged_index_from_slice<T: PagedIndex>(src: &[u8]) -> Result<T, ProgramError> {
+    unpack_with_kind(T::KIND, src, ProgramError::UninitializedAccount)
+}
+
+pub fn pack_community_feed_into_slice(feed: &CommunityFeed, dst: &mut [u8]) -> Result<(), ProgramError> {
+    pack_with_kind(AccountKind::CommunityFeed, feed, dst)
+}
+
+pub fn unpack_community_feed_from_slice(src: &[u8]) -> Result<CommunityFeed, ProgramError> {
+    unpack_with_kind(AccountKind::CommunityFeed, src, ProgramError::UninitializedAccount)
+}
+
+pub fn pack_mute_preferences_into_slice(preferences: &MutePreferences, dst: &mut [u8]) -> Result<(), ProgramError> {
+    pack_with_kind(AccountKind::MutePreferences, preferences, dst)
+}
+
+pub fn unpack_mute_preferences_from_slice(src: &[u8]) -> Result<MutePreferences, ProgramError> {
+    unpack_with_kind(AccountKind::MutePreferences, src, ProgramError::UninitializedAccount)
+}
+
+pub fn pack_follow_request_into_slice(request: &FollowRequest, dst: &mut [u8]) -> Result<(), ProgramError> {
+    pack_with_kind(AccountKind::FollowRequest, request, dst)
+}
+
+pub fn unpack_follow_request_from_slice(src: &[u8]) -> Result<FollowRequest, ProgramError> {
+    unpack_with_kind(AccountKind::FollowRequest, src, BlocksError::FollowRequestNotFound.into())
+}
+
+pub fn pack_leaderboard_into_slice(leaderboard: &Leaderboard, dst: &mut [u8]) -> Result<(), ProgramError> {
+    pack_with_kind(AccountKind::Leaderboard, leaderboard, dst)
+}
+
+pub fn unpack_leaderboard_from_slice(src: &[u8]) -> Result<Leaderboard, ProgramError> {
+    unpack_with_kind(AccountKind::Leaderboard, src, ProgramError::UninitializedAccount)
+}
+
+pub fn pack_like_record_into_slice(record: &LikeRecord, dst: &mut [u8]) -> Result<(), ProgramError> {
+    pack_with_kind(AccountKind::LikeRecord, record, dst)
+}
+
+pub fn unpack_like_record_from_slice(src: &[u8]) -> Result<LikeRecord, ProgramError> {
+    unpack_with_kind(AccountKind::LikeRecord, src, ProgramError::UninitializedAccount)
 }
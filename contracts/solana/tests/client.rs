@@ -0,0 +1,165 @@
+//! Batch decoding of fetched accounts through `client::decode_profiles`, and feed paging
+//! through `client::feed_page`.
+
+use blocks_contracts::{
+    client::{decode_profiles, feed_page, FeedCursor},
+    state::{pack_profile_into_slice, Post, PostRating, Profile, UcrTier, VerificationLevel},
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+fn profile_data(owner: Pubkey, username: &str) -> Vec<u8> {
+    let profile = Profile {
+        is_initialized: true,
+        owner,
+        username: username.to_string(),
+        bio: String::new(),
+        profile_image: String::new(),
+        cover_image: String::new(),
+        created_at: 0,
+        followers_count: 0,
+        following_count: 0,
+        user_credit_rating: 100,
+        posts_count: 0,
+        last_post_timestamp: 0,
+        daily_post_count: 0,
+        legacy_verified: false,
+        ucr_tier: UcrTier::from_score(100),
+        last_decay_timestamp: 0,
+        is_spam: false,
+        profile_image_hash: None,
+        cover_image_hash: None,
+        pinned_post: None,
+        follower_index_pages: 0,
+        following_index_pages: 0,
+        total_likes_received: 0,
+        total_comments_received: 0,
+        last_post_id: 0,
+        is_deleted: false,
+        deleted_at: 0,
+        milestone_awarded: false,
+        is_private: false,
+        updated_at: 0,
+        recent_content: Default::default(),
+        verification_level: VerificationLevel::None,
+    };
+    let mut data = vec![0; profile.required_space().unwrap()];
+    pack_profile_into_slice(&profile, &mut data).unwrap();
+    data
+}
+
+#[test]
+fn decode_profiles_pairs_results_and_skips_uninitialized() {
+    let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (closed, missing, corrupt) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let mut corrupt_data = profile_data(Pubkey::new_unique(), "carol");
+    corrupt_data.truncate(8);
+
+    let decoded = decode_profiles(&[
+        (alice, profile_data(alice, "alice")),
+        (closed, vec![0; 64]),
+        (missing, vec![]),
+        (corrupt, corrupt_data),
+        (bob, profile_data(bob, "bob")),
+    ]);
+
+    let addresses: Vec<Pubkey> = decoded.iter().map(|(address, _)| *address).collect();
+    assert_eq!(addresses, vec![alice, corrupt, bob]);
+    assert_eq!(decoded[0].1.as_ref().unwrap().username, "alice");
+    assert_eq!(decoded[1].1.as_ref().err(), Some(&ProgramError::InvalidAccountData));
+    assert_eq!(decoded[2].1.as_ref().unwrap().owner, bob);
+}
+
+#[test]
+fn decode_profiles_rejects_other_account_kinds() {
+    let address = Pubkey::new_unique();
+    // A post's discriminator in front of a profile body
+    let mut data = profile_data(address, "alice");
+    data[0] = 2;
+
+    let decoded = decode_profiles(&[(address, data)]);
+
+    assert_eq!(decoded.len(), 1);
+    assert_eq!(decoded[0].1.as_ref().err(), Some(&ProgramError::InvalidAccountData));
+}
+
+fn feed_post(id: u64, timestamp: u64) -> (Pubkey, Post) {
+    let address = Pubkey::new_unique();
+    let post = Post {
+        is_initialized: true,
+        id,
+        author: Pubkey::new_unique(),
+        content: format!("post {id}"),
+        timestamp,
+        likes: 0,
+        comments: 0,
+        mirrors: 0,
+        images: vec![],
+        rating: PostRating::None,
+        in_kill_zone: false,
+        edited_at: 0,
+        community: None,
+        hidden: false,
+        total_tips: 0,
+        quoted_post: None,
+        report_count: 0,
+        content_hash: None,
+        parent: None,
+        thread_root: address,
+        depth: 0,
+        is_deleted: false,
+        deleted_at: 0,
+        ucr_window_start: timestamp,
+        ucr_window_earned: 0,
+        allow_comments: true,
+        earned_milestone: false,
+    };
+    (address, post)
+}
+
+fn page_ids(posts: &[&(Pubkey, Post)]) -> Vec<u64> {
+    posts.iter().map(|(_, post)| post.id).collect()
+}
+
+#[test]
+fn feed_cursor_pages_newest_first_despite_new_posts() {
+    // Two posts share a timestamp, so the id breaks the tie
+    let mut feed: Vec<(Pubkey, Post)> = [(1, 100), (2, 200), (3, 200), (4, 300), (5, 400)]
+        .into_iter()
+        .map(|(id, timestamp)| feed_post(id, timestamp))
+        .collect();
+
+    let first = feed_page(&feed, None, 2);
+    assert_eq!(page_ids(&first.posts), vec![5, 4]);
+    let cursor = first.next.unwrap();
+    assert_eq!(cursor, FeedCursor { last_post_id: 4, last_timestamp: 300 });
+
+    // A post published between requests lands above the cursor and doesn't shift the next page
+    feed.push(feed_post(6, 500));
+    let second = feed_page(&feed, Some(cursor), 2);
+    assert_eq!(page_ids(&second.posts), vec![3, 2]);
+
+    let last = feed_page(&feed, second.next, 2);
+    assert_eq!(page_ids(&last.posts), vec![1]);
+    assert_eq!(last.next, None);
+}
+
+#[test]
+fn feed_cursor_handles_empty_and_exhausted_feeds() {
+    assert!(feed_page(&[], None, 10).posts.is_empty());
+    assert_eq!(feed_page(&[], None, 10).next, None);
+
+    // A page that ends exactly on the oldest post reports the feed as exhausted
+    let mut feed = vec![feed_post(1, 100), feed_post(2, 200)];
+    assert_eq!(feed_page(&feed, None, 2).next, None);
+
+    // Paging past the end yields nothing, and deleted posts never appear
+    feed[1].1.is_deleted = true;
+    let cursor = FeedCursor { last_post_id: 1, last_timestamp: 100 };
+    assert!(feed_page(&feed, Some(cursor), 2).posts.is_empty());
+    assert_eq!(page_ids(&feed_page(&feed, None, 2).posts), vec![1]);
+
+    // Cursors round-trip through borsh so clients can hand them back to a later request
+    let encoded = cursor.try_to_vec().unwrap();
+    assert_eq!(FeedCursor::try_from_slice(&encoded).unwrap(), cursor);
+}
@@ -0,0 +1,117 @@
+//! Checks that `Processor::process` routes every `ContractInstruction` to a handler, and that
+//! every variant keeps the Borsh tag deployed clients already send.
+//!
+//! `dispatch_cases!` takes one sample per variant, keyed by its fixed tag. It also expands to an
+//! exhaustive match, so a variant added to the enum without a sample here fails to compile.
+//! Tags are never renumbered: a new variant is appended to the enum and listed here with the next tag.
+
+use blocks_contracts::{instruction::ContractInstruction, processor::Processor, state::{CommunityPostRules, RatingConfig, VerificationLevel}};
+use borsh::BorshSerialize;
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+macro_rules! dispatch_cases {
+    ($($tag:literal => $variant:ident $({ $($field:ident: $value:expr),* $(,)? })?),* $(,)?) => {
+        // Exhaustive on purpose: every variant needs a sample below
+        fn assert_listed(instruction: &ContractInstruction) {
+            match instruction {
+                $(ContractInstruction::$variant { .. } => {})*
+            }
+        }
+
+        fn cases() -> Vec<(u8, ContractInstruction)> {
+            vec![$(($tag, ContractInstruction::$variant $({ $($field: $value),* })?)),*]
+        }
+    };
+}
+
+dispatch_cases! {
+    0 => CreateProfile {
+        username: "alice".to_string(),
+        bio: String::new(),
+        profile_image: String::new(),
+        cover_image: String::new(),
+        profile_image_hash: None,
+        cover_image_hash: None,
+    },
+    1 => UpdateProfile { bio: String::new(), profile_image: String::new(), cover_image: String::new() },
+    2 => CreatePost { content: "gm".to_string(), images: vec![], content_hash: None, allow_comments: true },
+    3 => LikePost { post_id: 1 },
+    4 => CommentOnPost { content: "gm".to_string(), parent_id: 1, images: vec![] },
+    5 => FollowProfile { profile_id: Pubkey::new_unique() },
+    6 => UnfollowProfile { profile_id: Pubkey::new_unique() },
+    7 => CreateCommunity { name: "sb/rust".to_string(), description: String::new(), avatar: String::new(), rules: vec![] },
+    8 => JoinCommunity { community_id: 1 },
+    9 => LeaveCommunity { community_id: 1 },
+    10 => VerifyProfile,
+    11 => DeletePost { post_id: 1, force: false },
+    12 => DeleteProfile,
+    13 => EditPost { post_id: 1, content: "gm".to_string(), images: vec![] },
+    14 => BlockUser { target: Pubkey::new_unique() },
+    15 => UnblockUser { target: Pubkey::new_unique() },
+    16 => AddModerator { target: Pubkey::new_unique() },
+    17 => ModeratePost { post_id: 1 },
+    18 => TransferCommunityOwnership { new_owner: Pubkey::new_unique() },
+    19 => TipPost { post_id: 1, amount: 1 },
+    20 => DecayUcr,
+    21 => QuotePost { post_id: 1, content: "gm".to_string() },
+    22 => ReportPost { post_id: 1, reason: 0 },
+    23 => ChangeUsername { new_username: "bob".to_string() },
+    24 => LikePosts { post_ids: vec![1] },
+    25 => SetRatingConfig { config: RatingConfig::default() },
+    26 => InitializeProgram,
+    27 => CreatePostInCommunity { content: "gm".to_string(), images: vec![], community_id: 1, content_hash: None },
+    28 => PinPost { post_id: 1 },
+    29 => UnpinPost,
+    30 => PinCommunityPost { post_id: 1 },
+    31 => UnpinCommunityPost,
+    32 => BookmarkPost { post_id: 1 },
+    33 => RemoveBookmark,
+    34 => MigrateAccount,
+    35 => SetVerification { target: Pubkey::new_unique(), verified: true },
+    36 => ReconcileState { profiles: 0, posts: 0, communities: 0 },
+    37 => DeleteComment { comment_id: 1, parent_id: 1, force: false },
+    38 => AddMutedWord { word: "spoilers".to_string() },
+    39 => RemoveMutedWord { word: "spoilers".to_string() },
+    40 => SoftDeleteAccount,
+    41 => SetCommunityPostRules { rules: CommunityPostRules::default() },
+    42 => SetProfilePrivacy { is_private: true },
+    43 => ApproveFollow { follower: Pubkey::new_unique() },
+    44 => RejectFollow { follower: Pubkey::new_unique() },
+    45 => SetCommunityMaxMembers { max_members: 10 },
+    46 => UpdateCommunity { description: None, avatar: None, rules: None },
+    47 => SetVerificationLevel { target: Pubkey::new_unique(), level: VerificationLevel::Notable },
+    48 => CreateCommunityWithMembers {
+        name: "sb/rust".to_string(),
+        description: String::new(),
+        avatar: String::new(),
+        rules: vec![],
+        members: vec![Pubkey::new_unique()],
+    },
+    49 => UpdateLeaderboard,
+}
+
+// Every handler checks its account count first, so reaching one without accounts yields
+// NotEnoughAccountKeys rather than the InvalidInstructionData of an undecodable instruction
+#[test]
+fn every_instruction_reaches_a_handler() {
+    let program_id = Pubkey::new_unique();
+    for (_, instruction) in cases() {
+        assert_listed(&instruction);
+        let data = instruction.try_to_vec().unwrap();
+        assert_eq!(
+            Processor::process(&program_id, &[], &data),
+            Err(ProgramError::NotEnoughAccountKeys),
+            "{instruction:?}"
+        );
+    }
+}
+
+// The leading byte is the variant's Borsh tag; moving a variant would silently retarget every
+// deployed client that sends it
+#[test]
+fn instruction_tags_are_stable() {
+    for (tag, instruction) in cases() {
+        let data = instruction.try_to_vec().unwrap();
+        assert_eq!(data[0], tag, "{instruction:?}");
+    }
+}
@@ -0,0 +1,67 @@
+//! Pins every `BlocksError` to its on-chain code so a reordered or renumbered variant fails CI.
+
+use blocks_contracts::error::BlocksError;
+use solana_program::program_error::ProgramError;
+
+const CODES: &[(BlocksError, u32)] = &[
+    (BlocksError::InvalidInstruction, 0),
+    (BlocksError::NotRentExempt, 1),
+    (BlocksError::ProfileAlreadyExists, 2),
+    (BlocksError::ProfileNotFound, 3),
+    (BlocksError::PostNotFound, 4),
+    (BlocksError::CommunityNotFound, 5),
+    (BlocksError::NotProfileOwner, 6),
+    (BlocksError::NotPostOwner, 7),
+    (BlocksError::NotCommunityOwner, 8),
+    (BlocksError::InvalidCommunityName, 9),
+    (BlocksError::AlreadyMember, 10),
+    (BlocksError::CommunityLimitExceeded, 11),
+    (BlocksError::DailyPostLimitReached, 12),
+    (BlocksError::PostTimeLimit, 13),
+    (BlocksError::SpamUser, 14),
+    (BlocksError::AlreadyLiked, 15),
+    (BlocksError::AlreadyDisliked, 16),
+    (BlocksError::PostInKillZone, 17),
+    (BlocksError::InsufficientFunds, 18),
+    (BlocksError::VerificationThresholdNotMet, 19),
+    (BlocksError::ProfileNotEmpty, 20),
+    (BlocksError::ContentTooLong, 21),
+    (BlocksError::UserBlocked, 22),
+    (BlocksError::AlreadyFollowing, 23),
+    (BlocksError::NotFollowing, 24),
+    (BlocksError::ArithmeticOverflow, 25),
+    (BlocksError::AlreadyReported, 26),
+    (BlocksError::FieldTooLong, 27),
+    (BlocksError::CommentLimitReached, 28),
+    (BlocksError::BatchTooLarge, 29),
+    (BlocksError::Unauthorized, 30),
+    (BlocksError::InvalidImageUri, 31),
+    (BlocksError::NotAMember, 32),
+    (BlocksError::AlreadyBookmarked, 33),
+    (BlocksError::BookmarkNotFound, 34),
+    (BlocksError::MemberIndexPageFull, 35),
+    (BlocksError::FollowIndexPageFull, 36),
+    (BlocksError::EditWindowExpired, 37),
+    (BlocksError::ReplyDepthExceeded, 38),
+    (BlocksError::AccountNotWritable, 39),
+    (BlocksError::MutedWordsFull, 40),
+    (BlocksError::AlreadyMuted, 41),
+    (BlocksError::MutedWordNotFound, 42),
+    (BlocksError::AccountStillReferenced, 43),
+    (BlocksError::AlreadyDeleted, 44),
+    (BlocksError::CommentsDisabled, 45),
+    (BlocksError::UcrBelowCommunityMinimum, 46),
+    (BlocksError::VerificationRequired, 47),
+    (BlocksError::FollowRequestPending, 48),
+    (BlocksError::FollowRequestNotFound, 49),
+    (BlocksError::CommunityAlreadyExists, 50),
+    (BlocksError::DuplicateContent, 51),
+    (BlocksError::NestedQuote, 52),
+];
+
+#[test]
+fn error_codes_are_stable() {
+    for &(error, code) in CODES {
+        assert_eq!(ProgramError::from(error), ProgramError::Custom(code), "{error:?}");
+    }
+}
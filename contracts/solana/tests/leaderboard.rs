@@ -0,0 +1,47 @@
+//! Property tests for how `Leaderboard::record` keeps the highest-UCR profiles in order.
+
+use blocks_contracts::state::{Leaderboard, LEADERBOARD_LEN};
+use proptest::prelude::*;
+use solana_program::pubkey::Pubkey;
+
+const PROFILES: usize = 2 * LEADERBOARD_LEN;
+
+proptest! {
+    // With scores that only rise, the board always matches a full re-sort of every profile
+    #[test]
+    fn board_holds_the_top_scores_in_order(raises in prop::collection::vec((0..PROFILES, 1..1_000i64), 1..100)) {
+        let profiles: Vec<Pubkey> = (0..PROFILES).map(|_| Pubkey::new_unique()).collect();
+        let mut scores = [0i64; PROFILES];
+        let mut leaderboard = Leaderboard { is_initialized: true, entries: Vec::new() };
+        for (index, raise) in raises {
+            scores[index] += raise;
+            let rank = leaderboard.record(profiles[index], scores[index]);
+            let listed = leaderboard.entries.iter().position(|entry| entry.profile == profiles[index]);
+            prop_assert_eq!(rank, listed);
+        }
+
+        let mut expected: Vec<i64> = scores.iter().copied().filter(|score| *score > 0).collect();
+        expected.sort_unstable_by(|a, b| b.cmp(a));
+        expected.truncate(LEADERBOARD_LEN);
+        let listed: Vec<i64> = leaderboard.entries.iter().map(|entry| entry.user_credit_rating).collect();
+        prop_assert_eq!(listed, expected);
+        for entry in &leaderboard.entries {
+            let index = profiles.iter().position(|profile| *profile == entry.profile).unwrap();
+            prop_assert_eq!(entry.user_credit_rating, scores[index]);
+        }
+    }
+}
+
+#[test]
+fn rerecording_a_profile_moves_it_instead_of_duplicating_it() {
+    let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut leaderboard = Leaderboard { is_initialized: true, entries: Vec::new() };
+    assert_eq!(leaderboard.record(first, 500), Some(0));
+    assert_eq!(leaderboard.record(second, 300), Some(1));
+    assert_eq!(leaderboard.record(second, 700), Some(0));
+
+    let order: Vec<Pubkey> = leaderboard.entries.iter().map(|entry| entry.profile).collect();
+    assert_eq!(order, vec![second, first]);
+    assert!(leaderboard.remove(&second));
+    assert_eq!(leaderboard.entries.len(), 1);
+}
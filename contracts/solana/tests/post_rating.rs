@@ -0,0 +1,98 @@
+//! Property tests for the like thresholds behind `PostRating::from_likes`.
+
+use blocks_contracts::state::PostRating;
+use proptest::prelude::*;
+
+// Ratings from lowest to highest
+const LADDER: [PostRating; 8] = [
+    PostRating::None,
+    PostRating::Bronze,
+    PostRating::Silver,
+    PostRating::Gold,
+    PostRating::Platinum,
+    PostRating::Diamond,
+    PostRating::Ace,
+    PostRating::Conqueror,
+];
+
+fn rank(rating: PostRating) -> usize {
+    LADDER.iter().position(|r| *r == rating).unwrap()
+}
+
+#[test]
+fn boundaries_match_the_default_thresholds() {
+    let cases = [
+        (0, PostRating::None),
+        (4, PostRating::None),
+        (5, PostRating::Bronze),
+        (19, PostRating::Bronze),
+        (20, PostRating::Silver),
+        (49, PostRating::Silver),
+        (50, PostRating::Gold),
+        (149, PostRating::Gold),
+        (150, PostRating::Platinum),
+        (499, PostRating::Platinum),
+        (500, PostRating::Diamond),
+        (999, PostRating::Diamond),
+        (1_000, PostRating::Ace),
+        (999_999, PostRating::Ace),
+        (1_000_000, PostRating::Conqueror),
+    ];
+    for (likes, expected) in cases {
+        assert_eq!(PostRating::from_likes(likes), expected, "{} likes", likes);
+    }
+}
+
+#[test]
+fn max_likes_is_conqueror() {
+    assert_eq!(PostRating::from_likes(u64::MAX), PostRating::Conqueror);
+}
+
+proptest! {
+    #[test]
+    fn more_likes_never_lowers_the_rating(a in any::<u64>(), b in any::<u64>()) {
+        let (low, high) = if a <= b { (a, b) } else { (b, a) };
+        prop_assert!(rank(PostRating::from_likes(low)) <= rank(PostRating::from_likes(high)));
+    }
+
+    #[test]
+    fn one_more_like_moves_at_most_one_step(likes in 0u64..2_000_000) {
+        let before = rank(PostRating::from_likes(likes));
+        let after = rank(PostRating::from_likes(likes + 1));
+        prop_assert!(after == before || after == before + 1);
+    }
+}
+
+#[test]
+fn names_round_trip_through_from_str() {
+    for rating in LADDER {
+        assert_eq!(rating.to_string().parse::<PostRating>(), Ok(rating));
+        assert_eq!(format!("{}", rating), rating.to_string());
+    }
+    assert_eq!("gold".parse::<PostRating>(), Ok(PostRating::Gold));
+}
+
+#[test]
+fn unknown_names_fail_to_parse() {
+    assert!("legendary".parse::<PostRating>().is_err());
+    assert!("Gold".parse::<PostRating>().is_err());
+    assert!("".parse::<PostRating>().is_err());
+}
+
+#[test]
+fn level_matches_ladder_position() {
+    for (position, rating) in LADDER.iter().enumerate() {
+        assert_eq!(rating.level() as usize, position);
+    }
+}
+
+#[test]
+fn ratings_order_by_prestige() {
+    assert!(PostRating::Bronze < PostRating::Gold);
+    assert!(PostRating::Gold < PostRating::Conqueror);
+    assert!(LADDER.windows(2).all(|pair| pair[0] < pair[1]));
+
+    let mut shuffled = [PostRating::Ace, PostRating::None, PostRating::Diamond, PostRating::Silver];
+    shuffled.sort();
+    assert_eq!(shuffled, [PostRating::None, PostRating::Silver, PostRating::Diamond, PostRating::Ace]);
+}
@@ -0,0 +1,3490 @@
+//! End-to-end tests driving the processor through `solana-program-test`.
+
+use blocks_contracts::{
+    error::BlocksError,
+    event::Event,
+    instruction::ContractInstruction,
+    instruction_builders::{
+        comment_on_post_ix, create_community_ix, delete_profile_ix, delete_comment_ix, delete_post_ix, edit_post_ix, create_post_ix, create_profile_ix, follow_profile_ix, unfollow_profile_ix, verify_profile_ix,
+        create_post_in_community_ix, initialize_program_ix, join_community_ix, leave_community_ix, like_post_ix, like_posts_ix, quote_post_ix, reconcile_state_ix, report_post_ix,
+        tip_post_ix, block_user_ix, unblock_user_ix, add_muted_word_ix, remove_muted_word_ix, soft_delete_account_ix, set_community_post_rules_ix,
+        set_profile_privacy_ix, approve_follow_ix, reject_follow_ix, set_community_max_members_ix,
+        update_community_ix, set_verification_level_ix, set_verification_ix, create_community_with_members_ix,
+        update_leaderboard_ix, set_rating_config_ix, add_moderator_ix, moderate_post_ix, transfer_community_ownership_ix, decay_ucr_ix, change_username_ix,
+        pin_post_ix, unpin_post_ix, pin_community_post_ix, unpin_community_post_ix, bookmark_post_ix, remove_bookmark_ix, migrate_account_ix, update_profile_ix,
+    },
+    pda::{bookmark_pda, comment_receipt_pda, community_pda, leaderboard_pda, like_record_pda, post_index_pda, follow_record_pda, username_registry_pda, follow_request_pda, community_feed_pda, mute_preferences_pda, post_pda, follower_index_pda, following_index_pda, member_index_pda, membership_pda, profile_pda, program_state_pda},
+    processor::Processor,
+    state::{
+        decode_account, pack_member_index_into_slice, pack_post_into_slice, profile_stats, AccountKind, ACCOUNT_VERSION, pack_profile_into_slice, unpack_bookmark_from_slice, unpack_comment_receipt_from_slice, unpack_community_feed_from_slice, unpack_community_from_slice, unpack_follow_record_from_slice, unpack_leaderboard_from_slice, unpack_like_record_from_slice, unpack_member_index_from_slice, unpack_membership_from_slice,
+        unpack_paged_index_from_slice, unpack_post_from_slice, unpack_post_index_from_slice, unpack_profile_from_slice,
+        unpack_program_state_from_slice, Community, DecodedAccount, FollowerIndex, FollowingIndex, Post, Profile, Role, KILL_ZONE_REPORT_THRESHOLD, REPORT_HIDE_THRESHOLD,
+        DAILY_POSTS_VALUABLE_CONTRIBUTOR, EDIT_WINDOW_SECONDS, EXTENDED_EDIT_WINDOW_SECONDS, MAX_BIO_LEN, MAX_POST_IMAGES, MAX_COMMENTS_PER_USER_PER_POST, MAX_MEMBER_INDEX_LEN, MAX_POST_INDEX_LEN, MAX_REPLY_DEPTH, MAX_USERNAME_LEN, PostIndex, TIP_LAMPORTS_PER_UCR, UCR_AVERAGE_CONTRIBUTOR, UCR_LOW_VALUE_CONTRIBUTOR, UCR_VALUABLE_CONTRIBUTOR, UcrTier, UCR_PER_COMMENT,
+        UCR_PER_LIKE, UCR_PER_MIRROR, UCR_PER_TIP, unpack_mute_preferences_from_slice, MAX_MUTED_WORDS,
+        DAILY_POST_UCR_CAP, MAX_POST_CONTENT_LEN, MAX_URI_LEN, LIKE_WEIGHT_AVERAGE_CONTRIBUTOR, LIKE_WEIGHT_SPAM_USER, LIKE_WEIGHT_TOP_CONTRIBUTOR, UCR_TOP_CONTRIBUTOR, CONQUEROR_MILESTONE_UCR_BONUS, PostRating, RatingConfig,
+        CommunityPostRules, MAX_COMMUNITY_RULES, UCR_DECAY_FLOOR, UCR_DECAY_PER_DAY, UCR_MAX, UCR_MIN, UCR_SPAM_USER, DUPLICATE_CONTENT_WINDOW, VerificationLevel, VERIFICATION_MIN_POSTS, MAX_INITIAL_MEMBERS,
+    },
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program_test::{processor, tokio, ProgramTest, ProgramTestBanksClientExt, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    instruction::{Instruction, InstructionError},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+
+const USER_LAMPORTS: u64 = 10_000_000_000;
+
+async fn start() -> (ProgramTestContext, Pubkey) {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new("blocks_contracts", program_id, processor!(Processor::process));
+    let mut context = program_test.start_with_context().await;
+    let authority = context.payer.pubkey();
+    send(&mut context, &[initialize_program_ix(&program_id, &authority)], &[]).await;
+    (context, program_id)
+}
+
+async fn try_send(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), solana_program_test::BanksClientError> {
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction =
+        Transaction::new_signed_with_payer(instructions, Some(&context.payer.pubkey()), &all_signers, blockhash);
+    context.banks_client.process_transaction(transaction).await
+}
+
+// Wait for a blockhash the bank hasn't handed out yet, so a resent transaction gets a new signature
+async fn refresh_blockhash(context: &mut ProgramTestContext) {
+    let latest = context.banks_client.get_latest_blockhash().await.unwrap();
+    context.last_blockhash = context.banks_client.get_new_latest_blockhash(&latest).await.unwrap();
+}
+
+fn assert_custom_error(result: Result<(), solana_program_test::BanksClientError>, error: BlocksError) {
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(error as u32))
+    );
+}
+
+async fn send(context: &mut ProgramTestContext, instructions: &[Instruction], signers: &[&Keypair]) {
+    try_send(context, instructions, signers).await.unwrap();
+}
+
+async fn account_data(context: &mut ProgramTestContext, address: &Pubkey) -> Vec<u8> {
+    context.banks_client.get_account(*address).await.unwrap().expect("account exists").data
+}
+
+async fn funded_user(context: &mut ProgramTestContext) -> Keypair {
+    let user = Keypair::new();
+    let payer = context.payer.pubkey();
+    send(context, &[system_instruction::transfer(&payer, &user.pubkey(), USER_LAMPORTS)], &[]).await;
+    user
+}
+
+async fn create_profile(context: &mut ProgramTestContext, program_id: &Pubkey, user: &Keypair, username: &str) -> Pubkey {
+    let ix = create_profile_ix(
+        program_id,
+        &user.pubkey(),
+        username.to_string(),
+        "gm".to_string(),
+        String::new(),
+        String::new(),
+        None,
+        None,
+    );
+    send(context, &[ix], &[user]).await;
+    profile_pda(program_id, &user.pubkey(), username).0
+}
+
+async fn create_post(
+    context: &mut ProgramTestContext,
+    program_id: &Pubkey,
+    user: &Keypair,
+    profile: &Pubkey,
+    content: &str,
+) -> Pubkey {
+    create_post_with_comments(context, program_id, user, profile, content, true).await
+}
+
+async fn create_post_with_comments(
+    context: &mut ProgramTestContext,
+    program_id: &Pubkey,
+    user: &Keypair,
+    profile: &Pubkey,
+    content: &str,
+    allow_comments: bool,
+) -> Pubkey {
+    let author = unpack_profile_from_slice(&account_data(context, profile).await).unwrap();
+    let post_id = author.last_post_id + 1;
+    let ix = create_post_ix(
+        program_id,
+        &user.pubkey(),
+        post_id,
+        profile,
+        &author.username,
+        content.to_string(),
+        vec![],
+        None,
+        allow_comments,
+    );
+    send(context, &[ix], &[user]).await;
+    post_pda(program_id, &user.pubkey(), post_id).0
+}
+
+// Id the next post, comment or quote by the profile's owner will receive
+async fn next_post_id(context: &mut ProgramTestContext, profile: &Pubkey) -> u64 {
+    unpack_profile_from_slice(&account_data(context, profile).await).unwrap().last_post_id + 1
+}
+
+// Comment on `parent` (a top-level post by `parent_author`), returning the comment's address
+#[allow(clippy::too_many_arguments)]
+async fn try_comment(
+    context: &mut ProgramTestContext,
+    program_id: &Pubkey,
+    commenter: &Keypair,
+    commenter_profile: &Pubkey,
+    parent: &Pubkey,
+    parent_id: u64,
+    parent_author: &Pubkey,
+    parent_author_profile: &Pubkey,
+    images: Vec<String>,
+) -> Result<Pubkey, solana_program_test::BanksClientError> {
+    let comment_id = next_post_id(context, commenter_profile).await;
+    let parent_author_username = profile(context, parent_author_profile).await.username;
+    let ix = comment_on_post_ix(
+        program_id,
+        &commenter.pubkey(),
+        comment_id,
+        parent,
+        parent_author,
+        parent_author_profile,
+        &parent_author_username,
+        None,
+        commenter_profile,
+        "nice".to_string(),
+        parent_id,
+        images,
+    );
+    try_send(context, &[ix], &[commenter]).await?;
+    Ok(post_pda(program_id, &commenter.pubkey(), comment_id).0)
+}
+
+async fn profile(context: &mut ProgramTestContext, address: &Pubkey) -> Profile {
+    unpack_profile_from_slice(&account_data(context, address).await).unwrap()
+}
+
+// Overwrite a profile's UCR directly in the bank, bypassing the instructions that earn it
+async fn set_ucr(context: &mut ProgramTestContext, address: &Pubkey, user_credit_rating: i64) {
+    let mut account = context.banks_client.get_account(*address).await.unwrap().unwrap();
+    let mut profile = unpack_profile_from_slice(&account.data).unwrap();
+    profile.user_credit_rating = user_credit_rating;
+    profile.refresh_ucr_status();
+    pack_profile_into_slice(&profile, &mut account.data).unwrap();
+    context.set_account(address, &account.into());
+}
+
+// Rewrite a profile directly in the bank, bypassing the instructions that maintain it
+async fn edit_profile(context: &mut ProgramTestContext, address: &Pubkey, edit: impl FnOnce(&mut Profile)) {
+    let mut account = context.banks_client.get_account(*address).await.unwrap().unwrap();
+    let mut profile = unpack_profile_from_slice(&account.data).unwrap();
+    edit(&mut profile);
+    pack_profile_into_slice(&profile, &mut account.data).unwrap();
+    context.set_account(address, &account.into());
+}
+
+async fn post(context: &mut ProgramTestContext, address: &Pubkey) -> Post {
+    unpack_post_from_slice(&account_data(context, address).await).unwrap()
+}
+
+// Rewrite a post directly in the bank, bypassing the instructions that maintain it
+async fn edit_post(context: &mut ProgramTestContext, address: &Pubkey, edit: impl FnOnce(&mut Post)) {
+    let mut account = context.banks_client.get_account(*address).await.unwrap().unwrap();
+    let mut post = unpack_post_from_slice(&account.data).unwrap();
+    edit(&mut post);
+    pack_post_into_slice(&post, &mut account.data).unwrap();
+    context.set_account(address, &account.into());
+}
+
+// Move the cluster clock forward so timestamps written afterwards are distinguishable
+async fn advance_clock(context: &mut ProgramTestContext, seconds: i64) -> u64 {
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp += seconds;
+    context.set_sysvar(&clock);
+    clock.unix_timestamp as u64
+}
+
+async fn community(context: &mut ProgramTestContext, address: &Pubkey) -> Community {
+    unpack_community_from_slice(&account_data(context, address).await).unwrap()
+}
+
+// Create a community owned by `owner`, returning its address and id
+async fn create_community(context: &mut ProgramTestContext, program_id: &Pubkey, owner: &Keypair, name: &str) -> (Pubkey, u64) {
+    let ix = create_community_ix(program_id, &owner.pubkey(), name.to_string(), String::new(), String::new(), vec![]);
+    send(context, &[ix], &[owner]).await;
+    let address = community_pda(program_id, name).0;
+    let id = community(context, &address).await.id;
+    (address, id)
+}
+
+#[tokio::test]
+async fn create_profile_registers_account_and_counts() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+
+    let address = create_profile(&mut context, &program_id, &alice, "alice").await;
+
+    let profile = profile(&mut context, &address).await;
+    assert!(profile.is_initialized);
+    assert_eq!(profile.owner, alice.pubkey());
+    assert_eq!(profile.username, "alice");
+    assert_eq!(profile.bio, "gm");
+    assert_eq!(profile.posts_count, 0);
+
+    let state = unpack_program_state_from_slice(&account_data(&mut context, &program_state_pda(&program_id).0).await).unwrap();
+    assert_eq!(state.profiles_count, 1);
+}
+
+#[tokio::test]
+async fn create_post_assigns_id_and_updates_profile() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let profile_address = create_profile(&mut context, &program_id, &alice, "alice").await;
+
+    let post_address = create_post(&mut context, &program_id, &alice, &profile_address, "hello blocks").await;
+
+    let post = post(&mut context, &post_address).await;
+    assert_eq!(post.author, alice.pubkey());
+    assert_eq!(post.content, "hello blocks");
+    assert_eq!(post.id, 1);
+    assert_eq!(post.likes, 0);
+    assert_eq!(post.thread_root, post_address);
+
+    let profile = profile(&mut context, &profile_address).await;
+    assert_eq!(profile.posts_count, 1);
+    assert_eq!(profile.daily_post_count, 1);
+}
+
+#[tokio::test]
+async fn like_post_increments_likes() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "like me").await;
+
+    let ix = like_post_ix(&program_id, &bob.pubkey(), &post_address, &alice_profile, "alice", &bob_profile, 1);
+    send(&mut context, &[ix], &[&bob]).await;
+
+    assert_eq!(post(&mut context, &post_address).await.likes, 1);
+}
+
+#[tokio::test]
+async fn comment_links_to_parent_and_counts() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "discuss").await;
+
+    let ix = comment_on_post_ix(
+        &program_id,
+        &bob.pubkey(),
+        1,
+        &post_address,
+        &alice.pubkey(),
+        &alice_profile,
+        "alice",
+        None,
+        &bob_profile,
+        "first".to_string(),
+        1,
+        vec![],
+    );
+    send(&mut context, &[ix], &[&bob]).await;
+
+    let comment = post(&mut context, &post_pda(&program_id, &bob.pubkey(), 1).0).await;
+    assert_eq!(comment.author, bob.pubkey());
+    assert_eq!(comment.parent, Some(post_address));
+    assert_eq!(comment.thread_root, post_address);
+    assert_eq!(comment.depth, 1);
+    assert_eq!(post(&mut context, &post_address).await.comments, 1);
+}
+
+#[tokio::test]
+async fn comment_at_content_cap_is_stored_whole() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "discuss").await;
+
+    // The largest comment the limits allow: full-length content and every image at full length
+    let content = "c".repeat(MAX_POST_CONTENT_LEN);
+    let image = format!("https://{}", "i".repeat(MAX_URI_LEN - "https://".len()));
+    let images = vec![image; MAX_POST_IMAGES];
+    let ix = comment_on_post_ix(
+        &program_id,
+        &bob.pubkey(),
+        1,
+        &post_address,
+        &alice.pubkey(),
+        &alice_profile,
+        "alice",
+        None,
+        &bob_profile,
+        content.clone(),
+        1,
+        images.clone(),
+    );
+    send(&mut context, &[ix], &[&bob]).await;
+
+    let comment = post(&mut context, &post_pda(&program_id, &bob.pubkey(), 1).0).await;
+    assert_eq!(comment.content, content);
+    assert_eq!(comment.images, images);
+}
+
+#[tokio::test]
+async fn comment_over_content_cap_is_rejected() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "discuss").await;
+
+    let ix = comment_on_post_ix(
+        &program_id,
+        &bob.pubkey(),
+        1,
+        &post_address,
+        &alice.pubkey(),
+        &alice_profile,
+        "alice",
+        None,
+        &bob_profile,
+        "c".repeat(MAX_POST_CONTENT_LEN + 1),
+        1,
+        vec![],
+    );
+    let result = try_send(&mut context, &[ix], &[&bob]).await;
+
+    assert_custom_error(result, BlocksError::ContentTooLong);
+    assert_eq!(post(&mut context, &post_address).await.comments, 0);
+}
+
+#[tokio::test]
+async fn follow_updates_counts_and_indexes() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+
+    let ix = follow_profile_ix(&program_id, &alice.pubkey(), &alice_profile, &bob_profile, &bob.pubkey(), 0, 0);
+    send(&mut context, &[ix], &[&alice]).await;
+
+    assert_eq!(profile(&mut context, &alice_profile).await.following_count, 1);
+    assert_eq!(profile(&mut context, &bob_profile).await.followers_count, 1);
+
+    let following_page = following_index_pda(&program_id, &alice.pubkey(), 0).0;
+    let following: FollowingIndex = unpack_paged_index_from_slice(&account_data(&mut context, &following_page).await).unwrap();
+    assert_eq!(following.following, vec![bob.pubkey()]);
+
+    let follower_page = follower_index_pda(&program_id, &bob.pubkey(), 0).0;
+    let followers: FollowerIndex = unpack_paged_index_from_slice(&account_data(&mut context, &follower_page).await).unwrap();
+    assert_eq!(followers.followers, vec![alice.pubkey()]);
+}
+
+#[tokio::test]
+async fn create_and_join_community() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+
+    let community_address = community_pda(&program_id, "rustaceans").0;
+    let ix = create_community_ix(
+        &program_id,
+        &alice.pubkey(),
+        "rustaceans".to_string(),
+        "all things rust".to_string(),
+        String::new(),
+        vec![],
+    );
+    send(&mut context, &[ix], &[&alice]).await;
+
+    let created = community(&mut context, &community_address).await;
+    assert_eq!(created.owner, alice.pubkey());
+    assert_eq!(created.member_count, 1);
+
+    // JoinCommunity takes the u64 community id, not the community's address
+    let ix = join_community_ix(&program_id, &bob.pubkey(), &community_address, created.id, 0);
+    send(&mut context, &[ix], &[&bob]).await;
+
+    let joined = community(&mut context, &community_address).await;
+    assert_eq!(joined.member_count, 2);
+
+    let membership_address = membership_pda(&program_id, &community_address, &bob.pubkey()).0;
+    let membership = unpack_membership_from_slice(&account_data(&mut context, &membership_address).await).unwrap();
+    assert!(membership.role == Role::Member);
+    assert_eq!(membership.index_page, Some(0));
+
+    let page_address = member_index_pda(&program_id, &community_address, 0).0;
+    let page = unpack_member_index_from_slice(&account_data(&mut context, &page_address).await).unwrap();
+    assert_eq!(page.members, vec![alice.pubkey(), bob.pubkey()]);
+}
+
+#[tokio::test]
+async fn join_community_rejects_wrong_id() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+
+    let community_address = community_pda(&program_id, "rustaceans").0;
+    let ix = create_community_ix(
+        &program_id,
+        &alice.pubkey(),
+        "rustaceans".to_string(),
+        String::new(),
+        String::new(),
+        vec![],
+    );
+    send(&mut context, &[ix], &[&alice]).await;
+
+    let ix = join_community_ix(&program_id, &bob.pubkey(), &community_address, 42, 0);
+    assert!(try_send(&mut context, &[ix], &[&bob]).await.is_err());
+    assert_eq!(community(&mut context, &community_address).await.member_count, 1);
+}
+
+#[tokio::test]
+async fn comment_images_persist() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "show me").await;
+
+    let images = vec!["ipfs://cat".to_string(), "https://example.com/dog.png".to_string()];
+    let comment_address = try_comment(
+        &mut context,
+        &program_id,
+        &bob,
+        &bob_profile,
+        &post_address,
+        1,
+        &alice.pubkey(),
+        &alice_profile,
+        images.clone(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(post(&mut context, &comment_address).await.images, images);
+}
+
+#[tokio::test]
+async fn comment_rejects_too_many_images() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "show me").await;
+
+    let images = (0..=MAX_POST_IMAGES).map(|i| format!("ipfs://image{}", i)).collect();
+    let result = try_comment(
+        &mut context,
+        &program_id,
+        &bob,
+        &bob_profile,
+        &post_address,
+        1,
+        &alice.pubkey(),
+        &alice_profile,
+        images,
+    )
+    .await;
+
+    assert!(result.is_err());
+    assert_eq!(post(&mut context, &post_address).await.comments, 0);
+}
+
+#[tokio::test]
+async fn low_tier_author_posts_into_kill_zone() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+
+    let regular = create_post(&mut context, &program_id, &alice, &alice_profile, "before").await;
+    assert!(!post(&mut context, &regular).await.in_kill_zone);
+
+    set_ucr(&mut context, &alice_profile, UCR_LOW_VALUE_CONTRIBUTOR).await;
+    let suppressed = create_post(&mut context, &program_id, &alice, &alice_profile, "after").await;
+    assert!(post(&mut context, &suppressed).await.in_kill_zone);
+}
+
+#[tokio::test]
+async fn heavily_reported_post_enters_kill_zone() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "controversial").await;
+
+    for reported in 1..=KILL_ZONE_REPORT_THRESHOLD {
+        let reporter = funded_user(&mut context).await;
+        send(&mut context, &[report_post_ix(&program_id, &reporter.pubkey(), &post_address, 1, 0)], &[&reporter]).await;
+        let post = post(&mut context, &post_address).await;
+        assert_eq!(post.in_kill_zone, reported >= KILL_ZONE_REPORT_THRESHOLD);
+    }
+}
+
+#[tokio::test]
+async fn community_feed_lists_posts_newest_first() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+
+    let community_address = community_pda(&program_id, "rustaceans").0;
+    let ix = create_community_ix(
+        &program_id,
+        &alice.pubkey(),
+        "rustaceans".to_string(),
+        String::new(),
+        String::new(),
+        vec![],
+    );
+    send(&mut context, &[ix], &[&alice]).await;
+    let community_id = community(&mut context, &community_address).await.id;
+
+    let mut posts = Vec::new();
+    for i in 0..3 {
+        let post_id = next_post_id(&mut context, &alice_profile).await;
+        let ix = create_post_in_community_ix(
+            &program_id,
+            &alice.pubkey(),
+            post_id,
+            &alice_profile,
+            "alice",
+            &community_address,
+            community_id,
+            format!("post {}", i),
+            vec![],
+            None,
+        );
+        send(&mut context, &[ix], &[&alice]).await;
+        posts.push(post_pda(&program_id, &alice.pubkey(), post_id).0);
+    }
+
+    let feed_address = community_feed_pda(&program_id, &community_address).0;
+    let feed = unpack_community_feed_from_slice(&account_data(&mut context, &feed_address).await).unwrap();
+    posts.reverse();
+    assert_eq!(feed.community, community_address);
+    assert_eq!(feed.posts, posts);
+}
+
+#[tokio::test]
+async fn like_rejects_read_only_author_profile() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "like me").await;
+
+    let mut ix = like_post_ix(&program_id, &bob.pubkey(), &post_address, &alice_profile, "alice", &bob_profile, 1);
+    ix.accounts[2].is_writable = false;
+    let result = try_send(&mut context, &[ix], &[&bob]).await;
+
+    assert_custom_error(result, BlocksError::AccountNotWritable);
+    assert_eq!(post(&mut context, &post_address).await.likes, 0);
+}
+
+#[tokio::test]
+async fn create_post_rejects_read_only_profile() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+
+    let mut ix = create_post_ix(
+        &program_id,
+        &alice.pubkey(),
+        1,
+        &alice_profile,
+        "alice",
+        "hello".to_string(),
+        vec![],
+        None,
+        true,
+    );
+    let profile_meta = ix.accounts.iter_mut().find(|meta| meta.pubkey == alice_profile).unwrap();
+    profile_meta.is_writable = false;
+    let result = try_send(&mut context, &[ix], &[&alice]).await;
+
+    assert_custom_error(result, BlocksError::AccountNotWritable);
+    assert_eq!(profile(&mut context, &alice_profile).await.posts_count, 0);
+}
+
+#[tokio::test]
+async fn authority_reconciles_program_counters() {
+    let (mut context, program_id) = start().await;
+    let authority = context.payer.pubkey();
+    let state_address = program_state_pda(&program_id).0;
+
+    send(&mut context, &[reconcile_state_ix(&program_id, &authority, 7, 42, 3)], &[]).await;
+
+    let state = unpack_program_state_from_slice(&account_data(&mut context, &state_address).await).unwrap();
+    assert_eq!(state.profiles_count, 7);
+    assert_eq!(state.posts_count, 42);
+    assert_eq!(state.communities_count, 3);
+}
+
+#[tokio::test]
+async fn reconcile_state_rejects_non_authority() {
+    let (mut context, program_id) = start().await;
+    let mallory = funded_user(&mut context).await;
+    let state_address = program_state_pda(&program_id).0;
+
+    let ix = reconcile_state_ix(&program_id, &mallory.pubkey(), 7, 42, 3);
+    let result = try_send(&mut context, &[ix], &[&mallory]).await;
+    assert_custom_error(result, BlocksError::Unauthorized);
+
+    let state = unpack_program_state_from_slice(&account_data(&mut context, &state_address).await).unwrap();
+    assert_eq!(state.profiles_count, 0);
+    assert_eq!(state.posts_count, 0);
+}
+
+#[tokio::test]
+async fn delete_comment_decrements_parent_count() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "discuss").await;
+    let comment_address = try_comment(
+        &mut context,
+        &program_id,
+        &bob,
+        &bob_profile,
+        &post_address,
+        1,
+        &alice.pubkey(),
+        &alice_profile,
+        vec![],
+    )
+    .await
+    .unwrap();
+    assert_eq!(post(&mut context, &post_address).await.comments, 1);
+
+    let ix = delete_comment_ix(&program_id, &bob.pubkey(), &comment_address, &post_address, &bob_profile, 1, 1, false);
+    send(&mut context, &[ix], &[&bob]).await;
+
+    assert_eq!(post(&mut context, &post_address).await.comments, 0);
+    assert_eq!(profile(&mut context, &bob_profile).await.posts_count, 0);
+    assert!(context.banks_client.get_account(comment_address).await.unwrap().is_none());
+    let state = unpack_program_state_from_slice(&account_data(&mut context, &program_state_pda(&program_id).0).await).unwrap();
+    assert_eq!(state.posts_count, 1);
+}
+
+#[tokio::test]
+async fn delete_comment_rejects_non_author() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "discuss").await;
+    let comment_address = try_comment(
+        &mut context,
+        &program_id,
+        &bob,
+        &bob_profile,
+        &post_address,
+        1,
+        &alice.pubkey(),
+        &alice_profile,
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    let ix = delete_comment_ix(&program_id, &alice.pubkey(), &comment_address, &post_address, &alice_profile, 1, 1, false);
+    let result = try_send(&mut context, &[ix], &[&alice]).await;
+
+    assert_custom_error(result, BlocksError::NotPostOwner);
+    assert_eq!(post(&mut context, &post_address).await.comments, 1);
+}
+
+// Alice posts and Bob (an average contributor) engages; returns the post and both profiles
+async fn engagement_setup(
+    context: &mut ProgramTestContext,
+    program_id: &Pubkey,
+    alice: &Keypair,
+    bob: &Keypair,
+) -> (Pubkey, Pubkey, Pubkey) {
+    let alice_profile = create_profile(context, program_id, alice, "alice").await;
+    let bob_profile = create_profile(context, program_id, bob, "bob").await;
+    set_ucr(context, &bob_profile, UCR_AVERAGE_CONTRIBUTOR).await;
+    let post_address = create_post(context, program_id, alice, &alice_profile, "engage").await;
+    (post_address, alice_profile, bob_profile)
+}
+
+#[tokio::test]
+async fn like_credits_ucr_per_like() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+    let before = profile(&mut context, &alice_profile).await.user_credit_rating;
+
+    let ix = like_post_ix(&program_id, &bob.pubkey(), &post_address, &alice_profile, "alice", &bob_profile, 1);
+    send(&mut context, &[ix], &[&bob]).await;
+
+    assert_eq!(profile(&mut context, &alice_profile).await.user_credit_rating, before + UCR_PER_LIKE);
+}
+
+#[tokio::test]
+async fn like_rejects_unregistered_author_profile() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+
+    // A program-owned copy of the author's profile, still naming Alice as its owner
+    let look_alike = Pubkey::new_unique();
+    let account = context.banks_client.get_account(alice_profile).await.unwrap().unwrap();
+    context.set_account(&look_alike, &account.into());
+    let before = profile(&mut context, &look_alike).await.user_credit_rating;
+
+    let rejected = |result: Result<(), solana_program_test::BanksClientError>| {
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+        );
+    };
+
+    let ix = like_post_ix(&program_id, &bob.pubkey(), &post_address, &look_alike, "alice", &bob_profile, 1);
+    rejected(try_send(&mut context, &[ix], &[&bob]).await);
+
+    // Comment rewards, tips and mirrors credit the author the same way
+    let result = try_comment(&mut context, &program_id, &bob, &bob_profile, &post_address, 1, &alice.pubkey(), &look_alike, vec![]).await;
+    rejected(result.map(|_| ()));
+
+    let ix = tip_post_ix(&program_id, &bob.pubkey(), &post_address, &alice.pubkey(), &look_alike, "alice", 1, TIP_LAMPORTS_PER_UCR);
+    rejected(try_send(&mut context, &[ix], &[&bob]).await);
+
+    let ix = quote_post_ix(&program_id, &bob.pubkey(), 1, &bob_profile, "bob", &post_address, &look_alike, "alice", 1, "look".to_string());
+    rejected(try_send(&mut context, &[ix], &[&bob]).await);
+
+    assert_eq!(profile(&mut context, &look_alike).await.user_credit_rating, before);
+    let untouched = post(&mut context, &post_address).await;
+    assert_eq!((untouched.likes, untouched.comments, untouched.total_tips, untouched.mirrors), (0, 0, 0, 0));
+}
+
+#[tokio::test]
+async fn repeat_like_is_rejected() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+
+    let ix = like_post_ix(&program_id, &bob.pubkey(), &post_address, &alice_profile, "alice", &bob_profile, 1);
+    send(&mut context, std::slice::from_ref(&ix), &[&bob]).await;
+    let liked = profile(&mut context, &alice_profile).await.user_credit_rating;
+    let record = account_data(&mut context, &like_record_pda(&program_id, &post_address, &bob.pubkey()).0).await;
+    assert_eq!(unpack_like_record_from_slice(&record).unwrap().liker, bob.pubkey());
+
+    // A fresh blockhash so the repeat isn't deduplicated as the same transaction
+    refresh_blockhash(&mut context).await;
+    assert_custom_error(try_send(&mut context, &[ix], &[&bob]).await, BlocksError::AlreadyLiked);
+
+    assert_eq!(post(&mut context, &post_address).await.likes, 1);
+    assert_eq!(profile(&mut context, &alice_profile).await.user_credit_rating, liked);
+}
+
+#[tokio::test]
+async fn like_posts_rejects_duplicate_posts_in_a_batch() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+
+    let likes = [(post_address, alice_profile, "alice", 1), (post_address, alice_profile, "alice", 1)];
+    let ix = like_posts_ix(&program_id, &bob.pubkey(), &bob_profile, &likes);
+    assert_custom_error(try_send(&mut context, &[ix], &[&bob]).await, BlocksError::AlreadyLiked);
+
+    assert_eq!(post(&mut context, &post_address).await.likes, 0);
+}
+
+#[tokio::test]
+async fn comment_credits_ucr_per_comment() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+    let before = profile(&mut context, &alice_profile).await.user_credit_rating;
+
+    try_comment(&mut context, &program_id, &bob, &bob_profile, &post_address, 1, &alice.pubkey(), &alice_profile, vec![])
+        .await
+        .unwrap();
+
+    assert_eq!(profile(&mut context, &alice_profile).await.user_credit_rating, before + UCR_PER_COMMENT);
+}
+
+#[tokio::test]
+async fn quote_credits_ucr_per_mirror() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+    let before = profile(&mut context, &alice_profile).await.user_credit_rating;
+
+    let ix = quote_post_ix(
+        &program_id,
+        &bob.pubkey(),
+        1,
+        &bob_profile,
+        "bob",
+        &post_address,
+        &alice_profile,
+        "alice",
+        1,
+        "look".to_string(),
+    );
+    send(&mut context, &[ix], &[&bob]).await;
+
+    assert_eq!(post(&mut context, &post_address).await.mirrors, 1);
+    assert_eq!(profile(&mut context, &alice_profile).await.user_credit_rating, before + UCR_PER_MIRROR);
+}
+
+#[tokio::test]
+async fn quoting_a_quote_is_rejected() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+    let ix = quote_post_ix(&program_id, &bob.pubkey(), 1, &bob_profile, "bob", &post_address, &alice_profile, "alice", 1, "look".to_string());
+    send(&mut context, &[ix], &[&bob]).await;
+    let quote_address = post_pda(&program_id, &bob.pubkey(), 1).0;
+
+    // Alice quotes Bob's quote of her post
+    let ix = quote_post_ix(&program_id, &alice.pubkey(), 2, &alice_profile, "alice", &quote_address, &bob_profile, "bob", 1, "again".to_string());
+    let result = try_send(&mut context, &[ix], &[&alice]).await;
+
+    assert_custom_error(result, BlocksError::NestedQuote);
+    assert_eq!(post(&mut context, &quote_address).await.mirrors, 0);
+    assert_eq!(profile(&mut context, &alice_profile).await.last_post_id, 1);
+}
+
+#[tokio::test]
+async fn quote_is_indexed_and_checked_for_duplicates() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+
+    let ix = quote_post_ix(&program_id, &bob.pubkey(), 1, &bob_profile, "bob", &post_address, &alice_profile, "alice", 1, "look".to_string());
+    send(&mut context, &[ix], &[&bob]).await;
+    let quote_address = post_pda(&program_id, &bob.pubkey(), 1).0;
+    let index = unpack_post_index_from_slice(&account_data(&mut context, &post_index_pda(&program_id, &bob.pubkey()).0).await).unwrap();
+    assert_eq!(index.posts, vec![quote_address]);
+
+    // The same commentary again is a duplicate, whether quoted or posted
+    let ix = quote_post_ix(&program_id, &bob.pubkey(), 2, &bob_profile, "bob", &post_address, &alice_profile, "alice", 1, "look".to_string());
+    assert_custom_error(try_send(&mut context, &[ix], &[&bob]).await, BlocksError::DuplicateContent);
+    let ix = create_post_ix(&program_id, &bob.pubkey(), 2, &bob_profile, "bob", "look".to_string(), vec![], None, true);
+    assert_custom_error(try_send(&mut context, &[ix], &[&bob]).await, BlocksError::DuplicateContent);
+    assert_eq!(post(&mut context, &post_address).await.mirrors, 1);
+}
+
+#[tokio::test]
+async fn tip_credits_ucr_per_tip() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, _) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+    let before = profile(&mut context, &alice_profile).await.user_credit_rating;
+
+    let ix = tip_post_ix(
+        &program_id,
+        &bob.pubkey(),
+        &post_address,
+        &alice.pubkey(),
+        &alice_profile,
+        "alice",
+        1,
+        3 * TIP_LAMPORTS_PER_UCR,
+    );
+    send(&mut context, &[ix], &[&bob]).await;
+
+    assert_eq!(profile(&mut context, &alice_profile).await.user_credit_rating, before + 3 * UCR_PER_TIP);
+}
+
+#[tokio::test]
+async fn profile_aggregates_engagement_across_posts() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let carol = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let carol_profile = create_profile(&mut context, &program_id, &carol, "carol").await;
+    let first = create_post(&mut context, &program_id, &alice, &alice_profile, "first").await;
+    let second = create_post(&mut context, &program_id, &alice, &alice_profile, "second").await;
+
+    for (liker, liker_profile) in [(&bob, &bob_profile), (&carol, &carol_profile)] {
+        let ix = like_post_ix(&program_id, &liker.pubkey(), &first, &alice_profile, "alice", liker_profile, 1);
+        send(&mut context, &[ix], &[liker]).await;
+    }
+    let ix = like_post_ix(&program_id, &bob.pubkey(), &second, &alice_profile, "alice", &bob_profile, 2);
+    send(&mut context, &[ix], &[&bob]).await;
+    for (parent, parent_id) in [(&first, 1), (&second, 2)] {
+        try_comment(&mut context, &program_id, &carol, &carol_profile, parent, parent_id, &alice.pubkey(), &alice_profile, vec![])
+            .await
+            .unwrap();
+    }
+
+    let alice_state = profile(&mut context, &alice_profile).await;
+    let first_post = post(&mut context, &first).await;
+    let second_post = post(&mut context, &second).await;
+    assert_eq!(alice_state.total_likes_received, first_post.likes + second_post.likes);
+    assert_eq!(alice_state.total_likes_received, 3);
+    assert_eq!(alice_state.total_comments_received, first_post.comments + second_post.comments);
+    assert_eq!(alice_state.total_comments_received, 2);
+    assert_eq!(alice_state.engagement_rate(), 150);
+}
+
+// A profile with VERIFICATION_MIN_POSTS posts and one like received per post
+async fn verification_setup(context: &mut ProgramTestContext, program_id: &Pubkey, user: &Keypair) -> Pubkey {
+    let profile_address = create_profile(context, program_id, user, "alice").await;
+    for index in 0..VERIFICATION_MIN_POSTS {
+        create_post(context, program_id, user, &profile_address, &format!("post {index}")).await;
+    }
+    edit_profile(context, &profile_address, |profile| profile.total_likes_received = VERIFICATION_MIN_POSTS).await;
+    profile_address
+}
+
+#[tokio::test]
+async fn verify_profile_uses_the_likes_received_aggregate() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = verification_setup(&mut context, &program_id, &alice).await;
+
+    let ix = verify_profile_ix(&program_id, &alice.pubkey(), &alice_profile);
+    send(&mut context, &[ix], &[&alice]).await;
+
+    assert_eq!(profile(&mut context, &alice_profile).await.verification_level, VerificationLevel::Identity);
+}
+
+#[tokio::test]
+async fn verify_profile_rates_likes_over_every_post() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = verification_setup(&mut context, &program_id, &alice).await;
+    for index in 0..5 {
+        create_post(&mut context, &program_id, &alice, &alice_profile, &format!("quiet {index}")).await;
+    }
+
+    // 10 likes over 15 posts is below the threshold
+    let ix = verify_profile_ix(&program_id, &alice.pubkey(), &alice_profile);
+    let result = try_send(&mut context, &[ix], &[&alice]).await;
+    assert_custom_error(result, BlocksError::VerificationThresholdNotMet);
+    assert_eq!(profile(&mut context, &alice_profile).await.verification_level, VerificationLevel::None);
+}
+
+#[tokio::test]
+async fn verify_profile_works_for_prolific_authors() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    // Far more posts than a transaction could list as accounts
+    edit_profile(&mut context, &alice_profile, |profile| {
+        profile.posts_count = 500;
+        profile.total_likes_received = 350;
+    })
+    .await;
+
+    let ix = verify_profile_ix(&program_id, &alice.pubkey(), &alice_profile);
+    send(&mut context, &[ix], &[&alice]).await;
+
+    assert_eq!(profile(&mut context, &alice_profile).await.verification_level, VerificationLevel::Identity);
+}
+
+#[tokio::test]
+async fn muted_words_can_be_added_and_removed() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let preferences_address = mute_preferences_pda(&program_id, &alice.pubkey()).0;
+
+    for word in ["spoilers", "crypto"] {
+        send(&mut context, &[add_muted_word_ix(&program_id, &alice.pubkey(), word.to_string())], &[&alice]).await;
+    }
+    let result = try_send(&mut context, &[add_muted_word_ix(&program_id, &alice.pubkey(), "Crypto".to_string())], &[&alice]).await;
+    assert_custom_error(result, BlocksError::AlreadyMuted);
+
+    send(&mut context, &[remove_muted_word_ix(&program_id, &alice.pubkey(), "spoilers".to_string())], &[&alice]).await;
+    let preferences = unpack_mute_preferences_from_slice(&account_data(&mut context, &preferences_address).await).unwrap();
+    assert_eq!(preferences.owner, alice.pubkey());
+    assert_eq!(preferences.words, vec!["crypto".to_string()]);
+
+    let result = try_send(&mut context, &[remove_muted_word_ix(&program_id, &alice.pubkey(), "politics".to_string())], &[&alice]).await;
+    assert_custom_error(result, BlocksError::MutedWordNotFound);
+
+    // Removing the last word closes the list
+    send(&mut context, &[remove_muted_word_ix(&program_id, &alice.pubkey(), "crypto".to_string())], &[&alice]).await;
+    assert!(context.banks_client.get_account(preferences_address).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn muted_words_are_capped() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+
+    for i in 0..MAX_MUTED_WORDS {
+        send(&mut context, &[add_muted_word_ix(&program_id, &alice.pubkey(), format!("word{i}"))], &[&alice]).await;
+    }
+    let ix = add_muted_word_ix(&program_id, &alice.pubkey(), "one-too-many".to_string());
+    let result = try_send(&mut context, &[ix], &[&alice]).await;
+
+    assert_custom_error(result, BlocksError::MutedWordsFull);
+}
+
+#[tokio::test]
+async fn delete_post_refuses_while_comments_remain() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "discuss").await;
+    try_comment(&mut context, &program_id, &bob, &bob_profile, &post_address, 1, &alice.pubkey(), &alice_profile, vec![])
+        .await
+        .unwrap();
+
+    let ix = delete_post_ix(&program_id, &alice.pubkey(), &post_address, &alice_profile, 1, false);
+    let result = try_send(&mut context, &[ix], &[&alice]).await;
+    assert_custom_error(result, BlocksError::AccountStillReferenced);
+    assert_eq!(post(&mut context, &post_address).await.comments, 1);
+
+    let ix = delete_post_ix(&program_id, &alice.pubkey(), &post_address, &alice_profile, 1, true);
+    send(&mut context, &[ix], &[&alice]).await;
+    assert!(context.banks_client.get_account(post_address).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn delete_post_without_comments_succeeds() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "solo").await;
+
+    let ix = delete_post_ix(&program_id, &alice.pubkey(), &post_address, &alice_profile, 1, false);
+    send(&mut context, &[ix], &[&alice]).await;
+
+    assert!(context.banks_client.get_account(post_address).await.unwrap().is_none());
+    assert_eq!(profile(&mut context, &alice_profile).await.posts_count, 0);
+    let state = unpack_program_state_from_slice(&account_data(&mut context, &program_state_pda(&program_id).0).await).unwrap();
+    assert_eq!(state.posts_count, 0);
+}
+
+#[tokio::test]
+async fn deleted_profile_releases_its_username() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+
+    let ix = delete_profile_ix(&program_id, &alice.pubkey(), &alice_profile, "alice");
+    send(&mut context, &[ix], &[&alice]).await;
+
+    assert!(context.banks_client.get_account(alice_profile).await.unwrap().is_none());
+    let state = unpack_program_state_from_slice(&account_data(&mut context, &program_state_pda(&program_id).0).await).unwrap();
+    assert_eq!(state.profiles_count, 0);
+
+    // The freed handle can be claimed by someone else
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "alice").await;
+    assert_eq!(profile(&mut context, &bob_profile).await.owner, bob.pubkey());
+}
+
+#[tokio::test]
+async fn create_profile_twice_fails_without_resetting() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let address = create_profile(&mut context, &program_id, &alice, "alice").await;
+    set_ucr(&mut context, &address, 250).await;
+
+    let ix = create_profile_ix(
+        &program_id,
+        &alice.pubkey(),
+        "alice".to_string(),
+        "fresh start".to_string(),
+        String::new(),
+        String::new(),
+        None,
+        None,
+    );
+    let result = try_send(&mut context, &[ix], &[&alice]).await;
+
+    assert_custom_error(result, BlocksError::ProfileAlreadyExists);
+    let profile = profile(&mut context, &address).await;
+    assert_eq!(profile.bio, "gm");
+    assert_eq!(profile.user_credit_rating, 250);
+    let state = unpack_program_state_from_slice(&account_data(&mut context, &program_state_pda(&program_id).0).await).unwrap();
+    assert_eq!(state.profiles_count, 1);
+}
+
+#[tokio::test]
+async fn create_post_rejects_unregistered_profile_account() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+
+    // A program-owned copy of Alice's profile at an address that isn't her profile PDA
+    let look_alike = Pubkey::new_unique();
+    let account = context.banks_client.get_account(alice_profile).await.unwrap().unwrap();
+    context.set_account(&look_alike, &account.into());
+
+    let ix = create_post_ix(
+        &program_id,
+        &alice.pubkey(),
+        1,
+        &look_alike,
+        "alice",
+        "hello".to_string(),
+        vec![],
+        None,
+        true,
+    );
+    let result = try_send(&mut context, &[ix], &[&alice]).await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+    assert_eq!(profile(&mut context, &look_alike).await.posts_count, 0);
+}
+
+#[tokio::test]
+async fn posts_live_at_deterministic_addresses() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+
+    let first = create_post(&mut context, &program_id, &alice, &alice_profile, "first").await;
+    let second = create_post(&mut context, &program_id, &alice, &alice_profile, "second").await;
+
+    assert_eq!(first, post_pda(&program_id, &alice.pubkey(), 1).0);
+    assert_eq!(second, post_pda(&program_id, &alice.pubkey(), 2).0);
+    let second_post = post(&mut context, &second).await;
+    assert_eq!(second_post.id, 2);
+    assert_eq!(post_pda(&program_id, &second_post.author, second_post.id).0, second);
+}
+
+#[tokio::test]
+async fn post_ids_are_not_reused_after_delete() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let first = create_post(&mut context, &program_id, &alice, &alice_profile, "first").await;
+    let second = create_post(&mut context, &program_id, &alice, &alice_profile, "second").await;
+
+    let ix = delete_post_ix(&program_id, &alice.pubkey(), &first, &alice_profile, 1, false);
+    send(&mut context, &[ix], &[&alice]).await;
+    let third = create_post(&mut context, &program_id, &alice, &alice_profile, "third").await;
+
+    assert_eq!(third, post_pda(&program_id, &alice.pubkey(), 3).0);
+    assert_eq!(post(&mut context, &second).await.content, "second");
+    assert_eq!(profile(&mut context, &alice_profile).await.posts_count, 2);
+}
+
+#[tokio::test]
+async fn post_creation_survives_prefunded_address() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+
+    // Anyone can send lamports to the next post's predictable address
+    let address = post_pda(&program_id, &alice.pubkey(), 1).0;
+    let payer = context.payer.pubkey();
+    send(&mut context, &[system_instruction::transfer(&payer, &address, 1_000_000)], &[]).await;
+
+    let created = create_post(&mut context, &program_id, &alice, &alice_profile, "still works").await;
+
+    assert_eq!(created, address);
+    assert_eq!(post(&mut context, &address).await.content, "still works");
+}
+
+#[tokio::test]
+async fn post_creation_rejects_underfunded_existing_index() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    create_post(&mut context, &program_id, &alice, &alice_profile, "first").await;
+
+    // Drain the post index the first post created below its rent-exempt minimum
+    let index_address = post_index_pda(&program_id, &alice.pubkey()).0;
+    let mut account = context.banks_client.get_account(index_address).await.unwrap().unwrap();
+    account.lamports = 1;
+    context.set_account(&index_address, &account.into());
+
+    let ix = create_post_ix(&program_id, &alice.pubkey(), 2, &alice_profile, "alice", "second".to_string(), vec![], None, true);
+    let result = try_send(&mut context, &[ix], &[&alice]).await;
+
+    assert_custom_error(result, BlocksError::NotRentExempt);
+    assert_eq!(profile(&mut context, &alice_profile).await.posts_count, 1);
+}
+
+#[tokio::test]
+async fn profile_account_is_owned_sized_and_rent_exempt() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+
+    // Alice's address is untouched; Bob's is pre-funded with less than the rent
+    let bob_address = profile_pda(&program_id, &bob.pubkey(), "bob").0;
+    let payer = context.payer.pubkey();
+    send(&mut context, &[system_instruction::transfer(&payer, &bob_address, 1_000_000)], &[]).await;
+    let alice_address = create_profile(&mut context, &program_id, &alice, "alice").await;
+    create_profile(&mut context, &program_id, &bob, "bob").await;
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    for address in [alice_address, bob_address] {
+        let account = context.banks_client.get_account(address).await.unwrap().unwrap();
+        let stored = unpack_profile_from_slice(&account.data).unwrap();
+        assert_eq!(account.owner, program_id);
+        assert_eq!(account.data.len(), stored.required_space().unwrap());
+        assert!(rent.is_exempt(account.lamports, account.data.len()));
+    }
+}
+
+#[tokio::test]
+async fn soft_deleted_post_is_excluded_from_engagement() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+
+    let ix = soft_delete_account_ix(&program_id, &alice.pubkey(), &post_address);
+    send(&mut context, &[ix], &[&alice]).await;
+    let ix = like_post_ix(&program_id, &bob.pubkey(), &post_address, &alice_profile, "alice", &bob_profile, 1);
+    let result = try_send(&mut context, &[ix], &[&bob]).await;
+
+    assert_custom_error(result, BlocksError::PostNotFound);
+    let deleted = post(&mut context, &post_address).await;
+    assert!(deleted.is_deleted);
+    assert!(deleted.deleted_at > 0);
+    assert_eq!(deleted.content, "engage");
+    assert_eq!(deleted.likes, 0);
+}
+
+#[tokio::test]
+async fn soft_deleted_author_earns_no_engagement() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+
+    let ix = soft_delete_account_ix(&program_id, &alice.pubkey(), &alice_profile);
+    send(&mut context, &[ix], &[&alice]).await;
+
+    let ix = like_post_ix(&program_id, &bob.pubkey(), &post_address, &alice_profile, "alice", &bob_profile, 1);
+    assert_custom_error(try_send(&mut context, &[ix], &[&bob]).await, BlocksError::ProfileNotFound);
+    let ix = tip_post_ix(&program_id, &bob.pubkey(), &post_address, &alice.pubkey(), &alice_profile, "alice", 1, TIP_LAMPORTS_PER_UCR);
+    assert_custom_error(try_send(&mut context, &[ix], &[&bob]).await, BlocksError::ProfileNotFound);
+
+    let untouched = post(&mut context, &post_address).await;
+    assert_eq!((untouched.likes, untouched.total_tips), (0, 0));
+}
+
+#[tokio::test]
+async fn soft_delete_rejects_non_owner_and_repeats() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, _, _) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+
+    let ix = soft_delete_account_ix(&program_id, &bob.pubkey(), &post_address);
+    let result = try_send(&mut context, &[ix], &[&bob]).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::IllegalOwner)
+    );
+    assert!(!post(&mut context, &post_address).await.is_deleted);
+
+    let ix = soft_delete_account_ix(&program_id, &alice.pubkey(), &post_address);
+    send(&mut context, std::slice::from_ref(&ix), &[&alice]).await;
+    // Alice pays for the repeat so it isn't deduplicated as the same transaction
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(&[ix], Some(&alice.pubkey()), &[&alice], blockhash);
+    let result = context.banks_client.process_transaction(transaction).await;
+    assert_custom_error(result, BlocksError::AlreadyDeleted);
+}
+
+#[tokio::test]
+async fn like_ucr_diminishes_past_daily_post_cap() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+    set_ucr(&mut context, &bob_profile, UCR_TOP_CONTRIBUTOR).await;
+
+    // Leave the post one point short of today's cap
+    edit_post(&mut context, &post_address, |post| post.ucr_window_earned = DAILY_POST_UCR_CAP - 1).await;
+    let before = profile(&mut context, &alice_profile).await.user_credit_rating;
+
+    let ix = like_post_ix(&program_id, &bob.pubkey(), &post_address, &alice_profile, "alice", &bob_profile, 1);
+    send(&mut context, &[ix], &[&bob]).await;
+
+    // One point fits under the cap; the other two count at a quarter and round away
+    let gained = profile(&mut context, &alice_profile).await.user_credit_rating - before;
+    assert_eq!(gained, 1);
+    assert!(gained < LIKE_WEIGHT_TOP_CONTRIBUTOR);
+    assert_eq!(post(&mut context, &post_address).await.ucr_window_earned, DAILY_POST_UCR_CAP + 2);
+}
+
+// Put a post one like short of Conqueror
+async fn near_conqueror(context: &mut ProgramTestContext, address: &Pubkey) {
+    edit_post(context, address, |post| {
+        post.likes = 999_999;
+        post.rating = PostRating::from_likes(post.likes);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn conqueror_milestone_bonus_applies_once() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+    near_conqueror(&mut context, &post_address).await;
+    let before = profile(&mut context, &alice_profile).await.user_credit_rating;
+
+    let ix = like_post_ix(&program_id, &bob.pubkey(), &post_address, &alice_profile, "alice", &bob_profile, 1);
+    send(&mut context, &[ix], &[&bob]).await;
+
+    let conqueror = post(&mut context, &post_address).await;
+    assert_eq!(conqueror.likes, 1_000_000);
+    assert_eq!(conqueror.rating, PostRating::Conqueror);
+    let awarded = profile(&mut context, &alice_profile).await;
+    assert!(awarded.milestone_awarded);
+    assert_eq!(awarded.user_credit_rating, before + UCR_PER_LIKE + CONQUEROR_MILESTONE_UCR_BONUS);
+
+    // A second post reaching Conqueror only earns the like
+    let second = create_post(&mut context, &program_id, &alice, &alice_profile, "again").await;
+    near_conqueror(&mut context, &second).await;
+    let ix = like_post_ix(&program_id, &bob.pubkey(), &second, &alice_profile, "alice", &bob_profile, 2);
+    send(&mut context, &[ix], &[&bob]).await;
+
+    assert_eq!(post(&mut context, &second).await.rating, PostRating::Conqueror);
+    assert_eq!(
+        profile(&mut context, &alice_profile).await.user_credit_rating,
+        awarded.user_credit_rating + UCR_PER_LIKE
+    );
+}
+
+#[tokio::test]
+async fn raised_conqueror_threshold_revokes_milestone_bonus() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let carol = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+    let carol_profile = create_profile(&mut context, &program_id, &carol, "carol").await;
+    set_ucr(&mut context, &carol_profile, UCR_AVERAGE_CONTRIBUTOR).await;
+    near_conqueror(&mut context, &post_address).await;
+
+    let ix = like_post_ix(&program_id, &bob.pubkey(), &post_address, &alice_profile, "alice", &bob_profile, 1);
+    send(&mut context, &[ix], &[&bob]).await;
+    let awarded = profile(&mut context, &alice_profile).await;
+    assert!(awarded.milestone_awarded);
+
+    // The authority raises the Conqueror threshold past the post's likes
+    let authority = context.payer.pubkey();
+    let config = RatingConfig { conqueror: 2_000_000, ..RatingConfig::default() };
+    send(&mut context, &[set_rating_config_ix(&program_id, &authority, config)], &[]).await;
+
+    let ix = like_post_ix(&program_id, &carol.pubkey(), &post_address, &alice_profile, "alice", &carol_profile, 1);
+    send(&mut context, &[ix], &[&carol]).await;
+
+    let demoted = post(&mut context, &post_address).await;
+    assert_eq!(demoted.rating, PostRating::Ace);
+    assert!(!demoted.earned_milestone);
+    let revoked = profile(&mut context, &alice_profile).await;
+    assert!(!revoked.milestone_awarded);
+    assert_eq!(
+        revoked.user_credit_rating,
+        awarded.user_credit_rating + UCR_PER_LIKE - CONQUEROR_MILESTONE_UCR_BONUS
+    );
+}
+
+#[tokio::test]
+async fn comment_rejected_when_comments_disabled() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let post_address = create_post_with_comments(&mut context, &program_id, &alice, &alice_profile, "no replies", false).await;
+
+    let result = try_comment(
+        &mut context,
+        &program_id,
+        &bob,
+        &bob_profile,
+        &post_address,
+        1,
+        &alice.pubkey(),
+        &alice_profile,
+        vec![],
+    )
+    .await;
+
+    assert_custom_error(result.map(|_| ()), BlocksError::CommentsDisabled);
+    let closed = post(&mut context, &post_address).await;
+    assert!(!closed.allow_comments);
+    assert_eq!(closed.comments, 0);
+}
+
+#[tokio::test]
+async fn verified_only_community_rejects_unverified_poster() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let community_address = community_pda(&program_id, "verified").0;
+    let ix = create_community_ix(
+        &program_id,
+        &alice.pubkey(),
+        "verified".to_string(),
+        String::new(),
+        String::new(),
+        vec!["Verified members only".to_string()],
+    );
+    send(&mut context, &[ix], &[&alice]).await;
+    let community_id = community(&mut context, &community_address).await.id;
+    let rules = CommunityPostRules { require_verified: true, ..CommunityPostRules::default() };
+    let ix = set_community_post_rules_ix(&program_id, &alice.pubkey(), &community_address, rules);
+    send(&mut context, &[ix], &[&alice]).await;
+
+    let post_ix = |content: &str| {
+        create_post_in_community_ix(
+            &program_id,
+            &alice.pubkey(),
+            1,
+            &alice_profile,
+            "alice",
+            &community_address,
+            community_id,
+            content.to_string(),
+            vec![],
+            None,
+        )
+    };
+    let result = try_send(&mut context, &[post_ix("unverified")], &[&alice]).await;
+    assert_custom_error(result, BlocksError::VerificationRequired);
+    assert_eq!(community(&mut context, &community_address).await.post_rules, rules);
+
+    edit_profile(&mut context, &alice_profile, |profile| profile.set_verification_level(VerificationLevel::Identity)).await;
+    send(&mut context, &[post_ix("verified")], &[&alice]).await;
+    assert_eq!(post(&mut context, &post_pda(&program_id, &alice.pubkey(), 1).0).await.content, "verified");
+}
+
+#[tokio::test]
+async fn post_is_found_from_author_and_id_alone() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    create_post(&mut context, &program_id, &alice, &alice_profile, "find me").await;
+
+    // Only the author's key and the post id are known to the client
+    let address = post_pda(&program_id, &alice.pubkey(), 1).0;
+    let found = post(&mut context, &address).await;
+    assert_eq!((found.author, found.id, found.content.as_str()), (alice.pubkey(), 1, "find me"));
+
+    // Ids past the profile's last_post_id have no account yet
+    let unissued = post_pda(&program_id, &alice.pubkey(), 2).0;
+    assert!(context.banks_client.get_account(unissued).await.unwrap().is_none());
+}
+
+// Alice asks to follow Bob's private profile; returns both profiles
+async fn request_private_follow(
+    context: &mut ProgramTestContext,
+    program_id: &Pubkey,
+    alice: &Keypair,
+    bob: &Keypair,
+) -> (Pubkey, Pubkey) {
+    let alice_profile = create_profile(context, program_id, alice, "alice").await;
+    let bob_profile = create_profile(context, program_id, bob, "bob").await;
+    send(context, &[set_profile_privacy_ix(program_id, &bob.pubkey(), &bob_profile, true)], &[bob]).await;
+
+    let ix = follow_profile_ix(program_id, &alice.pubkey(), &alice_profile, &bob_profile, &bob.pubkey(), 0, 0);
+    send(context, &[ix], &[alice]).await;
+
+    let request = follow_request_pda(program_id, &alice.pubkey(), &bob.pubkey()).0;
+    assert!(context.banks_client.get_account(request).await.unwrap().is_some());
+    assert_eq!(profile(context, &alice_profile).await.following_count, 0);
+    assert_eq!(profile(context, &bob_profile).await.followers_count, 0);
+    (alice_profile, bob_profile)
+}
+
+#[tokio::test]
+async fn private_follow_counts_only_after_approval() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (alice_profile, bob_profile) = request_private_follow(&mut context, &program_id, &alice, &bob).await;
+
+    let ix = approve_follow_ix(&program_id, &bob.pubkey(), &bob_profile, &alice.pubkey(), &alice_profile, 0, 0);
+    send(&mut context, &[ix], &[&bob]).await;
+
+    assert_eq!(profile(&mut context, &alice_profile).await.following_count, 1);
+    assert_eq!(profile(&mut context, &bob_profile).await.followers_count, 1);
+    let record = follow_record_pda(&program_id, &alice.pubkey(), &bob.pubkey()).0;
+    assert!(context.banks_client.get_account(record).await.unwrap().is_some());
+    let request = follow_request_pda(&program_id, &alice.pubkey(), &bob.pubkey()).0;
+    assert!(context.banks_client.get_account(request).await.unwrap().is_none());
+    let follower_page = follower_index_pda(&program_id, &bob.pubkey(), 0).0;
+    let followers: FollowerIndex = unpack_paged_index_from_slice(&account_data(&mut context, &follower_page).await).unwrap();
+    assert_eq!(followers.followers, vec![alice.pubkey()]);
+}
+
+#[tokio::test]
+async fn rejected_follow_request_is_closed_without_following() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (alice_profile, bob_profile) = request_private_follow(&mut context, &program_id, &alice, &bob).await;
+
+    send(&mut context, &[reject_follow_ix(&program_id, &bob.pubkey(), &bob_profile, &alice.pubkey())], &[&bob]).await;
+
+    let request = follow_request_pda(&program_id, &alice.pubkey(), &bob.pubkey()).0;
+    assert!(context.banks_client.get_account(request).await.unwrap().is_none());
+    assert_eq!(profile(&mut context, &alice_profile).await.following_count, 0);
+    assert_eq!(profile(&mut context, &bob_profile).await.followers_count, 0);
+
+    // Nothing is left to approve
+    let ix = approve_follow_ix(&program_id, &bob.pubkey(), &bob_profile, &alice.pubkey(), &alice_profile, 0, 0);
+    let result = try_send(&mut context, &[ix], &[&bob]).await;
+    assert_custom_error(result, BlocksError::FollowRequestNotFound);
+}
+
+#[tokio::test]
+async fn join_community_refuses_members_past_the_cap() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let carol = funded_user(&mut context).await;
+    let community_address = community_pda(&program_id, "exclusive").0;
+    let ix = create_community_ix(
+        &program_id,
+        &alice.pubkey(),
+        "exclusive".to_string(),
+        String::new(),
+        String::new(),
+        vec![],
+    );
+    send(&mut context, &[ix], &[&alice]).await;
+    let community_id = community(&mut context, &community_address).await.id;
+    send(&mut context, &[set_community_max_members_ix(&program_id, &alice.pubkey(), &community_address, 2)], &[&alice]).await;
+
+    // Alice counts as the first member, so Bob fills the community
+    let ix = join_community_ix(&program_id, &bob.pubkey(), &community_address, community_id, 0);
+    send(&mut context, &[ix], &[&bob]).await;
+    let ix = join_community_ix(&program_id, &carol.pubkey(), &community_address, community_id, 0);
+    let result = try_send(&mut context, &[ix], &[&carol]).await;
+
+    assert_custom_error(result, BlocksError::CommunityLimitExceeded);
+    let full = community(&mut context, &community_address).await;
+    assert_eq!((full.member_count, full.max_members), (2, 2));
+}
+
+#[tokio::test]
+async fn update_community_changes_only_provided_fields() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let community_address = community_pda(&program_id, "gardening").0;
+    let ix = create_community_ix(
+        &program_id,
+        &alice.pubkey(),
+        "gardening".to_string(),
+        "Plants".to_string(),
+        "ipfs://avatar".to_string(),
+        vec!["Be kind".to_string()],
+    );
+    send(&mut context, &[ix], &[&alice]).await;
+
+    let description = "Plants, seeds and everything that grows from them".to_string();
+    let ix = update_community_ix(&program_id, &alice.pubkey(), &community_address, Some(description.clone()), None, None);
+    send(&mut context, &[ix], &[&alice]).await;
+
+    let updated = community(&mut context, &community_address).await;
+    assert_eq!(updated.description, description);
+    assert_eq!(updated.avatar, "ipfs://avatar");
+    assert_eq!(updated.rules, vec!["Be kind".to_string()]);
+
+    let ix = update_community_ix(&program_id, &bob.pubkey(), &community_address, None, Some(String::new()), None);
+    let result = try_send(&mut context, &[ix], &[&bob]).await;
+    assert_custom_error(result, BlocksError::NotCommunityOwner);
+}
+
+#[tokio::test]
+async fn update_community_rejects_too_many_rules() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let community_address = community_pda(&program_id, "rulebook").0;
+    let ix = create_community_ix(
+        &program_id,
+        &alice.pubkey(),
+        "rulebook".to_string(),
+        String::new(),
+        String::new(),
+        vec![],
+    );
+    send(&mut context, &[ix], &[&alice]).await;
+
+    let rules = vec!["No spam".to_string(); MAX_COMMUNITY_RULES + 1];
+    let ix = update_community_ix(&program_id, &alice.pubkey(), &community_address, None, None, Some(rules));
+    let result = try_send(&mut context, &[ix], &[&alice]).await;
+
+    assert_custom_error(result, BlocksError::FieldTooLong);
+    assert!(community(&mut context, &community_address).await.rules.is_empty());
+}
+
+#[tokio::test]
+async fn follow_advances_updated_at_on_both_profiles() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let created = profile(&mut context, &alice_profile).await;
+    assert_eq!(created.updated_at, created.created_at);
+
+    let now = advance_clock(&mut context, 3_600).await;
+    let ix = follow_profile_ix(&program_id, &alice.pubkey(), &alice_profile, &bob_profile, &bob.pubkey(), 0, 0);
+    send(&mut context, &[ix], &[&alice]).await;
+
+    assert_eq!(profile(&mut context, &alice_profile).await.updated_at, now);
+    assert_eq!(profile(&mut context, &bob_profile).await.updated_at, now);
+}
+
+#[tokio::test]
+async fn update_community_advances_updated_at() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let community_address = community_pda(&program_id, "clocks").0;
+    let ix = create_community_ix(
+        &program_id,
+        &alice.pubkey(),
+        "clocks".to_string(),
+        String::new(),
+        String::new(),
+        vec![],
+    );
+    send(&mut context, &[ix], &[&alice]).await;
+    let created_at = community(&mut context, &community_address).await.updated_at;
+
+    let now = advance_clock(&mut context, 3_600).await;
+    let ix = update_community_ix(&program_id, &alice.pubkey(), &community_address, Some("Tick".to_string()), None, None);
+    send(&mut context, &[ix], &[&alice]).await;
+
+    let updated_at = community(&mut context, &community_address).await.updated_at;
+    assert!(updated_at > created_at);
+    assert_eq!(updated_at, now);
+}
+
+#[tokio::test]
+async fn ucr_clamps_at_ceiling() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+    near_conqueror(&mut context, &post_address).await;
+    edit_profile(&mut context, &alice_profile, |profile| profile.user_credit_rating = UCR_MAX - 1).await;
+
+    // The like plus the Conqueror bonus would overshoot the ceiling
+    let ix = like_post_ix(&program_id, &bob.pubkey(), &post_address, &alice_profile, "alice", &bob_profile, 1);
+    send(&mut context, &[ix], &[&bob]).await;
+
+    let capped = profile(&mut context, &alice_profile).await;
+    assert!(capped.milestone_awarded);
+    assert_eq!(capped.user_credit_rating, UCR_MAX);
+}
+
+#[tokio::test]
+async fn ucr_clamps_at_floor() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+
+    // A score written before the floor existed is pulled back onto it by the next change
+    edit_profile(&mut context, &alice_profile, |profile| profile.user_credit_rating = UCR_MIN - 500).await;
+    try_comment(&mut context, &program_id, &bob, &bob_profile, &post_address, 1, &alice.pubkey(), &alice_profile, vec![])
+        .await
+        .unwrap();
+
+    let floored = profile(&mut context, &alice_profile).await;
+    assert_eq!(floored.user_credit_rating, UCR_MIN);
+    assert!(floored.is_spam);
+}
+
+#[tokio::test]
+async fn community_is_found_from_its_name() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let ix = create_community_ix(&program_id, &alice.pubkey(), "sb/Rustaceans".to_string(), String::new(), String::new(), vec![]);
+    send(&mut context, &[ix], &[&alice]).await;
+
+    // Lookups ignore ASCII case
+    let found = community(&mut context, &community_pda(&program_id, "sb/rustaceans").0).await;
+    assert_eq!(found.name, "sb/Rustaceans");
+    assert_eq!(found.owner, alice.pubkey());
+}
+
+#[tokio::test]
+async fn community_names_collide_regardless_of_case() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let ix = create_community_ix(&program_id, &alice.pubkey(), "chess".to_string(), String::new(), String::new(), vec![]);
+    send(&mut context, &[ix], &[&alice]).await;
+
+    let ix = create_community_ix(&program_id, &bob.pubkey(), "Chess".to_string(), String::new(), String::new(), vec![]);
+    let result = try_send(&mut context, &[ix], &[&bob]).await;
+    assert_custom_error(result, BlocksError::CommunityAlreadyExists);
+    assert_eq!(community(&mut context, &community_pda(&program_id, "chess").0).await.owner, alice.pubkey());
+
+    // The account must be the name's PDA, not an arbitrary key
+    let mut ix = create_community_ix(&program_id, &bob.pubkey(), "checkers".to_string(), String::new(), String::new(), vec![]);
+    ix.accounts[1].pubkey = community_pda(&program_id, "draughts").0;
+    let result = try_send(&mut context, &[ix], &[&bob]).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn identical_repost_is_rejected_within_window() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    create_post(&mut context, &program_id, &alice, &alice_profile, "buy my token").await;
+
+    let repost_ix = |post_id| {
+        create_post_ix(&program_id, &alice.pubkey(), post_id, &alice_profile, "alice", "buy my token".to_string(), vec![], None, true)
+    };
+    let result = try_send(&mut context, &[repost_ix(2)], &[&alice]).await;
+    assert_custom_error(result, BlocksError::DuplicateContent);
+
+    // Different content is unaffected
+    create_post(&mut context, &program_id, &alice, &alice_profile, "something else").await;
+
+    advance_clock(&mut context, DUPLICATE_CONTENT_WINDOW as i64).await;
+    send(&mut context, &[repost_ix(3)], &[&alice]).await;
+    assert_eq!(profile(&mut context, &alice_profile).await.posts_count, 3);
+}
+
+#[tokio::test]
+async fn authority_sets_each_verification_level() {
+    let (mut context, program_id) = start().await;
+    let authority = context.payer.pubkey();
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+
+    for (level, verified) in [
+        (VerificationLevel::Identity, true),
+        (VerificationLevel::Notable, true),
+        (VerificationLevel::Organization, true),
+        (VerificationLevel::None, false),
+    ] {
+        send(&mut context, &[set_verification_level_ix(&program_id, &authority, &alice_profile, level)], &[]).await;
+        let updated = profile(&mut context, &alice_profile).await;
+        assert_eq!(updated.verification_level, level);
+        assert_eq!(updated.is_verified(), verified);
+        assert_eq!(updated.legacy_verified, verified);
+    }
+
+    // The boolean instruction grants the base tier without lowering a higher one
+    send(&mut context, &[set_verification_ix(&program_id, &authority, &alice_profile, true)], &[]).await;
+    assert_eq!(profile(&mut context, &alice_profile).await.verification_level, VerificationLevel::Identity);
+    let bob = funded_user(&mut context).await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    send(&mut context, &[set_verification_level_ix(&program_id, &authority, &bob_profile, VerificationLevel::Notable)], &[]).await;
+    send(&mut context, &[set_verification_ix(&program_id, &authority, &bob_profile, true)], &[]).await;
+    assert_eq!(profile(&mut context, &bob_profile).await.verification_level, VerificationLevel::Notable);
+}
+
+#[tokio::test]
+async fn set_verification_level_rejects_non_authority() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+
+    let ix = set_verification_level_ix(&program_id, &alice.pubkey(), &alice_profile, VerificationLevel::Organization);
+    let result = try_send(&mut context, &[ix], &[&alice]).await;
+    assert_custom_error(result, BlocksError::Unauthorized);
+    assert!(!profile(&mut context, &alice_profile).await.is_verified());
+}
+
+#[tokio::test]
+async fn community_created_with_initial_members() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let members: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+
+    let ix = create_community_with_members_ix(
+        &program_id,
+        &alice.pubkey(),
+        "imported".to_string(),
+        String::new(),
+        String::new(),
+        vec![],
+        members.clone(),
+    );
+    send(&mut context, &[ix], &[&alice]).await;
+
+    let community_address = community_pda(&program_id, "imported").0;
+    assert_eq!(community(&mut context, &community_address).await.member_count, 4);
+    for member in &members {
+        let membership_address = membership_pda(&program_id, &community_address, member).0;
+        let membership = unpack_membership_from_slice(&account_data(&mut context, &membership_address).await).unwrap();
+        assert_eq!(membership.user, *member);
+        assert!(membership.role == Role::Member);
+        assert_eq!(membership.index_page, Some(0));
+    }
+
+    let page_address = member_index_pda(&program_id, &community_address, 0).0;
+    let page = unpack_member_index_from_slice(&account_data(&mut context, &page_address).await).unwrap();
+    assert_eq!(page.members, [vec![alice.pubkey()], members].concat());
+}
+
+#[tokio::test]
+async fn community_with_members_rejects_oversized_and_repeated_lists() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let create = |members: Vec<Pubkey>| {
+        create_community_with_members_ix(&program_id, &alice.pubkey(), "seeded".to_string(), String::new(), String::new(), vec![], members)
+    };
+
+    let oversized = (0..=MAX_INITIAL_MEMBERS).map(|_| Pubkey::new_unique()).collect();
+    let result = try_send(&mut context, &[create(oversized)], &[&alice]).await;
+    assert_custom_error(result, BlocksError::BatchTooLarge);
+
+    let bob = Pubkey::new_unique();
+    let result = try_send(&mut context, &[create(vec![bob, bob])], &[&alice]).await;
+    assert_custom_error(result, BlocksError::AlreadyMember);
+    let result = try_send(&mut context, &[create(vec![alice.pubkey()])], &[&alice]).await;
+    assert_custom_error(result, BlocksError::AlreadyMember);
+}
+
+// Swap the system program account at `index` for an impostor
+fn with_fake_system_program(mut ix: Instruction, index: usize) -> Instruction {
+    assert_eq!(ix.accounts[index].pubkey, solana_sdk::system_program::id());
+    ix.accounts[index].pubkey = Pubkey::new_unique();
+    ix
+}
+
+#[tokio::test]
+async fn account_creation_rejects_fake_system_program() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+    let invalid = |result: Result<(), solana_program_test::BanksClientError>| {
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+        );
+    };
+
+    let ix = create_post_ix(&program_id, &alice.pubkey(), 2, &alice_profile, "alice", "fresh".to_string(), vec![], None, true);
+    invalid(try_send(&mut context, &[with_fake_system_program(ix, 3)], &[&alice]).await);
+
+    let ix = comment_on_post_ix(
+        &program_id,
+        &bob.pubkey(),
+        1,
+        &post_address,
+        &alice.pubkey(),
+        &alice_profile,
+        "alice",
+        None,
+        &bob_profile,
+        "nice".to_string(),
+        1,
+        vec![],
+    );
+    invalid(try_send(&mut context, &[with_fake_system_program(ix, 4)], &[&bob]).await);
+
+    let ix = create_community_ix(&program_id, &alice.pubkey(), "impostors".to_string(), String::new(), String::new(), vec![]);
+    invalid(try_send(&mut context, &[with_fake_system_program(ix, 2)], &[&alice]).await);
+
+    assert_eq!(profile(&mut context, &alice_profile).await.posts_count, 1);
+    assert_eq!(post(&mut context, &post_address).await.comments, 0);
+}
+
+#[tokio::test]
+async fn leaderboard_orders_profiles_by_ucr() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let carol = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let carol_profile = create_profile(&mut context, &program_id, &carol, "carol").await;
+    set_ucr(&mut context, &alice_profile, 300).await;
+    set_ucr(&mut context, &bob_profile, 900).await;
+    set_ucr(&mut context, &carol_profile, 600).await;
+
+    let ix = update_leaderboard_ix(&program_id, &alice.pubkey(), &[alice_profile, bob_profile, carol_profile]);
+    send(&mut context, &[ix], &[&alice]).await;
+
+    let leaderboard_address = leaderboard_pda(&program_id).0;
+    let ranking = |data: &[u8]| -> Vec<(Pubkey, i64)> {
+        let leaderboard = unpack_leaderboard_from_slice(data).unwrap();
+        leaderboard.entries.iter().map(|entry| (entry.profile, entry.user_credit_rating)).collect()
+    };
+    assert_eq!(
+        ranking(&account_data(&mut context, &leaderboard_address).await),
+        vec![(bob_profile, 900), (carol_profile, 600), (alice_profile, 300)]
+    );
+
+    // Alice overtakes both; resubmitting only her profile reorders the board
+    set_ucr(&mut context, &alice_profile, 1_000).await;
+    let ix = update_leaderboard_ix(&program_id, &alice.pubkey(), &[alice_profile]);
+    send(&mut context, &[ix], &[&alice]).await;
+
+    assert_eq!(
+        ranking(&account_data(&mut context, &leaderboard_address).await),
+        vec![(alice_profile, 1_000), (bob_profile, 900), (carol_profile, 600)]
+    );
+}
+
+// Passes with and without `--features profiling`, checking the compute unit logs follow the feature
+#[tokio::test]
+async fn compute_units_are_logged_only_when_profiling() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let ix = create_profile_ix(&program_id, &alice.pubkey(), "alice".to_string(), "gm".to_string(), String::new(), String::new(), None, None);
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(&[ix], Some(&context.payer.pubkey()), &[&context.payer, &alice], blockhash);
+
+    let result = context.banks_client.process_transaction_with_metadata(transaction).await.unwrap();
+
+    assert!(result.result.is_ok());
+    // On chain the syscall logs "units remaining"; the native test runtime logs that it is unavailable
+    let logged = result
+        .metadata
+        .unwrap()
+        .log_messages
+        .iter()
+        .filter(|line| line.contains("units remaining") || line.contains("sol_log_compute_units"))
+        .count();
+    assert_eq!(logged, if cfg!(feature = "profiling") { 2 } else { 0 });
+}
+
+// Passes with and without `--features debug-logs`; release builds create profiles without the tracing
+#[tokio::test]
+async fn create_profile_traces_only_with_debug_logs() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let ix = create_profile_ix(&program_id, &alice.pubkey(), "alice".to_string(), "gm".to_string(), String::new(), String::new(), None, None);
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(&[ix], Some(&context.payer.pubkey()), &[&context.payer, &alice], blockhash);
+
+    let result = context.banks_client.process_transaction_with_metadata(transaction).await.unwrap();
+
+    assert!(result.result.is_ok());
+    let logs = result.metadata.unwrap().log_messages;
+    assert!(logs.iter().any(|line| line.contains("Profile created successfully")));
+    let traced = logs.iter().any(|line| line.contains("Expected PDA"));
+    assert_eq!(traced, cfg!(feature = "debug-logs"));
+    assert_eq!(profile(&mut context, &profile_pda(&program_id, &alice.pubkey(), "alice").0).await.username, "alice");
+}
+
+#[tokio::test]
+async fn membership_is_tracked_per_user() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (community_address, community_id) = create_community(&mut context, &program_id, &alice, "rustaceans").await;
+    let membership_address = membership_pda(&program_id, &community_address, &bob.pubkey()).0;
+
+    let join = join_community_ix(&program_id, &bob.pubkey(), &community_address, community_id, 0);
+    send(&mut context, std::slice::from_ref(&join), &[&bob]).await;
+    let membership = unpack_membership_from_slice(&account_data(&mut context, &membership_address).await).unwrap();
+    assert_eq!((membership.community, membership.user), (community_address, bob.pubkey()));
+
+    // A second join finds the existing membership
+    refresh_blockhash(&mut context).await;
+    assert_custom_error(try_send(&mut context, std::slice::from_ref(&join), &[&bob]).await, BlocksError::AlreadyMember);
+    assert_eq!(community(&mut context, &community_address).await.member_count, 2);
+
+    // Leaving closes the membership, so Bob can join again
+    let ix = leave_community_ix(&program_id, &bob.pubkey(), &community_address, community_id, 0);
+    send(&mut context, &[ix], &[&bob]).await;
+    assert!(context.banks_client.get_account(membership_address).await.unwrap().is_none());
+    assert_eq!(community(&mut context, &community_address).await.member_count, 1);
+
+    refresh_blockhash(&mut context).await;
+    send(&mut context, &[join], &[&bob]).await;
+    assert_eq!(community(&mut context, &community_address).await.member_count, 2);
+}
+
+#[tokio::test]
+async fn join_community_checks_the_id_against_the_account() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (first, first_id) = create_community(&mut context, &program_id, &alice, "rustaceans").await;
+    let (_, second_id) = create_community(&mut context, &program_id, &alice, "gophers").await;
+
+    // Another community's id doesn't match this account
+    let ix = join_community_ix(&program_id, &bob.pubkey(), &first, second_id, 0);
+    let result = try_send(&mut context, &[ix], &[&bob]).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+
+    let ix = join_community_ix(&program_id, &bob.pubkey(), &first, first_id, 0);
+    send(&mut context, &[ix], &[&bob]).await;
+    assert_eq!(community(&mut context, &first).await.member_count, 2);
+}
+
+#[tokio::test]
+async fn communities_get_sequential_ids_from_program_state() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+
+    let ids = [
+        create_community(&mut context, &program_id, &alice, "rustaceans").await.1,
+        create_community(&mut context, &program_id, &alice, "gophers").await.1,
+        create_community(&mut context, &program_id, &alice, "crabs").await.1,
+    ];
+
+    assert_eq!(ids, [0, 1, 2]);
+    let state_address = program_state_pda(&program_id).0;
+    let state = unpack_program_state_from_slice(&account_data(&mut context, &state_address).await).unwrap();
+    assert_eq!(state.communities_count, 3);
+}
+
+#[tokio::test]
+async fn earned_ucr_updates_the_stored_tier() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+    set_ucr(&mut context, &alice_profile, UCR_VALUABLE_CONTRIBUTOR - UCR_PER_LIKE).await;
+    assert!(profile(&mut context, &alice_profile).await.ucr_tier == UcrTier::AverageContributor);
+
+    let ix = like_post_ix(&program_id, &bob.pubkey(), &post_address, &alice_profile, "alice", &bob_profile, 1);
+    send(&mut context, &[ix], &[&bob]).await;
+
+    let promoted = profile(&mut context, &alice_profile).await;
+    assert_eq!(promoted.user_credit_rating, UCR_VALUABLE_CONTRIBUTOR);
+    assert!(promoted.ucr_tier == UcrTier::ValuableContributor);
+    assert!(promoted.ucr_tier == promoted.tier());
+}
+
+#[tokio::test]
+async fn delete_post_refunds_rent_to_the_author_only() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "mine").await;
+
+    let ix = delete_post_ix(&program_id, &bob.pubkey(), &post_address, &bob_profile, 1, false);
+    assert_custom_error(try_send(&mut context, &[ix], &[&bob]).await, BlocksError::NotPostOwner);
+
+    let rent = context.banks_client.get_balance(post_address).await.unwrap();
+    let before = context.banks_client.get_balance(alice.pubkey()).await.unwrap();
+    let ix = delete_post_ix(&program_id, &alice.pubkey(), &post_address, &alice_profile, 1, false);
+    send(&mut context, &[ix], &[&alice]).await;
+
+    assert!(rent > 0);
+    assert_eq!(context.banks_client.get_balance(alice.pubkey()).await.unwrap(), before + rent);
+    assert!(context.banks_client.get_account(post_address).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn delete_profile_refuses_while_posts_or_follows_remain() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "still here").await;
+    let ix = follow_profile_ix(&program_id, &alice.pubkey(), &alice_profile, &bob_profile, &bob.pubkey(), 0, 0);
+    send(&mut context, &[ix], &[&alice]).await;
+    let delete = delete_profile_ix(&program_id, &alice.pubkey(), &alice_profile, "alice");
+
+    assert_custom_error(try_send(&mut context, std::slice::from_ref(&delete), &[&alice]).await, BlocksError::ProfileNotEmpty);
+
+    let ix = delete_post_ix(&program_id, &alice.pubkey(), &post_address, &alice_profile, 1, false);
+    send(&mut context, &[ix], &[&alice]).await;
+    refresh_blockhash(&mut context).await;
+    assert_custom_error(try_send(&mut context, std::slice::from_ref(&delete), &[&alice]).await, BlocksError::ProfileNotEmpty);
+
+    let ix = unfollow_profile_ix(&program_id, &alice.pubkey(), &alice_profile, &bob_profile, &bob.pubkey(), 0, 0);
+    send(&mut context, &[ix], &[&alice]).await;
+    refresh_blockhash(&mut context).await;
+    send(&mut context, &[delete], &[&alice]).await;
+    assert!(context.banks_client.get_account(alice_profile).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn author_can_edit_post_content() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "tpyo").await;
+    let images = vec!["ipfs://fixed".to_string()];
+
+    let ix = edit_post_ix(&program_id, &bob.pubkey(), &post_address, None, 1, "hijacked".to_string(), vec![]);
+    assert_custom_error(try_send(&mut context, &[ix], &[&bob]).await, BlocksError::NotPostOwner);
+
+    let edited_at = advance_clock(&mut context, 5).await;
+    let ix = edit_post_ix(&program_id, &alice.pubkey(), &post_address, Some((&alice_profile, "alice")), 1, "typo".to_string(), images.clone());
+    send(&mut context, &[ix], &[&alice]).await;
+
+    let edited = post(&mut context, &post_address).await;
+    assert_eq!(edited.content, "typo");
+    assert_eq!(edited.images, images);
+    assert_eq!(edited.edited_at, edited_at);
+    assert!(edited.timestamp < edited_at);
+}
+
+#[tokio::test]
+async fn post_content_is_capped() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let post_ix = |content: String| create_post_ix(&program_id, &alice.pubkey(), 1, &alice_profile, "alice", content, vec![], None, true);
+
+    let result = try_send(&mut context, &[post_ix("p".repeat(MAX_POST_CONTENT_LEN + 1))], &[&alice]).await;
+    assert_custom_error(result, BlocksError::ContentTooLong);
+    assert_eq!(profile(&mut context, &alice_profile).await.posts_count, 0);
+
+    send(&mut context, &[post_ix("p".repeat(MAX_POST_CONTENT_LEN))], &[&alice]).await;
+    let stored = post(&mut context, &post_pda(&program_id, &alice.pubkey(), 1).0).await;
+    assert_eq!(stored.content.len(), MAX_POST_CONTENT_LEN);
+}
+
+#[tokio::test]
+async fn post_accounts_are_sized_to_their_content() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+
+    let short = create_post(&mut context, &program_id, &alice, &alice_profile, "gm").await;
+    let long = create_post(&mut context, &program_id, &alice, &alice_profile, &"l".repeat(MAX_POST_CONTENT_LEN)).await;
+
+    let short_len = account_data(&mut context, &short).await.len();
+    let long_len = account_data(&mut context, &long).await.len();
+    assert_eq!(short_len, post(&mut context, &short).await.required_space().unwrap());
+    assert_eq!(long_len, post(&mut context, &long).await.required_space().unwrap());
+    assert_eq!(long_len - short_len, MAX_POST_CONTENT_LEN - "gm".len());
+}
+
+#[tokio::test]
+async fn blocked_user_cannot_follow_or_comment_until_unblocked() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "no trolls").await;
+    send(&mut context, &[block_user_ix(&program_id, &alice.pubkey(), &bob.pubkey())], &[&alice]).await;
+
+    let follow = follow_profile_ix(&program_id, &bob.pubkey(), &bob_profile, &alice_profile, &alice.pubkey(), 0, 0);
+    assert_custom_error(try_send(&mut context, std::slice::from_ref(&follow), &[&bob]).await, BlocksError::UserBlocked);
+    let result = try_comment(&mut context, &program_id, &bob, &bob_profile, &post_address, 1, &alice.pubkey(), &alice_profile, vec![]).await;
+    assert_custom_error(result.map(|_| ()), BlocksError::UserBlocked);
+
+    send(&mut context, &[unblock_user_ix(&program_id, &alice.pubkey(), &bob.pubkey())], &[&alice]).await;
+    refresh_blockhash(&mut context).await;
+    send(&mut context, &[follow], &[&bob]).await;
+    assert_eq!(profile(&mut context, &alice_profile).await.followers_count, 1);
+}
+
+#[tokio::test]
+async fn follows_are_recorded_once_per_pair() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let unfollow = unfollow_profile_ix(&program_id, &alice.pubkey(), &alice_profile, &bob_profile, &bob.pubkey(), 0, 0);
+
+    assert_custom_error(try_send(&mut context, std::slice::from_ref(&unfollow), &[&alice]).await, BlocksError::NotFollowing);
+
+    let follow = follow_profile_ix(&program_id, &alice.pubkey(), &alice_profile, &bob_profile, &bob.pubkey(), 0, 0);
+    send(&mut context, std::slice::from_ref(&follow), &[&alice]).await;
+    let record_address = follow_record_pda(&program_id, &alice.pubkey(), &bob.pubkey()).0;
+    let record = unpack_follow_record_from_slice(&account_data(&mut context, &record_address).await).unwrap();
+    assert_eq!((record.follower, record.followed), (alice.pubkey(), bob.pubkey()));
+
+    refresh_blockhash(&mut context).await;
+    assert_custom_error(try_send(&mut context, &[follow], &[&alice]).await, BlocksError::AlreadyFollowing);
+    assert_eq!(profile(&mut context, &bob_profile).await.followers_count, 1);
+
+    refresh_blockhash(&mut context).await;
+    send(&mut context, &[unfollow], &[&alice]).await;
+    assert!(context.banks_client.get_account(record_address).await.unwrap().is_none());
+    assert_eq!(profile(&mut context, &bob_profile).await.followers_count, 0);
+}
+
+#[tokio::test]
+async fn only_granted_moderators_can_hide_community_posts() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let (community_address, community_id) = create_community(&mut context, &program_id, &alice, "rustaceans").await;
+    send(&mut context, &[join_community_ix(&program_id, &bob.pubkey(), &community_address, community_id, 0)], &[&bob]).await;
+
+    let post_id = next_post_id(&mut context, &alice_profile).await;
+    let ix = create_post_in_community_ix(
+        &program_id,
+        &alice.pubkey(),
+        post_id,
+        &alice_profile,
+        "alice",
+        &community_address,
+        community_id,
+        "off topic".to_string(),
+        vec![],
+        None,
+    );
+    send(&mut context, &[ix], &[&alice]).await;
+    let post_address = post_pda(&program_id, &alice.pubkey(), post_id).0;
+
+    // A plain member can't moderate
+    let moderate = moderate_post_ix(&program_id, &bob.pubkey(), &community_address, &post_address, post_id);
+    let result = try_send(&mut context, std::slice::from_ref(&moderate), &[&bob]).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+
+    // Only the owner grants the role
+    let ix = add_moderator_ix(&program_id, &bob.pubkey(), &community_address, &bob.pubkey());
+    assert_custom_error(try_send(&mut context, &[ix], &[&bob]).await, BlocksError::NotCommunityOwner);
+
+    send(&mut context, &[add_moderator_ix(&program_id, &alice.pubkey(), &community_address, &bob.pubkey())], &[&alice]).await;
+    let membership_address = membership_pda(&program_id, &community_address, &bob.pubkey()).0;
+    let membership = unpack_membership_from_slice(&account_data(&mut context, &membership_address).await).unwrap();
+    assert!(membership.role == Role::Moderator);
+
+    refresh_blockhash(&mut context).await;
+    send(&mut context, &[moderate], &[&bob]).await;
+    assert!(post(&mut context, &post_address).await.hidden);
+}
+
+#[tokio::test]
+async fn ownership_transfers_only_to_a_member() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (community_address, community_id) = create_community(&mut context, &program_id, &alice, "rustaceans").await;
+
+    // Bob has no membership to promote yet
+    let transfer = transfer_community_ownership_ix(&program_id, &alice.pubkey(), &community_address, &bob.pubkey());
+    assert!(try_send(&mut context, std::slice::from_ref(&transfer), &[&alice]).await.is_err());
+
+    send(&mut context, &[join_community_ix(&program_id, &bob.pubkey(), &community_address, community_id, 0)], &[&bob]).await;
+    let ix = transfer_community_ownership_ix(&program_id, &bob.pubkey(), &community_address, &bob.pubkey());
+    assert_custom_error(try_send(&mut context, &[ix], &[&bob]).await, BlocksError::NotCommunityOwner);
+
+    refresh_blockhash(&mut context).await;
+    send(&mut context, &[transfer], &[&alice]).await;
+    assert_eq!(community(&mut context, &community_address).await.owner, bob.pubkey());
+    let role = |data: Vec<u8>| unpack_membership_from_slice(&data).unwrap().role;
+    assert!(role(account_data(&mut context, &membership_pda(&program_id, &community_address, &bob.pubkey()).0).await) == Role::Owner);
+    assert!(role(account_data(&mut context, &membership_pda(&program_id, &community_address, &alice.pubkey()).0).await) == Role::Member);
+
+    // The previous owner can no longer hand it on
+    let ix = transfer_community_ownership_ix(&program_id, &alice.pubkey(), &community_address, &alice.pubkey());
+    assert_custom_error(try_send(&mut context, &[ix], &[&alice]).await, BlocksError::NotCommunityOwner);
+}
+
+#[tokio::test]
+async fn community_names_follow_the_naming_rule() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+
+    for name in ["ab", "   ", "rust aceans", "rust_aceans", "sb/ab", "sb/rust/aceans"] {
+        let ix = create_community_ix(&program_id, &alice.pubkey(), name.to_string(), String::new(), String::new(), vec![]);
+        assert_custom_error(try_send(&mut context, &[ix], &[&alice]).await, BlocksError::InvalidCommunityName);
+    }
+
+    let (plain, _) = create_community(&mut context, &program_id, &alice, "rust-aceans").await;
+    assert!(!community(&mut context, &plain).await.is_sb_community);
+    let (sub_blocks, _) = create_community(&mut context, &program_id, &alice, "sb/gophers").await;
+    let sub_blocks = community(&mut context, &sub_blocks).await;
+    assert!(sub_blocks.is_sb_community);
+    assert_eq!(sub_blocks.name, "sb/gophers");
+}
+
+#[tokio::test]
+async fn tip_moves_lamports_to_the_author() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, _) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+    let tip = |amount| tip_post_ix(&program_id, &bob.pubkey(), &post_address, &alice.pubkey(), &alice_profile, "alice", 1, amount);
+
+    // Bob can't cover more than his balance
+    assert_custom_error(try_send(&mut context, &[tip(2 * USER_LAMPORTS)], &[&bob]).await, BlocksError::InsufficientFunds);
+
+    let author_before = context.banks_client.get_balance(alice.pubkey()).await.unwrap();
+    let tipper_before = context.banks_client.get_balance(bob.pubkey()).await.unwrap();
+    send(&mut context, &[tip(5_000_000)], &[&bob]).await;
+    send(&mut context, &[tip(2_000_000)], &[&bob]).await;
+
+    assert_eq!(context.banks_client.get_balance(alice.pubkey()).await.unwrap(), author_before + 7_000_000);
+    assert_eq!(context.banks_client.get_balance(bob.pubkey()).await.unwrap(), tipper_before - 7_000_000);
+    assert_eq!(post(&mut context, &post_address).await.total_tips, 7_000_000);
+}
+
+#[tokio::test]
+async fn inactivity_decays_ucr_by_whole_days_down_to_the_floor() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    set_ucr(&mut context, &alice_profile, UCR_DECAY_FLOOR + 10).await;
+    let decay = decay_ucr_ix(&program_id, &alice_profile);
+
+    // Three and a half idle days decay three days' worth
+    advance_clock(&mut context, 3 * 86400 + 43200).await;
+    send(&mut context, std::slice::from_ref(&decay), &[]).await;
+    assert_eq!(profile(&mut context, &alice_profile).await.user_credit_rating, UCR_DECAY_FLOOR + 10 - 3 * UCR_DECAY_PER_DAY);
+
+    // The leftover half day isn't a full day yet
+    refresh_blockhash(&mut context).await;
+    send(&mut context, std::slice::from_ref(&decay), &[]).await;
+    assert_eq!(profile(&mut context, &alice_profile).await.user_credit_rating, UCR_DECAY_FLOOR + 10 - 3 * UCR_DECAY_PER_DAY);
+
+    advance_clock(&mut context, 30 * 86400).await;
+    refresh_blockhash(&mut context).await;
+    send(&mut context, std::slice::from_ref(&decay), &[]).await;
+    assert_eq!(profile(&mut context, &alice_profile).await.user_credit_rating, UCR_DECAY_FLOOR);
+
+    // Scores already under the floor are left alone
+    set_ucr(&mut context, &alice_profile, UCR_DECAY_FLOOR - 5).await;
+    advance_clock(&mut context, 30 * 86400).await;
+    refresh_blockhash(&mut context).await;
+    send(&mut context, &[decay], &[]).await;
+    assert_eq!(profile(&mut context, &alice_profile).await.user_credit_rating, UCR_DECAY_FLOOR - 5);
+}
+
+#[tokio::test]
+async fn spam_flagged_users_cannot_post_or_comment() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let bob_post = create_post(&mut context, &program_id, &bob, &bob_profile, "gm").await;
+
+    set_ucr(&mut context, &alice_profile, UCR_SPAM_USER).await;
+    assert!(profile(&mut context, &alice_profile).await.is_spam);
+    let post_ix = create_post_ix(&program_id, &alice.pubkey(), 1, &alice_profile, "alice", "buy now".to_string(), vec![], None, true);
+    assert_custom_error(try_send(&mut context, std::slice::from_ref(&post_ix), &[&alice]).await, BlocksError::SpamUser);
+    let result = try_comment(&mut context, &program_id, &alice, &alice_profile, &bob_post, 1, &bob.pubkey(), &bob_profile, vec![]).await;
+    assert_custom_error(result.map(|_| ()), BlocksError::SpamUser);
+
+    // Climbing back above the threshold lifts the flag
+    set_ucr(&mut context, &alice_profile, UCR_SPAM_USER + 1).await;
+    assert!(!profile(&mut context, &alice_profile).await.is_spam);
+    refresh_blockhash(&mut context).await;
+    send(&mut context, &[post_ix], &[&alice]).await;
+    assert_eq!(profile(&mut context, &alice_profile).await.posts_count, 1);
+}
+
+#[test]
+fn builders_round_trip_through_the_instruction_enum() {
+    let program_id = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+    let profile = profile_pda(&program_id, &user, "alice").0;
+
+    let ix = create_post_ix(&program_id, &user, 7, &profile, "alice", "gm".to_string(), vec!["ipfs://a".to_string()], None, false);
+    assert_eq!(ix.program_id, program_id);
+    match ContractInstruction::try_from_slice(&ix.data).unwrap() {
+        ContractInstruction::CreatePost { content, images, content_hash, allow_comments } => {
+            assert_eq!((content.as_str(), images.len(), content_hash, allow_comments), ("gm", 1, None, false));
+        }
+        other => panic!("decoded {:?}", other),
+    }
+    // Builders derive the PDAs in the order the handler reads them
+    assert!(ix.accounts[0].is_signer && ix.accounts[0].pubkey == user);
+    assert_eq!(ix.accounts[1].pubkey, post_pda(&program_id, &user, 7).0);
+    assert_eq!(ix.accounts[4].pubkey, program_state_pda(&program_id).0);
+    assert_eq!(ix.accounts[5].pubkey, post_index_pda(&program_id, &user).0);
+
+    let community = community_pda(&program_id, "rustaceans").0;
+    let ix = join_community_ix(&program_id, &user, &community, 3, 0);
+    assert!(matches!(ContractInstruction::try_from_slice(&ix.data).unwrap(), ContractInstruction::JoinCommunity { community_id: 3 }));
+    assert_eq!(ix.accounts[2].pubkey, membership_pda(&program_id, &community, &user).0);
+
+    let ix = tip_post_ix(&program_id, &user, &Pubkey::new_unique(), &Pubkey::new_unique(), &profile, "alice", 2, 500);
+    assert!(matches!(
+        ContractInstruction::try_from_slice(&ix.data).unwrap(),
+        ContractInstruction::TipPost { post_id: 2, amount: 500 }
+    ));
+}
+
+#[test]
+fn pda_helpers_match_the_documented_seeds() {
+    let program_id = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+    let other = Pubkey::new_unique();
+    let find = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &program_id);
+
+    assert_eq!(profile_pda(&program_id, &user, "alice"), find(&[user.as_ref(), b"profile", b"alice"]));
+    assert_eq!(post_pda(&program_id, &user, 7), find(&[user.as_ref(), b"post", &7u64.to_le_bytes()]));
+    assert_eq!(program_state_pda(&program_id), find(&[b"state"]));
+    assert_eq!(membership_pda(&program_id, &other, &user), find(&[other.as_ref(), user.as_ref(), b"member"]));
+    assert_eq!(follow_record_pda(&program_id, &user, &other), find(&[user.as_ref(), other.as_ref(), b"follow"]));
+    assert_eq!(post_index_pda(&program_id, &user), find(&[user.as_ref(), b"posts"]));
+
+    // Seeds are order sensitive, so a follow in each direction gets its own record
+    assert_ne!(follow_record_pda(&program_id, &user, &other).0, follow_record_pda(&program_id, &other, &user).0);
+}
+
+#[tokio::test]
+async fn accounts_decode_by_their_kind_byte() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "gm").await;
+    let (community_address, _) = create_community(&mut context, &program_id, &alice, "rustaceans").await;
+
+    let profile_data = account_data(&mut context, &alice_profile).await;
+    let post_data = account_data(&mut context, &post_address).await;
+    let community_data = account_data(&mut context, &community_address).await;
+    assert!(matches!(decode_account(&profile_data).unwrap(), DecodedAccount::Profile(profile) if profile.username == "alice"));
+    assert!(matches!(decode_account(&post_data).unwrap(), DecodedAccount::Post(post) if post.content == "gm"));
+    assert!(matches!(decode_account(&community_data).unwrap(), DecodedAccount::Community(community) if community.name == "rustaceans"));
+
+    // Typed unpacks refuse another kind's data
+    assert!(unpack_post_from_slice(&profile_data).is_err());
+    assert!(unpack_profile_from_slice(&community_data).is_err());
+    assert!(unpack_community_from_slice(&post_data).is_err());
+}
+
+#[tokio::test]
+async fn counters_at_their_limit_fail_with_arithmetic_overflow() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+
+    edit_post(&mut context, &post_address, |post| post.likes = u64::MAX).await;
+    let ix = like_post_ix(&program_id, &bob.pubkey(), &post_address, &alice_profile, "alice", &bob_profile, 1);
+    assert_custom_error(try_send(&mut context, &[ix], &[&bob]).await, BlocksError::ArithmeticOverflow);
+    assert_eq!(post(&mut context, &post_address).await.likes, u64::MAX);
+
+    edit_profile(&mut context, &alice_profile, |profile| profile.followers_count = u64::MAX).await;
+    let ix = follow_profile_ix(&program_id, &bob.pubkey(), &bob_profile, &alice_profile, &alice.pubkey(), 0, 0);
+    assert_custom_error(try_send(&mut context, &[ix], &[&bob]).await, BlocksError::ArithmeticOverflow);
+    assert_eq!(profile(&mut context, &bob_profile).await.following_count, 0);
+}
+
+#[tokio::test]
+async fn handlers_reject_one_account_too_few() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let user = alice.pubkey();
+    let profile = profile_pda(&program_id, &user, "alice").0;
+    let post = post_pda(&program_id, &user, 1).0;
+    let other = Pubkey::new_unique();
+    let community = community_pda(&program_id, "rustaceans").0;
+
+    let instructions = [
+        create_profile_ix(&program_id, &user, "alice".to_string(), String::new(), String::new(), String::new(), None, None),
+        create_post_ix(&program_id, &user, 1, &profile, "alice", "gm".to_string(), vec![], None, true),
+        like_post_ix(&program_id, &user, &post, &profile, "alice", &profile, 1),
+        tip_post_ix(&program_id, &user, &post, &other, &profile, "alice", 1, 1),
+        unfollow_profile_ix(&program_id, &user, &profile, &other, &other, 0, 0),
+        join_community_ix(&program_id, &user, &community, 0, 0),
+        delete_post_ix(&program_id, &user, &post, &profile, 1, false),
+        block_user_ix(&program_id, &user, &other),
+        report_post_ix(&program_id, &user, &post, 1, 0),
+    ];
+    for (index, mut ix) in instructions.into_iter().enumerate() {
+        ix.accounts.pop();
+        let result = try_send(&mut context, &[ix], &[&alice]).await;
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys),
+            "instruction {}",
+            index
+        );
+    }
+}
+
+#[tokio::test]
+async fn quote_references_the_original_post() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+    let quote = |content: &str| {
+        quote_post_ix(&program_id, &bob.pubkey(), 1, &bob_profile, "bob", &post_address, &alice_profile, "alice", 1, content.to_string())
+    };
+
+    // Kill-zone posts can't be quoted
+    edit_post(&mut context, &post_address, |post| post.in_kill_zone = true).await;
+    assert_custom_error(try_send(&mut context, &[quote("look")], &[&bob]).await, BlocksError::PostInKillZone);
+    edit_post(&mut context, &post_address, |post| post.in_kill_zone = false).await;
+
+    let result = try_send(&mut context, &[quote(&"q".repeat(MAX_POST_CONTENT_LEN + 1))], &[&bob]).await;
+    assert_custom_error(result, BlocksError::ContentTooLong);
+
+    send(&mut context, &[quote("look at this")], &[&bob]).await;
+    let stored = post(&mut context, &post_pda(&program_id, &bob.pubkey(), 1).0).await;
+    assert_eq!(stored.quoted_post, Some(post_address));
+    assert_eq!((stored.author, stored.content.as_str()), (bob.pubkey(), "look at this"));
+    assert_eq!(post(&mut context, &post_address).await.mirrors, 1);
+    assert_eq!(profile(&mut context, &bob_profile).await.posts_count, 1);
+}
+
+#[tokio::test]
+async fn post_index_lists_recent_posts_oldest_first() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+
+    let mut posts = Vec::new();
+    for i in 0..3 {
+        posts.push(create_post(&mut context, &program_id, &alice, &alice_profile, &format!("post {}", i)).await);
+    }
+    let index = unpack_post_index_from_slice(&account_data(&mut context, &post_index_pda(&program_id, &alice.pubkey()).0).await).unwrap();
+    assert_eq!((index.owner, index.posts), (alice.pubkey(), posts));
+
+    // A full index drops its oldest entry for each new post
+    let mut index = PostIndex { is_initialized: true, owner: alice.pubkey(), posts: vec![] };
+    let all: Vec<Pubkey> = (0..MAX_POST_INDEX_LEN + 2).map(|_| Pubkey::new_unique()).collect();
+    for post in &all {
+        index.push(*post);
+    }
+    assert_eq!(index.posts, all[2..]);
+}
+
+#[tokio::test]
+async fn reports_hide_a_post_at_the_threshold_once_per_reporter() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "controversial").await;
+
+    let first = funded_user(&mut context).await;
+    let report = report_post_ix(&program_id, &first.pubkey(), &post_address, 1, 0);
+    send(&mut context, std::slice::from_ref(&report), &[&first]).await;
+    refresh_blockhash(&mut context).await;
+    assert_custom_error(try_send(&mut context, &[report], &[&first]).await, BlocksError::AlreadyReported);
+    assert_eq!(post(&mut context, &post_address).await.report_count, 1);
+
+    for reported in 2..=REPORT_HIDE_THRESHOLD {
+        let reporter = funded_user(&mut context).await;
+        send(&mut context, &[report_post_ix(&program_id, &reporter.pubkey(), &post_address, 1, 0)], &[&reporter]).await;
+        let post = post(&mut context, &post_address).await;
+        assert_eq!(post.report_count, reported);
+        assert_eq!(post.hidden, reported >= REPORT_HIDE_THRESHOLD);
+    }
+}
+
+#[tokio::test]
+async fn profile_fields_are_checked_against_their_own_limits() {
+    let (mut context, program_id) = start().await;
+    let profile_ix = |user: &Pubkey, username: String, bio: usize, image: usize| {
+        create_profile_ix(&program_id, user, username, "b".repeat(bio), "i".repeat(image), String::new(), None, None)
+    };
+
+    let alice = funded_user(&mut context).await;
+    // An over-long username can't seed a PDA, so only its data differs from a valid instruction
+    let mut long_username = profile_ix(&alice.pubkey(), "alice".to_string(), 0, 0);
+    long_username.data = ContractInstruction::CreateProfile {
+        username: "a".repeat(MAX_USERNAME_LEN + 1),
+        bio: String::new(),
+        profile_image: String::new(),
+        cover_image: String::new(),
+        profile_image_hash: None,
+        cover_image_hash: None,
+    }
+    .try_to_vec()
+    .unwrap();
+    for ix in [
+        long_username,
+        profile_ix(&alice.pubkey(), "alice".to_string(), MAX_BIO_LEN + 1, 0),
+        profile_ix(&alice.pubkey(), "alice".to_string(), 0, MAX_URI_LEN + 1),
+    ] {
+        assert_custom_error(try_send(&mut context, &[ix], &[&alice]).await, BlocksError::FieldTooLong);
+    }
+
+    // Every field at its limit is stored whole rather than truncated
+    let username = "a".repeat(MAX_USERNAME_LEN);
+    send(&mut context, &[profile_ix(&alice.pubkey(), username.clone(), MAX_BIO_LEN, MAX_URI_LEN)], &[&alice]).await;
+    let stored = profile(&mut context, &profile_pda(&program_id, &alice.pubkey(), &username).0).await;
+    assert_eq!(stored.username, username);
+    assert_eq!((stored.bio.len(), stored.profile_image.len()), (MAX_BIO_LEN, MAX_URI_LEN));
+}
+
+#[tokio::test]
+async fn changing_username_frees_the_old_name() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    create_profile(&mut context, &program_id, &bob, "bob").await;
+
+    let ix = change_username_ix(&program_id, &alice.pubkey(), &alice_profile, "alice", "bob".to_string());
+    assert_custom_error(try_send(&mut context, &[ix], &[&alice]).await, BlocksError::ProfileAlreadyExists);
+
+    let ix = change_username_ix(&program_id, &alice.pubkey(), &alice_profile, "alice", "alicia".to_string());
+    send(&mut context, &[ix], &[&alice]).await;
+    // The profile keeps its address; only the stored name and the registry entries move
+    assert_eq!(profile(&mut context, &alice_profile).await.username, "alicia");
+    assert!(context.banks_client.get_account(username_registry_pda(&program_id, "alice").0).await.unwrap().is_none());
+
+    let carol = funded_user(&mut context).await;
+    let ix = create_profile_ix(&program_id, &carol.pubkey(), "alicia".to_string(), String::new(), String::new(), String::new(), None, None);
+    assert_custom_error(try_send(&mut context, &[ix], &[&carol]).await, BlocksError::ProfileAlreadyExists);
+    let carol_profile = create_profile(&mut context, &program_id, &carol, "alice").await;
+    assert_eq!(profile(&mut context, &carol_profile).await.owner, carol.pubkey());
+}
+
+#[tokio::test]
+async fn like_weight_follows_the_likers_tier() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+    let carol = funded_user(&mut context).await;
+    let carol_profile = create_profile(&mut context, &program_id, &carol, "carol").await;
+    set_ucr(&mut context, &carol_profile, UCR_TOP_CONTRIBUTOR).await;
+    let dave = funded_user(&mut context).await;
+    let dave_profile = create_profile(&mut context, &program_id, &dave, "dave").await;
+    set_ucr(&mut context, &dave_profile, UCR_SPAM_USER).await;
+
+    let mut gains = Vec::new();
+    for (liker, liker_profile) in [(&carol, carol_profile), (&bob, bob_profile), (&dave, dave_profile)] {
+        let before = profile(&mut context, &alice_profile).await.user_credit_rating;
+        let ix = like_post_ix(&program_id, &liker.pubkey(), &post_address, &alice_profile, "alice", &liker_profile, 1);
+        send(&mut context, &[ix], &[liker]).await;
+        gains.push(profile(&mut context, &alice_profile).await.user_credit_rating - before);
+    }
+
+    // A spam account's like still counts toward the post, just not toward the author's UCR
+    assert_eq!(gains, [LIKE_WEIGHT_TOP_CONTRIBUTOR, LIKE_WEIGHT_AVERAGE_CONTRIBUTOR, LIKE_WEIGHT_SPAM_USER]);
+    assert_eq!(post(&mut context, &post_address).await.likes, 3);
+}
+
+#[tokio::test]
+async fn comments_reward_the_author_up_to_a_per_user_cap() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+    let before = profile(&mut context, &alice_profile).await.user_credit_rating;
+
+    for _ in 0..MAX_COMMENTS_PER_USER_PER_POST {
+        try_comment(&mut context, &program_id, &bob, &bob_profile, &post_address, 1, &alice.pubkey(), &alice_profile, vec![]).await.unwrap();
+    }
+    let result = try_comment(&mut context, &program_id, &bob, &bob_profile, &post_address, 1, &alice.pubkey(), &alice_profile, vec![]).await;
+    assert_custom_error(result.map(|_| ()), BlocksError::CommentLimitReached);
+    let receipt = unpack_comment_receipt_from_slice(&account_data(&mut context, &comment_receipt_pda(&program_id, &post_address, &bob.pubkey()).0).await).unwrap();
+    assert_eq!(receipt.count, MAX_COMMENTS_PER_USER_PER_POST);
+
+    // The cap is per commenter, and replying to yourself earns nothing
+    let carol = funded_user(&mut context).await;
+    let carol_profile = create_profile(&mut context, &program_id, &carol, "carol").await;
+    set_ucr(&mut context, &carol_profile, UCR_AVERAGE_CONTRIBUTOR).await;
+    try_comment(&mut context, &program_id, &carol, &carol_profile, &post_address, 1, &alice.pubkey(), &alice_profile, vec![]).await.unwrap();
+    try_comment(&mut context, &program_id, &alice, &alice_profile, &post_address, 1, &alice.pubkey(), &alice_profile, vec![]).await.unwrap();
+
+    let gained = profile(&mut context, &alice_profile).await.user_credit_rating - before;
+    assert_eq!(gained, (MAX_COMMENTS_PER_USER_PER_POST as i64 + 1) * UCR_PER_COMMENT);
+    assert_eq!(post(&mut context, &post_address).await.comments, MAX_COMMENTS_PER_USER_PER_POST + 2);
+}
+
+// The native test runtime prints `sol_log_data` to stdout instead of the transaction log, so this
+// checks the bytes `Event::emit` logs rather than capturing them from a transaction
+#[test]
+fn events_round_trip_through_borsh() {
+    let post = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+    let events = [
+        Event::ProfileCreated { profile: Pubkey::new_unique(), owner: user },
+        Event::PostCreated { post, author: user, id: 7 },
+        Event::PostLiked { post, liker: user, likes: 12 },
+        Event::ProfileFollowed { follower: user, followed: Pubkey::new_unique() },
+    ];
+    for event in events {
+        let data = event.try_to_vec().unwrap();
+        assert_eq!(Event::try_from_slice(&data).unwrap(), event);
+    }
+
+    // The leading byte is the variant tag indexers switch on
+    assert_eq!(Event::PostCreated { post, author: user, id: 7 }.try_to_vec().unwrap()[0], 2);
+}
+
+#[tokio::test]
+async fn like_posts_applies_a_batch_atomically() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (first, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+    let second = create_post(&mut context, &program_id, &alice, &alice_profile, "second").await;
+    let third = create_post(&mut context, &program_id, &alice, &alice_profile, "third").await;
+
+    let ix = like_posts_ix(&program_id, &bob.pubkey(), &bob_profile, &[]);
+    assert_custom_error(try_send(&mut context, &[ix], &[&bob]).await, BlocksError::BatchTooLarge);
+
+    // One mismatched id rolls back the likes before it
+    let batch = [(first, alice_profile, "alice", 1), (second, alice_profile, "alice", 2), (third, alice_profile, "alice", 99)];
+    let result = try_send(&mut context, &[like_posts_ix(&program_id, &bob.pubkey(), &bob_profile, &batch)], &[&bob]).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+    for address in [first, second, third] {
+        assert_eq!(post(&mut context, &address).await.likes, 0);
+    }
+
+    let batch = [(first, alice_profile, "alice", 1), (second, alice_profile, "alice", 2), (third, alice_profile, "alice", 3)];
+    send(&mut context, &[like_posts_ix(&program_id, &bob.pubkey(), &bob_profile, &batch)], &[&bob]).await;
+    for address in [first, second, third] {
+        assert_eq!(post(&mut context, &address).await.likes, 1);
+    }
+}
+
+#[tokio::test]
+async fn ratings_follow_the_configured_thresholds() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, alice_profile, bob_profile) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+    let config = RatingConfig { bronze: 1, silver: 2, ..RatingConfig::default() };
+
+    let ix = set_rating_config_ix(&program_id, &bob.pubkey(), config);
+    assert_custom_error(try_send(&mut context, &[ix], &[&bob]).await, BlocksError::Unauthorized);
+    let authority = context.payer.pubkey();
+    let unordered = RatingConfig { bronze: 30, ..RatingConfig::default() };
+    let result = try_send(&mut context, &[set_rating_config_ix(&program_id, &authority, unordered)], &[]).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+
+    // One like is Bronze under the new config rather than unrated under the default
+    send(&mut context, &[set_rating_config_ix(&program_id, &authority, config)], &[]).await;
+    let state = unpack_program_state_from_slice(&account_data(&mut context, &program_state_pda(&program_id).0).await).unwrap();
+    assert_eq!(state.rating_config, config);
+    send(&mut context, &[like_post_ix(&program_id, &bob.pubkey(), &post_address, &alice_profile, "alice", &bob_profile, 1)], &[&bob]).await;
+    assert_eq!(post(&mut context, &post_address).await.rating, PostRating::Bronze);
+}
+
+#[tokio::test]
+async fn initialize_program_records_the_authority_once() {
+    let (mut context, program_id) = start().await;
+    let state = unpack_program_state_from_slice(&account_data(&mut context, &program_state_pda(&program_id).0).await).unwrap();
+    assert_eq!(state.authority, context.payer.pubkey());
+
+    // Nobody can take over the program by initializing it again
+    let mallory = funded_user(&mut context).await;
+    let result = try_send(&mut context, &[initialize_program_ix(&program_id, &mallory.pubkey())], &[&mallory]).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::AccountAlreadyInitialized)
+    );
+
+    // Admin instructions check the signer against the recorded authority
+    let mallory_profile = create_profile(&mut context, &program_id, &mallory, "mallory").await;
+    let ix = set_verification_level_ix(&program_id, &mallory.pubkey(), &mallory_profile, VerificationLevel::Notable);
+    assert_custom_error(try_send(&mut context, &[ix], &[&mallory]).await, BlocksError::Unauthorized);
+}
+
+#[tokio::test]
+async fn initialize_program_creates_zeroed_state_once() {
+    let (mut context, program_id) = start().await;
+    let address = program_state_pda(&program_id).0;
+    let account = context.banks_client.get_account(address).await.unwrap().unwrap();
+    assert_eq!(account.owner, program_id);
+
+    let state = unpack_program_state_from_slice(&account.data).unwrap();
+    assert!(state.is_initialized);
+    assert_eq!((state.profiles_count, state.posts_count, state.communities_count), (0, 0, 0));
+    assert_eq!(state.rating_config, RatingConfig::default());
+
+    // `start` already initialized the program, so the same call again fails
+    let authority = context.payer.pubkey();
+    refresh_blockhash(&mut context).await;
+    let result = try_send(&mut context, &[initialize_program_ix(&program_id, &authority)], &[]).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::AccountAlreadyInitialized)
+    );
+}
+
+#[tokio::test]
+async fn post_images_are_validated_per_uri() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let post_ix = |images: Vec<String>| create_post_ix(&program_id, &alice.pubkey(), 1, &alice_profile, "alice", "pics".to_string(), images, None, true);
+    let uri = |prefix: &str, len: usize| format!("{}{}", prefix, "x".repeat(len - prefix.len()));
+
+    let cases = [
+        (vec![uri("https://", MAX_URI_LEN + 1)], BlocksError::InvalidImageUri),
+        (vec!["ftp://host/cat.png".to_string()], BlocksError::InvalidImageUri),
+        (vec!["ipfs://cat".to_string(); MAX_POST_IMAGES + 1], BlocksError::ContentTooLong),
+    ];
+    for (images, error) in cases {
+        assert_custom_error(try_send(&mut context, &[post_ix(images)], &[&alice]).await, error);
+    }
+
+    let images = vec![uri("https://", MAX_URI_LEN), "http://host/a.png".to_string(), "ipfs://cid".to_string()];
+    send(&mut context, &[post_ix(images.clone())], &[&alice]).await;
+    assert_eq!(post(&mut context, &post_pda(&program_id, &alice.pubkey(), 1).0).await.images, images);
+}
+
+#[tokio::test]
+async fn content_hashes_are_stored_when_given() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let ix = create_profile_ix(&program_id, &alice.pubkey(), "alice".to_string(), String::new(), "ipfs://avatar".to_string(), String::new(), Some([1; 32]), None);
+    send(&mut context, &[ix], &[&alice]).await;
+    let alice_profile = profile_pda(&program_id, &alice.pubkey(), "alice").0;
+    let stored = profile(&mut context, &alice_profile).await;
+    assert_eq!((stored.profile_image_hash, stored.cover_image_hash), (Some([1; 32]), None));
+
+    for (post_id, content_hash) in [(1, Some([2; 32])), (2, None)] {
+        let content = format!("media {}", post_id);
+        let ix = create_post_ix(&program_id, &alice.pubkey(), post_id, &alice_profile, "alice", content, vec![], content_hash, true);
+        send(&mut context, &[ix], &[&alice]).await;
+        assert_eq!(post(&mut context, &post_pda(&program_id, &alice.pubkey(), post_id).0).await.content_hash, content_hash);
+    }
+}
+
+#[tokio::test]
+async fn profile_stats_match_the_stored_profile() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    create_post(&mut context, &program_id, &alice, &alice_profile, "gm").await;
+    let now = advance_clock(&mut context, 0).await;
+
+    let stats = profile_stats(&profile(&mut context, &alice_profile).await, now);
+    // New profiles start at a UCR of 100, in the valuable band
+    assert!(stats.tier == UcrTier::ValuableContributor);
+    assert_eq!(stats.remaining_daily_posts, DAILY_POSTS_VALUABLE_CONTRIBUTOR - 1);
+    assert!(!stats.is_verified && !stats.meets_verification_min_posts);
+
+    // Tier boundaries are inclusive at the top of each band
+    for (score, tier) in [
+        (UCR_TOP_CONTRIBUTOR, UcrTier::TopContributor),
+        (UCR_TOP_CONTRIBUTOR - 1, UcrTier::ValuableContributor),
+        (UCR_VALUABLE_CONTRIBUTOR - 1, UcrTier::AverageContributor),
+        (UCR_LOW_VALUE_CONTRIBUTOR, UcrTier::LowValueContributor),
+        (UCR_SPAM_USER, UcrTier::SpamUser),
+    ] {
+        set_ucr(&mut context, &alice_profile, score).await;
+        assert!(profile_stats(&profile(&mut context, &alice_profile).await, now).tier == tier);
+    }
+
+    // The post minimum is met exactly at VERIFICATION_MIN_POSTS
+    edit_profile(&mut context, &alice_profile, |profile| profile.posts_count = VERIFICATION_MIN_POSTS - 1).await;
+    assert!(!profile_stats(&profile(&mut context, &alice_profile).await, now).meets_verification_min_posts);
+    edit_profile(&mut context, &alice_profile, |profile| profile.posts_count = VERIFICATION_MIN_POSTS).await;
+    assert!(profile_stats(&profile(&mut context, &alice_profile).await, now).meets_verification_min_posts);
+}
+
+#[tokio::test]
+async fn community_posts_require_membership() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let (community_address, community_id) = create_community(&mut context, &program_id, &alice, "rustaceans").await;
+    let post_ix = create_post_in_community_ix(
+        &program_id,
+        &bob.pubkey(),
+        1,
+        &bob_profile,
+        "bob",
+        &community_address,
+        community_id,
+        "hello crabs".to_string(),
+        vec![],
+        None,
+    );
+
+    assert_custom_error(try_send(&mut context, std::slice::from_ref(&post_ix), &[&bob]).await, BlocksError::NotAMember);
+    assert_eq!(profile(&mut context, &bob_profile).await.posts_count, 0);
+
+    send(&mut context, &[join_community_ix(&program_id, &bob.pubkey(), &community_address, community_id, 0)], &[&bob]).await;
+    refresh_blockhash(&mut context).await;
+    send(&mut context, &[post_ix], &[&bob]).await;
+    let post_address = post_pda(&program_id, &bob.pubkey(), 1).0;
+    assert_eq!(post(&mut context, &post_address).await.community, Some(community_address));
+}
+
+#[tokio::test]
+async fn members_only_actions_reject_non_members() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let (community_address, community_id) = create_community(&mut context, &program_id, &alice, "rustaceans").await;
+    let ix = create_post_in_community_ix(
+        &program_id,
+        &alice.pubkey(),
+        1,
+        &alice_profile,
+        "alice",
+        &community_address,
+        community_id,
+        "welcome".to_string(),
+        vec![],
+        None,
+    );
+    send(&mut context, &[ix], &[&alice]).await;
+    let post_address = post_pda(&program_id, &alice.pubkey(), 1).0;
+
+    let ix = leave_community_ix(&program_id, &bob.pubkey(), &community_address, community_id, 0);
+    assert_custom_error(try_send(&mut context, &[ix], &[&bob]).await, BlocksError::NotAMember);
+    let ix = moderate_post_ix(&program_id, &bob.pubkey(), &community_address, &post_address, 1);
+    assert_custom_error(try_send(&mut context, &[ix], &[&bob]).await, BlocksError::NotAMember);
+    let ix = add_moderator_ix(&program_id, &alice.pubkey(), &community_address, &bob.pubkey());
+    assert_custom_error(try_send(&mut context, &[ix], &[&alice]).await, BlocksError::NotAMember);
+    assert_eq!(community(&mut context, &community_address).await.member_count, 1);
+}
+
+#[tokio::test]
+async fn owners_pin_and_unpin_their_own_posts() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let alice_post = create_post(&mut context, &program_id, &alice, &alice_profile, "pin me").await;
+    let bob_post = create_post(&mut context, &program_id, &bob, &bob_profile, "not yours").await;
+
+    let ix = pin_post_ix(&program_id, &alice.pubkey(), &alice_profile, &bob_post, 1);
+    assert_custom_error(try_send(&mut context, &[ix], &[&alice]).await, BlocksError::NotPostOwner);
+
+    send(&mut context, &[pin_post_ix(&program_id, &alice.pubkey(), &alice_profile, &alice_post, 1)], &[&alice]).await;
+    assert_eq!(profile(&mut context, &alice_profile).await.pinned_post, Some(alice_post));
+    send(&mut context, &[unpin_post_ix(&program_id, &alice.pubkey(), &alice_profile)], &[&alice]).await;
+    assert_eq!(profile(&mut context, &alice_profile).await.pinned_post, None);
+
+    // Communities pin through their owner, and only their own posts
+    let (community_address, community_id) = create_community(&mut context, &program_id, &alice, "rustaceans").await;
+    let ix = create_post_in_community_ix(
+        &program_id,
+        &alice.pubkey(),
+        2,
+        &alice_profile,
+        "alice",
+        &community_address,
+        community_id,
+        "rules".to_string(),
+        vec![],
+        None,
+    );
+    send(&mut context, &[ix], &[&alice]).await;
+    let community_post = post_pda(&program_id, &alice.pubkey(), 2).0;
+    let ix = pin_community_post_ix(&program_id, &bob.pubkey(), &community_address, &community_post, 2);
+    assert_custom_error(try_send(&mut context, &[ix], &[&bob]).await, BlocksError::NotCommunityOwner);
+    let ix = pin_community_post_ix(&program_id, &alice.pubkey(), &community_address, &alice_post, 1);
+    assert_eq!(
+        try_send(&mut context, &[ix], &[&alice]).await.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+
+    send(&mut context, &[pin_community_post_ix(&program_id, &alice.pubkey(), &community_address, &community_post, 2)], &[&alice]).await;
+    assert_eq!(community(&mut context, &community_address).await.pinned_post, Some(community_post));
+    send(&mut context, &[unpin_community_post_ix(&program_id, &alice.pubkey(), &community_address)], &[&alice]).await;
+    assert_eq!(community(&mut context, &community_address).await.pinned_post, None);
+}
+
+#[tokio::test]
+async fn bookmarks_are_created_once_and_removed() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (post_address, _, _) = engagement_setup(&mut context, &program_id, &alice, &bob).await;
+    let bookmark_address = bookmark_pda(&program_id, &bob.pubkey(), &post_address).0;
+
+    // A post id that was never issued has no account to bookmark
+    let missing = post_pda(&program_id, &alice.pubkey(), 9).0;
+    let ix = bookmark_post_ix(&program_id, &bob.pubkey(), &missing, 9);
+    assert_custom_error(try_send(&mut context, &[ix], &[&bob]).await, BlocksError::PostNotFound);
+
+    let bookmark = bookmark_post_ix(&program_id, &bob.pubkey(), &post_address, 1);
+    send(&mut context, std::slice::from_ref(&bookmark), &[&bob]).await;
+    let record = unpack_bookmark_from_slice(&account_data(&mut context, &bookmark_address).await).unwrap();
+    assert_eq!((record.user, record.post), (bob.pubkey(), post_address));
+    refresh_blockhash(&mut context).await;
+    assert_custom_error(try_send(&mut context, std::slice::from_ref(&bookmark), &[&bob]).await, BlocksError::AlreadyBookmarked);
+
+    send(&mut context, &[remove_bookmark_ix(&program_id, &bob.pubkey(), &post_address)], &[&bob]).await;
+    assert!(context.banks_client.get_account(bookmark_address).await.unwrap().is_none());
+    refresh_blockhash(&mut context).await;
+    send(&mut context, &[bookmark], &[&bob]).await;
+    assert!(context.banks_client.get_account(bookmark_address).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn unpack_tells_missing_accounts_from_corrupt_ones() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "gm").await;
+    let profile_data = account_data(&mut context, &alice_profile).await;
+    let post_data = account_data(&mut context, &post_address).await;
+    let profile_not_found: ProgramError = BlocksError::ProfileNotFound.into();
+    let post_not_found: ProgramError = BlocksError::PostNotFound.into();
+
+    // Empty and zeroed buffers are accounts nobody has written yet
+    assert_eq!(unpack_profile_from_slice(&[]).err(), Some(profile_not_found.clone()));
+    assert_eq!(unpack_profile_from_slice(&[0; 512]).err(), Some(profile_not_found));
+    assert_eq!(unpack_post_from_slice(&vec![0; post_data.len()]).err(), Some(post_not_found));
+
+    // A record cut short is corrupt rather than missing
+    assert_eq!(unpack_profile_from_slice(&profile_data[..40]).err(), Some(ProgramError::InvalidAccountData));
+    assert_eq!(unpack_post_from_slice(&post_data[..post_data.len() / 2]).err(), Some(ProgramError::InvalidAccountData));
+
+    // Trailing space past the record is ignored
+    let mut padded = post_data.clone();
+    padded.extend_from_slice(&[0; 64]);
+    assert_eq!(unpack_post_from_slice(&padded).unwrap().content, "gm");
+}
+
+#[test]
+fn version_one_profiles_decode_with_defaults() {
+    let owner = Pubkey::new_unique();
+    // The version 1 layout: the fourteen original profile fields, nothing after `legacy_verified`
+    let fields = (
+        true,
+        owner,
+        "alice".to_string(),
+        "gm".to_string(),
+        String::new(),
+        String::new(),
+        1_000u64,
+        (3u64, 4u64),
+        (UCR_SPAM_USER - 5, 6u64, 2_000u64, 1u64, true),
+    );
+    let mut data = vec![AccountKind::Profile as u8, 1];
+    data.extend(fields.try_to_vec().unwrap());
+
+    let profile = unpack_profile_from_slice(&data).unwrap();
+    assert_eq!((profile.owner, profile.username.as_str(), profile.bio.as_str()), (owner, "alice", "gm"));
+    assert_eq!((profile.followers_count, profile.following_count, profile.posts_count), (3, 4, 6));
+    // Derived fields are filled in from the old ones
+    assert!(profile.ucr_tier == UcrTier::SpamUser && profile.is_spam);
+    assert_eq!(profile.last_post_id, 6);
+    assert_eq!(profile.updated_at, 1_000);
+    assert_eq!(profile.verification_level, VerificationLevel::Identity);
+    // Fields with no old counterpart start empty
+    assert_eq!((profile.pinned_post, profile.profile_image_hash), (None, None));
+    assert!(!profile.is_deleted && !profile.is_private);
+}
+
+#[tokio::test]
+async fn migrate_account_upgrades_a_version_one_profile() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let mallory = funded_user(&mut context).await;
+
+    // A profile as version 1 wrote it: the original fields in a fixed 512-byte account
+    let fields = (true, alice.pubkey(), "alice".to_string(), "gm".to_string(), String::new(), String::new(), 1_000u64, (0u64, 0u64), (150i64, 2u64, 0u64, 0u64, false));
+    let mut data = vec![AccountKind::Profile as u8, 1];
+    data.extend(fields.try_to_vec().unwrap());
+    data.resize(512, 0);
+    let address = Pubkey::new_unique();
+    let lamports = context.banks_client.get_rent().await.unwrap().minimum_balance(data.len());
+    context.set_account(&address, &Account { lamports, data, owner: program_id, executable: false, rent_epoch: 0 }.into());
+
+    let ix = migrate_account_ix(&program_id, &mallory.pubkey(), &address);
+    let result = try_send(&mut context, &[ix], &[&mallory]).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::IllegalOwner)
+    );
+
+    let migrate = migrate_account_ix(&program_id, &alice.pubkey(), &address);
+    send(&mut context, std::slice::from_ref(&migrate), &[&alice]).await;
+    let migrated = account_data(&mut context, &address).await;
+    assert_eq!(migrated[1], ACCOUNT_VERSION);
+    let profile = unpack_profile_from_slice(&migrated).unwrap();
+    assert_eq!((profile.username.as_str(), profile.last_post_id, profile.updated_at), ("alice", 2, 1_000));
+    assert!(profile.ucr_tier == UcrTier::ValuableContributor);
+    assert_eq!(profile.verification_level, VerificationLevel::None);
+
+    // A current record is left as it is
+    refresh_blockhash(&mut context).await;
+    send(&mut context, &[migrate], &[&alice]).await;
+    assert_eq!(account_data(&mut context, &address).await, migrated);
+}
+
+#[tokio::test]
+async fn profile_grows_to_fit_larger_fields() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let before = context.banks_client.get_account(alice_profile).await.unwrap().unwrap();
+
+    let bio = "b".repeat(MAX_BIO_LEN);
+    let image = format!("https://{}", "i".repeat(MAX_URI_LEN - 8));
+    let ix = update_profile_ix(&program_id, &alice.pubkey(), &alice_profile, bio.clone(), image.clone(), image.clone());
+    send(&mut context, &[ix], &[&alice]).await;
+
+    let after = context.banks_client.get_account(alice_profile).await.unwrap().unwrap();
+    assert!(after.data.len() > before.data.len());
+    let rent = context.banks_client.get_rent().await.unwrap();
+    assert!(after.lamports >= rent.minimum_balance(after.data.len()));
+    let stored = unpack_profile_from_slice(&after.data).unwrap();
+    assert_eq!((stored.bio, stored.profile_image, stored.cover_image), (bio, image.clone(), image));
+}
+
+#[tokio::test]
+async fn member_index_tracks_joins_and_leaves_across_pages() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let carol = funded_user(&mut context).await;
+    let (community_address, community_id) = create_community(&mut context, &program_id, &alice, "rustaceans").await;
+    let first_page = member_index_pda(&program_id, &community_address, 0).0;
+
+    for user in [&bob, &carol] {
+        send(&mut context, &[join_community_ix(&program_id, &user.pubkey(), &community_address, community_id, 0)], &[user]).await;
+    }
+    send(&mut context, &[leave_community_ix(&program_id, &bob.pubkey(), &community_address, community_id, 0)], &[&bob]).await;
+    let page = unpack_member_index_from_slice(&account_data(&mut context, &first_page).await).unwrap();
+    assert_eq!(page.members, vec![alice.pubkey(), carol.pubkey()]);
+
+    // Fill the first page so the next member opens a second one
+    let mut account = context.banks_client.get_account(first_page).await.unwrap().unwrap();
+    let mut full = unpack_member_index_from_slice(&account.data).unwrap();
+    full.members.resize_with(MAX_MEMBER_INDEX_LEN, Pubkey::new_unique);
+    pack_member_index_into_slice(&full, &mut account.data).unwrap();
+    context.set_account(&first_page, &account.into());
+
+    let dave = funded_user(&mut context).await;
+    let ix = join_community_ix(&program_id, &dave.pubkey(), &community_address, community_id, 0);
+    assert_custom_error(try_send(&mut context, &[ix], &[&dave]).await, BlocksError::MemberIndexPageFull);
+    send(&mut context, &[join_community_ix(&program_id, &dave.pubkey(), &community_address, community_id, 1)], &[&dave]).await;
+
+    let second_page = member_index_pda(&program_id, &community_address, 1).0;
+    let page = unpack_member_index_from_slice(&account_data(&mut context, &second_page).await).unwrap();
+    assert_eq!((page.page, page.members), (1, vec![dave.pubkey()]));
+    let membership = unpack_membership_from_slice(&account_data(&mut context, &membership_pda(&program_id, &community_address, &dave.pubkey()).0).await).unwrap();
+    assert_eq!(membership.index_page, Some(1));
+}
+
+#[tokio::test]
+async fn follow_indexes_stay_consistent_after_unfollows() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let mut followed = Vec::new();
+    for name in ["bob", "carol", "dave"] {
+        let user = funded_user(&mut context).await;
+        let user_profile = create_profile(&mut context, &program_id, &user, name).await;
+        let ix = follow_profile_ix(&program_id, &alice.pubkey(), &alice_profile, &user_profile, &user.pubkey(), 0, 0);
+        send(&mut context, &[ix], &[&alice]).await;
+        followed.push((user.pubkey(), user_profile));
+    }
+
+    let (carol, carol_profile) = followed[1];
+    let ix = unfollow_profile_ix(&program_id, &alice.pubkey(), &alice_profile, &carol_profile, &carol, 0, 0);
+    send(&mut context, &[ix], &[&alice]).await;
+
+    let following: FollowingIndex =
+        unpack_paged_index_from_slice(&account_data(&mut context, &following_index_pda(&program_id, &alice.pubkey(), 0).0).await).unwrap();
+    assert_eq!(following.following, vec![followed[0].0, followed[2].0]);
+    for (user, user_profile) in followed {
+        let followers: FollowerIndex =
+            unpack_paged_index_from_slice(&account_data(&mut context, &follower_index_pda(&program_id, &user, 0).0).await).unwrap();
+        let expected = if user == carol { vec![] } else { vec![alice.pubkey()] };
+        assert_eq!(followers.followers, expected);
+        assert_eq!(profile(&mut context, &user_profile).await.followers_count, expected.len() as u64);
+    }
+    assert_eq!(profile(&mut context, &alice_profile).await.following_count, 2);
+}
+
+#[tokio::test]
+async fn edits_are_limited_to_the_tiers_window() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "draft").await;
+    let edit = |profile: Option<(&Pubkey, &str)>, content: &str| edit_post_ix(&program_id, &alice.pubkey(), &post_address, profile, 1, content.to_string(), vec![]);
+
+    advance_clock(&mut context, EDIT_WINDOW_SECONDS as i64 - 10).await;
+    send(&mut context, &[edit(None, "second draft")], &[&alice]).await;
+
+    // Past the base window, only the valuable tier's longer window still allows edits
+    advance_clock(&mut context, 20).await;
+    assert_custom_error(try_send(&mut context, &[edit(None, "third draft")], &[&alice]).await, BlocksError::EditWindowExpired);
+    send(&mut context, &[edit(Some((&alice_profile, "alice")), "third draft")], &[&alice]).await;
+    assert_eq!(post(&mut context, &post_address).await.content, "third draft");
+
+    advance_clock(&mut context, EXTENDED_EDIT_WINDOW_SECONDS as i64).await;
+    let result = try_send(&mut context, &[edit(Some((&alice_profile, "alice")), "final")], &[&alice]).await;
+    assert_custom_error(result, BlocksError::EditWindowExpired);
+    assert_eq!(post(&mut context, &post_address).await.content, "third draft");
+}
+
+#[tokio::test]
+async fn underfunded_payer_gets_insufficient_funds() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+
+    // Leave Alice far less than the rent for any new account
+    let mut wallet = context.banks_client.get_account(alice.pubkey()).await.unwrap().unwrap();
+    wallet.lamports = 10_000;
+    context.set_account(&alice.pubkey(), &wallet.into());
+
+    let ix = create_post_ix(&program_id, &alice.pubkey(), 1, &alice_profile, "alice", "gm".to_string(), vec![], None, true);
+    assert_custom_error(try_send(&mut context, &[ix], &[&alice]).await, BlocksError::InsufficientFunds);
+    let ix = create_community_ix(&program_id, &alice.pubkey(), "rustaceans".to_string(), String::new(), String::new(), vec![]);
+    assert_custom_error(try_send(&mut context, &[ix], &[&alice]).await, BlocksError::InsufficientFunds);
+
+    assert_eq!(profile(&mut context, &alice_profile).await.posts_count, 0);
+    assert_eq!(context.banks_client.get_balance(alice.pubkey()).await.unwrap(), 10_000);
+}
+
+#[tokio::test]
+async fn reply_chains_stop_at_the_depth_limit() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let root = create_post(&mut context, &program_id, &alice, &alice_profile, "thread").await;
+
+    // Each reply answers the one before it
+    let (mut parent, mut parent_id) = (root, 1);
+    for depth in 1..=MAX_REPLY_DEPTH {
+        let reply_id = next_post_id(&mut context, &alice_profile).await;
+        let reply = try_comment(&mut context, &program_id, &alice, &alice_profile, &parent, parent_id, &alice.pubkey(), &alice_profile, vec![])
+            .await
+            .unwrap();
+        let stored = post(&mut context, &reply).await;
+        assert_eq!((stored.parent, stored.thread_root, stored.depth), (Some(parent), root, depth));
+        (parent, parent_id) = (reply, reply_id);
+    }
+
+    let result = try_comment(&mut context, &program_id, &alice, &alice_profile, &parent, parent_id, &alice.pubkey(), &alice_profile, vec![]).await;
+    assert_custom_error(result.map(|_| ()), BlocksError::ReplyDepthExceeded);
+}
+
+#[tokio::test]
+async fn authority_verifies_and_revokes_profiles() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let authority = context.payer.pubkey();
+
+    let ix = set_verification_ix(&program_id, &alice.pubkey(), &alice_profile, true);
+    assert_custom_error(try_send(&mut context, &[ix], &[&alice]).await, BlocksError::Unauthorized);
+    assert!(!profile(&mut context, &alice_profile).await.is_verified());
+
+    send(&mut context, &[set_verification_ix(&program_id, &authority, &alice_profile, true)], &[]).await;
+    let verified = profile(&mut context, &alice_profile).await;
+    assert!(verified.is_verified() && verified.legacy_verified);
+
+    send(&mut context, &[set_verification_ix(&program_id, &authority, &alice_profile, false)], &[]).await;
+    let revoked = profile(&mut context, &alice_profile).await;
+    assert!(!revoked.is_verified() && !revoked.legacy_verified);
+    assert_eq!(revoked.verification_level, VerificationLevel::None);
+}
+
+#[tokio::test]
+async fn delete_profile_refuses_while_followed() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let ix = follow_profile_ix(&program_id, &bob.pubkey(), &bob_profile, &alice_profile, &alice.pubkey(), 0, 0);
+    send(&mut context, &[ix], &[&bob]).await;
+    let delete = delete_profile_ix(&program_id, &alice.pubkey(), &alice_profile, "alice");
+
+    assert_custom_error(try_send(&mut context, std::slice::from_ref(&delete), &[&alice]).await, BlocksError::ProfileNotEmpty);
+
+    // The follower can still unfollow, after which the profile can go
+    let ix = unfollow_profile_ix(&program_id, &bob.pubkey(), &bob_profile, &alice_profile, &alice.pubkey(), 0, 0);
+    send(&mut context, &[ix], &[&bob]).await;
+    refresh_blockhash(&mut context).await;
+    send(&mut context, &[delete], &[&alice]).await;
+    assert!(context.banks_client.get_account(alice_profile).await.unwrap().is_none());
+    assert_eq!(profile(&mut context, &bob_profile).await.following_count, 0);
+}
+
+#[tokio::test]
+async fn deleting_posts_removes_their_engagement_from_the_aggregates() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let kept = create_post(&mut context, &program_id, &alice, &alice_profile, "kept").await;
+    let deleted = create_post(&mut context, &program_id, &alice, &alice_profile, "deleted").await;
+    for (post_address, post_id) in [(&kept, 1), (&deleted, 2)] {
+        let ix = like_post_ix(&program_id, &bob.pubkey(), post_address, &alice_profile, "alice", &bob_profile, post_id);
+        send(&mut context, &[ix], &[&bob]).await;
+    }
+    let comment_address = try_comment(&mut context, &program_id, &bob, &bob_profile, &deleted, 2, &alice.pubkey(), &alice_profile, vec![])
+        .await
+        .unwrap();
+    let ix = like_post_ix(&program_id, &alice.pubkey(), &comment_address, &bob_profile, "bob", &alice_profile, 1);
+    send(&mut context, &[ix], &[&alice]).await;
+
+    let ix = delete_post_ix(&program_id, &alice.pubkey(), &deleted, &alice_profile, 2, true);
+    send(&mut context, &[ix], &[&alice]).await;
+    let alice_state = profile(&mut context, &alice_profile).await;
+    assert_eq!(alice_state.total_likes_received, 1);
+    assert_eq!(alice_state.total_comments_received, 0);
+
+    // The parent is gone, so the comment is closed without touching it
+    let ix = delete_comment_ix(&program_id, &bob.pubkey(), &comment_address, &deleted, &bob_profile, 1, 2, false);
+    send(&mut context, &[ix], &[&bob]).await;
+    assert_eq!(profile(&mut context, &bob_profile).await.total_likes_received, 0);
+}
+
+#[tokio::test]
+async fn delete_post_refuses_comments() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "hello").await;
+    let comment_address = try_comment(&mut context, &program_id, &bob, &bob_profile, &post_address, 1, &alice.pubkey(), &alice_profile, vec![])
+        .await
+        .unwrap();
+
+    let ix = delete_post_ix(&program_id, &bob.pubkey(), &comment_address, &bob_profile, 1, false);
+    let result = try_send(&mut context, &[ix], &[&bob]).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+    assert!(context.banks_client.get_account(comment_address).await.unwrap().is_some());
+    assert_eq!(post(&mut context, &post_address).await.comments, 1);
+}
+
+#[tokio::test]
+async fn edit_window_comes_from_the_registered_profile_only() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "draft").await;
+
+    // A second profile account Alice owns, but not the one her username points to
+    let spare_profile = Pubkey::new_unique();
+    let account = context.banks_client.get_account(alice_profile).await.unwrap().unwrap();
+    context.set_account(&spare_profile, &account.into());
+    let ix = edit_post_ix(&program_id, &alice.pubkey(), &post_address, Some((&spare_profile, "alice")), 1, "edited".to_string(), vec![]);
+    let result = try_send(&mut context, &[ix], &[&alice]).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+
+    // The profile alone is not enough; its registry entry must come with it
+    let mut ix = edit_post_ix(&program_id, &alice.pubkey(), &post_address, Some((&alice_profile, "alice")), 1, "edited".to_string(), vec![]);
+    ix.accounts.pop();
+    let result = try_send(&mut context, &[ix], &[&alice]).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys)
+    );
+    assert_eq!(post(&mut context, &post_address).await.content, "draft");
+}
+
+#[tokio::test]
+async fn counters_at_zero_fail_with_arithmetic_overflow() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let bob_profile = create_profile(&mut context, &program_id, &bob, "bob").await;
+    let post_address = create_post(&mut context, &program_id, &alice, &alice_profile, "counted").await;
+    let ix = follow_profile_ix(&program_id, &alice.pubkey(), &alice_profile, &bob_profile, &bob.pubkey(), 0, 0);
+    send(&mut context, &[ix], &[&alice]).await;
+
+    // Counters already at zero must not be silently left there
+    edit_profile(&mut context, &bob_profile, |profile| profile.followers_count = 0).await;
+    let ix = unfollow_profile_ix(&program_id, &alice.pubkey(), &alice_profile, &bob_profile, &bob.pubkey(), 0, 0);
+    assert_custom_error(try_send(&mut context, &[ix], &[&alice]).await, BlocksError::ArithmeticOverflow);
+
+    edit_profile(&mut context, &alice_profile, |profile| profile.posts_count = 0).await;
+    let ix = delete_post_ix(&program_id, &alice.pubkey(), &post_address, &alice_profile, 1, false);
+    assert_custom_error(try_send(&mut context, &[ix], &[&alice]).await, BlocksError::ArithmeticOverflow);
+    assert!(context.banks_client.get_account(post_address).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn set_verification_leaves_an_unchanged_profile_untouched() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let alice_profile = create_profile(&mut context, &program_id, &alice, "alice").await;
+    let authority = context.payer.pubkey();
+    edit_profile(&mut context, &alice_profile, |profile| profile.set_verification_level(VerificationLevel::Organization)).await;
+    let before = profile(&mut context, &alice_profile).await.updated_at;
+
+    advance_clock(&mut context, 5).await;
+    send(&mut context, &[set_verification_ix(&program_id, &authority, &alice_profile, true)], &[]).await;
+
+    let unchanged = profile(&mut context, &alice_profile).await;
+    assert_eq!(unchanged.verification_level, VerificationLevel::Organization);
+    assert_eq!(unchanged.updated_at, before);
+}
+
+#[tokio::test]
+async fn approving_a_follow_respects_blocks_placed_since_the_request() {
+    let (mut context, program_id) = start().await;
+    let alice = funded_user(&mut context).await;
+    let bob = funded_user(&mut context).await;
+    let (alice_profile, bob_profile) = request_private_follow(&mut context, &program_id, &alice, &bob).await;
+    let approve = approve_follow_ix(&program_id, &bob.pubkey(), &bob_profile, &alice.pubkey(), &alice_profile, 0, 0);
+
+    // Bob blocks the requester
+    send(&mut context, &[block_user_ix(&program_id, &bob.pubkey(), &alice.pubkey())], &[&bob]).await;
+    assert_custom_error(try_send(&mut context, std::slice::from_ref(&approve), &[&bob]).await, BlocksError::UserBlocked);
+
+    // The requester blocks Bob
+    send(&mut context, &[unblock_user_ix(&program_id, &bob.pubkey(), &alice.pubkey())], &[&bob]).await;
+    send(&mut context, &[block_user_ix(&program_id, &alice.pubkey(), &bob.pubkey())], &[&alice]).await;
+    refresh_blockhash(&mut context).await;
+    assert_custom_error(try_send(&mut context, std::slice::from_ref(&approve), &[&bob]).await, BlocksError::UserBlocked);
+
+    assert_eq!(profile(&mut context, &bob_profile).await.followers_count, 0);
+    let record = follow_record_pda(&program_id, &alice.pubkey(), &bob.pubkey()).0;
+    assert!(context.banks_client.get_account(record).await.unwrap().is_none());
+}